@@ -14,4 +14,6 @@ pub enum AccountType {
     DelegationRecord,
     ProposalVoteWeight,
     ProposalOption,
+    SignedVoteNonceRecord,
+    Realm,
 }
@@ -553,6 +553,122 @@ pub enum GovernanceError {
     // VotePowerOriginRecordAlreadyExist
     #[error("VotePowerOriginRecordAlreadyExist")]
     VotePowerOriginRecordAlreadyExist,
+
+    /// Governance is frozen
+    #[error("Governance is frozen")]
+    GovernanceFrozen,
+
+    /// Invalid parent governance
+    #[error("Invalid parent governance")]
+    InvalidParentGovernance,
+
+    /// Governance already has a parent
+    #[error("Governance already has a parent")]
+    GovernanceAlreadyHasParent,
+
+    /// Scope is not delegated by the parent governance
+    #[error("Scope is not delegated by the parent governance")]
+    ScopeNotDelegatedByParent,
+
+    /// Too many delegated scopes
+    #[error("Too many delegated scopes")]
+    TooManyDelegatedScopes,
+
+    /// Governance still has children
+    #[error("Governance still has children")]
+    GovernanceHasChildren,
+
+    /// Signed vote nonce already used
+    #[error("Signed vote nonce already used")]
+    SignedVoteNonceAlreadyUsed,
+
+    /// Invalid signed vote
+    #[error("Invalid signed vote")]
+    InvalidSignedVote,
+
+    /// Voting has not started
+    #[error("Voting has not started")]
+    VotingNotStarted,
+
+    /// Invalid start voting time
+    #[error("Invalid start voting time")]
+    InvalidStartVotingTime,
+
+    /// Governance is already in the Realm
+    #[error("Governance is already in the Realm")]
+    GovernanceAlreadyInRealm,
+
+    /// Governance is paused
+    #[error("Governance is paused")]
+    GovernancePaused,
+
+    /// Invalid pause duration
+    #[error("Invalid pause duration")]
+    InvalidPauseDuration,
+
+    /// Invalid guardian for Governance
+    #[error("Invalid guardian for Governance")]
+    InvalidGuardianForGovernance,
+
+    /// Scope is not allowed to pause or unpause the Governance
+    #[error("Scope is not allowed to pause or unpause the Governance")]
+    InvalidScopeForPause,
+
+    /// Invalid State: Can't edit source
+    #[error("Invalid State: Can't edit source")]
+    InvalidStateCannotEditSource,
+
+    /// Only the last option can be removed
+    #[error("Only the last option can be removed")]
+    CanOnlyRemoveLastOption,
+
+    /// Option still has transactions
+    #[error("Option still has transactions")]
+    OptionHasTransactions,
+
+    /// Deny option can only be removed when it is the only option
+    #[error("Deny option can only be removed when it is the only option")]
+    CannotRemoveDenyOption,
+
+    /// Invalid option for transaction
+    #[error("Invalid option for transaction")]
+    InvalidOptionForTransaction,
+
+    /// The option is not among the winning options
+    #[error("The option is not among the winning options")]
+    OptionNotWinning,
+
+    /// Not enough budget
+    #[error("Not enough budget")]
+    NotEnoughBudget,
+
+    /// Budget can not exceed the deposit
+    #[error("Budget can not exceed the deposit")]
+    InvalidBudget,
+
+    /// Budget is committed to delegations
+    #[error("Budget is committed to delegations")]
+    BudgetCommitted,
+
+    /// Guardian can not pause the Governance again before the cooldown ends
+    #[error("Guardian can not pause the Governance again before the cooldown ends")]
+    GuardianPauseCooldown,
+
+    /// Child governance has to be frozen before it is dissolved
+    #[error("Child governance has to be frozen before it is dissolved")]
+    ChildGovernanceNotFrozen,
+
+    /// Vote power comes from an expired tag record
+    #[error("Vote power comes from an expired tag record")]
+    VotePowerExpired,
+
+    /// Tag record deposit is no longer backed by the tag record
+    #[error("Tag record deposit is no longer backed by the tag record")]
+    TagDepositNotBacked,
+
+    /// Tag record deposit is still backed by a valid tag record
+    #[error("Tag record deposit is still backed by a valid tag record")]
+    TagDepositStillBacked,
 }
 impl PrintProgramError for GovernanceError {
     fn print<E>(&self) {
@@ -1,8 +1,11 @@
 use std::collections::HashSet;
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use lsignforme::instruction::SignerMaybeSignForMe;
+use ltag::condition::TagCondition;
 use shared::content::ContentSource;
 use solana_program::{
+    clock::UnixTimestamp,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     system_program, sysvar,
@@ -10,7 +13,7 @@ use solana_program::{
 
 use crate::state::{
     delegation::scope_delegation_record_account::get_scope_delegation_account_program_address,
-    governance::get_governance_address,
+    governance::{get_governance_address, PauseConfig},
     native_treasury::get_native_treasury_address,
     proposal::{
         get_proposal_address,
@@ -18,10 +21,15 @@ use crate::state::{
         proposal_transaction::{get_proposal_transaction_address, ConditionedInstruction},
         VoteType,
     },
-    realm::{get_realm_mint_authority_program_address, get_realm_mint_program_address},
+    realm::{
+        get_realm_address, get_realm_mint_authority_program_address, get_realm_mint_program_address,
+    },
     scopes::scope::{get_scope_program_address, ScopeConfig, VotePowerUnit},
+    signed_vote::{get_signed_vote_nonce_record_address, SignedVote},
     token_owner_budget_record::get_token_owner_budget_record_address,
-    vote_power_origin_record::get_vote_power_origin_record_address,
+    vote_power_origin_record::{
+        get_indexed_vote_power_origin_record_address, get_vote_power_origin_record_address,
+    },
     vote_power_owner_record::get_vote_power_owner_record_address,
     vote_record::get_vote_record_address,
 };
@@ -44,13 +52,37 @@ pub struct PostVote {
 pub enum SignedCreateProposal {
     Tag {
         record: Pubkey,
-        owner: Pubkey,
+        owner: SignerMaybeSignForMe,
     },
     Token {
         owner_record: Pubkey,
-        governing_owner: Pubkey,
+        governing_owner: SignerMaybeSignForMe,
     },
+    Tags {
+        owner: SignerMaybeSignForMe,
+        condition: TagCondition,
+    },
+}
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
+pub struct SignedVoteArgs {
+    pub signature_instruction_index: u16,
+    pub vote_record_bump_seed: u8,
+    pub nonce_record_bump_seed: u8,
+}
+
+/// A vote signed off-chain, with the accounts needed to cast it
+pub struct CastSignedVote {
+    pub vote: SignedVote,
+
+    /// Index of the ed25519 program instruction of the transaction verifying the signature of the vote
+    pub signature_instruction_index: u16,
+    pub governance: Pubkey,
+    pub governing_owner: Pubkey,
+    pub token_record: Pubkey,
+    pub options: Vec<Pubkey>,
+    pub last_vote_record: Option<Pubkey>,
 }
+
 #[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
 pub enum PostInstruction {
     // Create channel
@@ -58,6 +90,7 @@ pub enum PostInstruction {
         vote_type: VoteType,
         scopes_count: u8,
         source: ContentSource,
+        start_voting_at: Option<UnixTimestamp>,
         bump_seed: u8,
     },
     Delegate {
@@ -123,14 +156,80 @@ pub enum PostInstruction {
         token_owner_budget_record_bump_seed: u8,
     },
 
-    FinalizeDraft,
+    FinalizeDraft {
+        start_voting_at: Option<UnixTimestamp>,
+    },
+
+    RegisterChildGovernance {
+        delegated_scopes: Vec<Pubkey>,
+    },
+    DetachChildGovernance,
+    FreezeChildGovernance {
+        frozen: bool,
+    },
+    UpdateChildGovernance {
+        delegated_scopes: Vec<Pubkey>,
+        optional_authority: Option<Pubkey>,
+    },
+    DissolveChildGovernance,
+    QueryChildGovernances,
+
+    CastSignedVotes {
+        votes: Vec<SignedVoteArgs>,
+    },
+
+    StartVoting,
+
+    CreateRealmRegistry {
+        name: String,
+        authority: Option<Pubkey>,
+        governing_mints: Vec<Pubkey>,
+        tag_record_factories: Vec<Pubkey>,
+        min_weight_to_create_governance: u64,
+        bump_seed: u8,
+    },
+    UpdateRealm {
+        authority: Option<Pubkey>,
+        governing_mints: Vec<Pubkey>,
+        tag_record_factories: Vec<Pubkey>,
+        min_weight_to_create_governance: u64,
+    },
+    AddRealmGovernance,
+    RemoveRealmGovernance,
+    LookupRealm {
+        name: String,
+    },
+
+    UpdatePauseConfig {
+        pause_config: PauseConfig,
+    },
+    PauseGovernance {
+        duration: u64,
+    },
+    UnpauseGovernance,
+
+    RemoveTransaction,
+    RemoveProposalOption,
+    UpdateProposalSource {
+        source: ContentSource,
+    },
+
+    CountVotesAll,
+
+    UpdateTokenOwnerBudget {
+        budget: u64,
+    },
+
+    ExpireTagDelegation {
+        tag_record_index: Option<u64>,
+    },
 }
 
 pub fn create_proposal(
     program_id: &Pubkey,
 
     // Accounts
-    creator: &Pubkey,
+    creator: &SignerMaybeSignForMe,
     governance: &Pubkey,
     payer: &Pubkey,
 
@@ -139,17 +238,19 @@ pub fn create_proposal(
     vote_type: VoteType,
     scopes_count: u8,
     source: &ContentSource,
+    start_voting_at: Option<UnixTimestamp>,
 ) -> Instruction {
     let (proposal_address, proposal_bump_seed) =
         get_proposal_address(program_id, governance, &proposal_index.to_le_bytes());
 
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(proposal_address, false),
         AccountMeta::new_readonly(*governance, false),
-        AccountMeta::new_readonly(*creator, true),
+        creator.get_account_meta(),
         AccountMeta::new(*payer, true),
         AccountMeta::new_readonly(system_program::id(), false),
     ];
+    creator.add_sign_for_me_account_infos(&mut accounts);
 
     Instruction {
         program_id: *program_id,
@@ -157,6 +258,7 @@ pub fn create_proposal(
             vote_type,
             source: source.clone(),
             scopes_count,
+            start_voting_at,
             bump_seed: proposal_bump_seed,
         })
         .try_to_vec()
@@ -199,15 +301,18 @@ pub fn update_governance_authority(
     // Accounts
     governance: &Pubkey,
     current_authority: Option<&Pubkey>,
+    current_authority_scope: Option<&Pubkey>,
 
     // Args
     new_authority: Option<Pubkey>,
 ) -> Instruction {
-    let mut accounts = vec![AccountMeta::new(*governance, current_authority.is_none())];
-
-    if let Some(authority) = current_authority {
-        accounts.push(AccountMeta::new_readonly(*authority, true))
-    }
+    let mut accounts = vec![AccountMeta::new(*governance, false)];
+    push_governance_accounts(
+        &mut accounts,
+        governance,
+        current_authority,
+        current_authority_scope,
+    );
     Instruction {
         program_id: *program_id,
         data: (PostInstruction::UpdateGovernanceAuthority { new_authority })
@@ -277,12 +382,64 @@ pub fn deposit_governing_tag(
             },
             tag_record_owner,
         );
+    deposit_governing_tag_with_origin_record(
+        program_id,
+        tag_record,
+        tag_record_owner,
+        tag_record_factory,
+        payer,
+        &token_origin_record_address,
+        token_origin_record_bump_seed,
+    )
+}
+
+/// Deposit a record of a factory with indexed records, every index is deposited separately
+pub fn deposit_governing_indexed_tag(
+    program_id: &Pubkey,
+    // Accounts
+    tag_record: &Pubkey,
+    tag_record_owner: &Pubkey,
+    tag_record_factory: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    index: u64,
+) -> Instruction {
+    let (token_origin_record_address, token_origin_record_bump_seed) =
+        get_indexed_vote_power_origin_record_address(
+            program_id,
+            &VotePowerUnit::Tag {
+                record_factory: *tag_record_factory,
+            },
+            tag_record_owner,
+            index,
+        );
+    deposit_governing_tag_with_origin_record(
+        program_id,
+        tag_record,
+        tag_record_owner,
+        tag_record_factory,
+        payer,
+        &token_origin_record_address,
+        token_origin_record_bump_seed,
+    )
+}
 
+fn deposit_governing_tag_with_origin_record(
+    program_id: &Pubkey,
+    // Accounts
+    tag_record: &Pubkey,
+    tag_record_owner: &Pubkey,
+    tag_record_factory: &Pubkey,
+    payer: &Pubkey,
+    token_origin_record: &Pubkey,
+    // Args
+    token_origin_record_bump_seed: u8,
+) -> Instruction {
     let accounts = vec![
         AccountMeta::new_readonly(*tag_record, false),
         AccountMeta::new_readonly(*tag_record_owner, true),
         AccountMeta::new_readonly(*tag_record_factory, false),
-        AccountMeta::new(token_origin_record_address, false),
+        AccountMeta::new(*token_origin_record, false),
         AccountMeta::new(*payer, true),
         AccountMeta::new_readonly(system_program::id(), false),
         AccountMeta::new_readonly(spl_token::id(), false),
@@ -331,15 +488,19 @@ pub fn create_delegatee(
     }
 }
 
+/// Inserts a transaction into a proposal option
+/// If the governance is a child governance, parent_scopes must provide a delegated parent scope for every instruction
 pub fn insert_transaction(
     program_id: &Pubkey,
     payer: &Pubkey,
     creator: &Pubkey,
     proposal: &Pubkey,
+    governance: &Pubkey,
     option_index: u16,
     instruction_index: u16,
     hold_up_time: u32,
     instructions: Vec<ConditionedInstruction>,
+    parent_scopes: &[Pubkey],
 ) -> Instruction {
     let proposal_transaction_address = get_proposal_transaction_address(
         program_id,
@@ -357,7 +518,11 @@ pub fn insert_transaction(
         AccountMeta::new(*payer, true), //  voter token owner record
         AccountMeta::new(system_program::id(), false),
         AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(*governance, false),
     ];
+    parent_scopes
+        .iter()
+        .for_each(|scope| accounts.push(AccountMeta::new_readonly(*scope, false)));
     let unique_scopes = instructions
         .iter()
         .map(|i| i.scope)
@@ -437,50 +602,78 @@ pub fn create_proposal_option(
 pub fn finalize_draft(
     program_id: &Pubkey,
     // Accounts
-    creator: &Pubkey,
+    creator: &SignerMaybeSignForMe,
     proposal: &Pubkey,
     governance: &Pubkey,
     scopes: &Vec<(Pubkey, SignedCreateProposal)>,
+
+    // Args
+    start_voting_at: Option<UnixTimestamp>,
 ) -> Instruction {
     let mut accounts = vec![
         AccountMeta::new(*proposal, false),
         AccountMeta::new(*governance, false),
-        AccountMeta::new_readonly(*creator, true),
+        creator.get_account_meta(),
     ];
+    creator.add_sign_for_me_account_infos(&mut accounts);
+
+    // The program verifies every owner once, so SignForMe accounts are only passed the first time
+    let mut verified_owners = vec![creator.original_signer];
     for (scope_address, signed_create_proposal) in scopes {
         accounts.push(AccountMeta::new_readonly(*scope_address, false));
         match signed_create_proposal {
             SignedCreateProposal::Tag { owner, record } => {
                 accounts.push(AccountMeta::new_readonly(*record, false));
-                accounts.push(AccountMeta::new_readonly(*owner, true));
+                add_owner_account_infos_once(owner, &mut accounts, &mut verified_owners);
             }
             SignedCreateProposal::Token {
                 governing_owner,
                 owner_record,
             } => {
                 accounts.push(AccountMeta::new_readonly(*owner_record, false));
-                accounts.push(AccountMeta::new_readonly(*governing_owner, true));
+                add_owner_account_infos_once(governing_owner, &mut accounts, &mut verified_owners);
+            }
+            SignedCreateProposal::Tags { owner, condition } => {
+                add_owner_account_infos_once(owner, &mut accounts, &mut verified_owners);
+                for address in condition.get_account_addresses(&ltag::id(), &owner.original_signer)
+                {
+                    accounts.push(AccountMeta::new_readonly(address, false));
+                }
             }
         }
     }
 
     Instruction {
         program_id: *program_id,
-        data: PostInstruction::FinalizeDraft.try_to_vec().unwrap(),
+        data: (PostInstruction::FinalizeDraft { start_voting_at })
+            .try_to_vec()
+            .unwrap(),
         accounts,
     }
 }
 
+fn add_owner_account_infos_once(
+    owner: &SignerMaybeSignForMe,
+    accounts: &mut Vec<AccountMeta>,
+    verified_owners: &mut Vec<Pubkey>,
+) {
+    accounts.push(owner.get_account_meta());
+    if !verified_owners.contains(&owner.original_signer) {
+        owner.add_sign_for_me_account_infos(accounts);
+        verified_owners.push(owner.original_signer);
+    }
+}
+
 pub fn cast_vote(
     program_id: &Pubkey,
     payer: &Pubkey,
     proposal: &Pubkey,
     token_record: &Pubkey,
-    governing_owner: &Pubkey,
+    governing_owner: &SignerMaybeSignForMe,
     scope: &Pubkey,
+    governance: &Pubkey,
     options: &Vec<Pubkey>,
     last_vote_record: Option<&Pubkey>,
-    delegated: bool,
 ) -> Instruction {
     let (vote_record, vote_record_bump_seed) =
         get_vote_record_address(program_id, proposal, token_record, scope);
@@ -489,23 +682,13 @@ pub fn cast_vote(
         AccountMeta::new(*proposal, false),
         AccountMeta::new(vote_record, false),
         AccountMeta::new(*token_record, false),
-        AccountMeta::new_readonly(*governing_owner, true),
+        governing_owner.get_account_meta(),
         AccountMeta::new_readonly(*scope, false),
+        AccountMeta::new_readonly(*governance, false),
         AccountMeta::new(*payer, true),
         AccountMeta::new(system_program::id(), false),
     ];
-    match delegated {
-        false => {
-            // If not delegated, we include the budget
-            // this because delegated token owner records
-            // does not have budgets (i.e. delegated again)
-            let (budget_record_address, _) =
-                get_token_owner_budget_record_address(program_id, token_record, scope);
-            accounts.push(AccountMeta::new_readonly(budget_record_address, false));
-        }
-        true => {}
-    };
-
+    governing_owner.add_sign_for_me_account_infos(&mut accounts);
     if let Some(last_vote) = last_vote_record {
         accounts.push(AccountMeta::new(*last_vote, false));
     }
@@ -531,7 +714,7 @@ pub fn uncast_vote(
     // Accounts
     proposal: &Pubkey,
     token_record: &Pubkey,
-    governing_owner: &Pubkey,
+    governing_owner: &SignerMaybeSignForMe,
     beneficiary: &Pubkey,
 
     // Args
@@ -544,10 +727,11 @@ pub fn uncast_vote(
         AccountMeta::new(*proposal, false),
         AccountMeta::new(vote_record, false),
         AccountMeta::new(*token_record, false),
-        AccountMeta::new_readonly(*governing_owner, true),
+        governing_owner.get_account_meta(),
         AccountMeta::new_readonly(*scope, false),
-        AccountMeta::new(*beneficiary, false),
     ];
+    governing_owner.add_sign_for_me_account_infos(&mut accounts);
+    accounts.push(AccountMeta::new(*beneficiary, false));
 
     for option in options {
         accounts.push(AccountMeta::new(*option, false))
@@ -587,6 +771,36 @@ pub fn create_token_owner_budget_record(
     }
 }
 
+/// Sets the share of the deposit given to the scope of the budget
+pub fn update_token_owner_budget(
+    program_id: &Pubkey,
+
+    // Accounts
+    token_record: &Pubkey,
+    governing_owner: &Pubkey,
+    scope: &Pubkey,
+
+    // Args
+    budget: u64,
+) -> Instruction {
+    let (token_owner_budget_record, _) =
+        get_token_owner_budget_record_address(program_id, token_record, scope);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*token_record, false),
+        AccountMeta::new(token_owner_budget_record, false),
+        AccountMeta::new_readonly(*governing_owner, true),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        data: (PostInstruction::UpdateTokenOwnerBudget { budget })
+            .try_to_vec()
+            .unwrap(),
+        accounts,
+    }
+}
+
 pub fn count_vote_max_weights(
     program_id: &Pubkey,
     proposal: &Pubkey,
@@ -635,6 +849,34 @@ pub fn count_votes(
     }
 }
 
+/// Counts the votes of many options at once
+/// If all options do not fit in one transaction, the options can be split over several instructions
+pub fn count_votes_all(
+    program_id: &Pubkey,
+
+    // Accounts
+    proposal: &Pubkey,
+    deny_option: Option<&Pubkey>,
+    scopes: &[Pubkey],
+    options: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![AccountMeta::new(*proposal, false)];
+    if let Some(key) = deny_option {
+        accounts.push(AccountMeta::new(*key, false));
+    }
+    for scope in scopes {
+        accounts.push(AccountMeta::new_readonly(*scope, false));
+    }
+    for option in options {
+        accounts.push(AccountMeta::new(*option, false));
+    }
+    Instruction {
+        program_id: *program_id,
+        data: (PostInstruction::CountVotesAll).try_to_vec().unwrap(),
+        accounts,
+    }
+}
+
 pub fn create_realm(
     program_id: &Pubkey,
 
@@ -673,6 +915,7 @@ pub fn create_scope(
     payer: &Pubkey,
 
     temporary_authority: &Option<Pubkey>,
+    temporary_authority_scope: Option<&Pubkey>,
     // Args
     config: &ScopeConfig,
 ) -> Instruction {
@@ -687,6 +930,9 @@ pub fn create_scope(
 
     if let Some(signer) = temporary_authority {
         accounts.push(AccountMeta::new_readonly(*signer, true));
+        if let Some(scope) = temporary_authority_scope {
+            accounts.push(AccountMeta::new_readonly(*scope, false));
+        }
     }
 
     Instruction {
@@ -714,6 +960,7 @@ pub fn execute_transaction(
     proposal_transaction: &Pubkey,
     proposal_option: &Pubkey,
     goverance_seed: Pubkey,
+    parent_scopes: &[Pubkey],
 
     instruction_accounts: &[AccountMeta],
 ) -> Instruction {
@@ -725,6 +972,9 @@ pub fn execute_transaction(
         AccountMeta::new(*proposal_transaction, false),
         AccountMeta::new(*proposal_option, false),
     ];
+    parent_scopes
+        .iter()
+        .for_each(|scope| accounts.push(AccountMeta::new_readonly(*scope, false)));
 
     accounts.extend_from_slice(instruction_accounts);
 
@@ -745,10 +995,11 @@ pub fn delegate(
     // Accounts
     token_owner_record: &Pubkey,
     token_owner_budget_record: &Pubkey,
-    governing_owner: &Pubkey,
+    governing_owner: &SignerMaybeSignForMe,
     delegatee_token_owner_record: &Pubkey,
     delegatee_governing_owner: &Pubkey,
     payer: &Pubkey,
+    tag_record: Option<&Pubkey>,
 
     // Args
     amount: &u64,
@@ -762,16 +1013,22 @@ pub fn delegate(
             scope,
         );
 
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(delegation_record, false),
         AccountMeta::new_readonly(*token_owner_record, false),
         AccountMeta::new(*token_owner_budget_record, false),
-        AccountMeta::new_readonly(*governing_owner, true),
+        governing_owner.get_account_meta(),
         AccountMeta::new(*delegatee_token_owner_record, false),
         AccountMeta::new_readonly(*delegatee_governing_owner, false),
         AccountMeta::new(*payer, true),
         AccountMeta::new(system_program::id(), false),
     ];
+    governing_owner.add_sign_for_me_account_infos(&mut accounts);
+
+    // Tag deposits are delegated with the tag record they were made with
+    if let Some(tag_record) = tag_record {
+        accounts.push(AccountMeta::new_readonly(*tag_record, false));
+    }
 
     let instruction = PostInstruction::Delegate {
         amount: *amount,
@@ -792,7 +1049,7 @@ pub fn undelegate(
     delegation_record: &Pubkey,
     token_owner_record: &Pubkey,
     token_owner_budget_record: &Pubkey,
-    governing_owner: &Pubkey,
+    governing_owner: &SignerMaybeSignForMe,
     delegatee_token_owner_record: &Pubkey,
     delegatee_governing_owner: &Pubkey,
     beneficiary: &Pubkey,
@@ -800,15 +1057,16 @@ pub fn undelegate(
     // Args
     amount: &u64,
 ) -> Instruction {
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(*delegation_record, false),
         AccountMeta::new_readonly(*token_owner_record, false),
         AccountMeta::new(*token_owner_budget_record, false),
-        AccountMeta::new_readonly(*governing_owner, true),
+        governing_owner.get_account_meta(),
         AccountMeta::new(*delegatee_token_owner_record, false),
         AccountMeta::new_readonly(*delegatee_governing_owner, false),
         AccountMeta::new(*beneficiary, false),
     ];
+    governing_owner.add_sign_for_me_account_infos(&mut accounts);
 
     let instruction = PostInstruction::Undelegate { amount: *amount };
 
@@ -818,6 +1076,39 @@ pub fn undelegate(
         data: instruction.try_to_vec().unwrap(),
     }
 }
+
+/// Expires a delegation of a tag deposit that is no longer backed by its tag record,
+/// i.e. the record was revoked, suspended or reclaimed. Anyone can expire the delegation
+pub fn expire_tag_delegation(
+    program_id: &Pubkey,
+
+    // Accounts
+    delegation_record: &Pubkey,
+    token_owner_record: &Pubkey,
+    tag_record: &Pubkey,
+    delegatee_token_owner_record: &Pubkey,
+    payer: &Pubkey,
+
+    // Args
+    tag_record_index: Option<u64>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*delegation_record, false),
+        AccountMeta::new(*token_owner_record, false),
+        AccountMeta::new_readonly(*tag_record, false),
+        AccountMeta::new(*delegatee_token_owner_record, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    let instruction = PostInstruction::ExpireTagDelegation { tag_record_index };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
 /*
 pub fn sync_delegation(
     program_id: &Pubkey,
@@ -973,3 +1264,605 @@ pub fn create_native_treasury(
         data: instruction.try_to_vec().unwrap(),
     }
 }
+
+/// Pushes the governance account, and its authority if the governance is not signing itself.
+/// The authority of a child governance is followed by the scope delegated by the parent the instruction falls within
+fn push_governance_accounts(
+    accounts: &mut Vec<AccountMeta>,
+    governance: &Pubkey,
+    authority: Option<&Pubkey>,
+    authority_scope: Option<&Pubkey>,
+) {
+    if let Some(authority) = authority {
+        accounts.push(AccountMeta::new_readonly(*authority, true));
+        if let Some(authority_scope) = authority_scope {
+            accounts.push(AccountMeta::new_readonly(*authority_scope, false));
+        }
+    } else {
+        // Governance is expected to sign through a proposal
+        accounts
+            .iter_mut()
+            .filter(|account| &account.pubkey == governance)
+            .for_each(|account| account.is_signer = true);
+    }
+}
+
+pub fn register_child_governance(
+    program_id: &Pubkey,
+
+    // Accounts
+    child_governance: &Pubkey,
+    child_authority: Option<&Pubkey>,
+    parent_governance: &Pubkey,
+    parent_authority: Option<&Pubkey>,
+    parent_authority_scope: Option<&Pubkey>,
+    parent_ancestors: &[Pubkey],
+
+    // Args
+    delegated_scopes: Vec<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*child_governance, false),
+        AccountMeta::new(*parent_governance, false),
+    ];
+    // The child can not have a parent yet
+    push_governance_accounts(&mut accounts, child_governance, child_authority, None);
+    push_governance_accounts(
+        &mut accounts,
+        parent_governance,
+        parent_authority,
+        parent_authority_scope,
+    );
+    delegated_scopes
+        .iter()
+        .for_each(|scope| accounts.push(AccountMeta::new_readonly(*scope, false)));
+    parent_ancestors
+        .iter()
+        .for_each(|ancestor| accounts.push(AccountMeta::new_readonly(*ancestor, false)));
+
+    Instruction {
+        program_id: *program_id,
+        data: (PostInstruction::RegisterChildGovernance { delegated_scopes })
+            .try_to_vec()
+            .unwrap(),
+        accounts,
+    }
+}
+
+pub fn detach_child_governance(
+    program_id: &Pubkey,
+
+    // Accounts
+    child_governance: &Pubkey,
+    parent_governance: &Pubkey,
+    parent_authority: Option<&Pubkey>,
+    parent_authority_scope: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*child_governance, false),
+        AccountMeta::new(*parent_governance, false),
+    ];
+    push_governance_accounts(
+        &mut accounts,
+        parent_governance,
+        parent_authority,
+        parent_authority_scope,
+    );
+
+    Instruction {
+        program_id: *program_id,
+        data: PostInstruction::DetachChildGovernance.try_to_vec().unwrap(),
+        accounts,
+    }
+}
+
+pub fn freeze_child_governance(
+    program_id: &Pubkey,
+
+    // Accounts
+    child_governance: &Pubkey,
+    parent_governance: &Pubkey,
+    parent_authority: Option<&Pubkey>,
+    parent_authority_scope: Option<&Pubkey>,
+
+    // Args
+    frozen: bool,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*child_governance, false),
+        AccountMeta::new_readonly(*parent_governance, false),
+    ];
+    push_governance_accounts(
+        &mut accounts,
+        parent_governance,
+        parent_authority,
+        parent_authority_scope,
+    );
+
+    Instruction {
+        program_id: *program_id,
+        data: (PostInstruction::FreezeChildGovernance { frozen })
+            .try_to_vec()
+            .unwrap(),
+        accounts,
+    }
+}
+
+pub fn update_child_governance(
+    program_id: &Pubkey,
+
+    // Accounts
+    child_governance: &Pubkey,
+    parent_governance: &Pubkey,
+    parent_authority: Option<&Pubkey>,
+    parent_authority_scope: Option<&Pubkey>,
+
+    // Args
+    delegated_scopes: Vec<Pubkey>,
+    optional_authority: Option<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*child_governance, false),
+        AccountMeta::new_readonly(*parent_governance, false),
+    ];
+    push_governance_accounts(
+        &mut accounts,
+        parent_governance,
+        parent_authority,
+        parent_authority_scope,
+    );
+    delegated_scopes
+        .iter()
+        .for_each(|scope| accounts.push(AccountMeta::new_readonly(*scope, false)));
+
+    Instruction {
+        program_id: *program_id,
+        data: (PostInstruction::UpdateChildGovernance {
+            delegated_scopes,
+            optional_authority,
+        })
+        .try_to_vec()
+        .unwrap(),
+        accounts,
+    }
+}
+
+pub fn dissolve_child_governance(
+    program_id: &Pubkey,
+
+    // Accounts
+    child_governance: &Pubkey,
+    parent_governance: &Pubkey,
+    parent_authority: Option<&Pubkey>,
+    parent_authority_scope: Option<&Pubkey>,
+    beneficiary: &Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*child_governance, false),
+        AccountMeta::new(*parent_governance, false),
+        AccountMeta::new(*beneficiary, false),
+    ];
+    push_governance_accounts(
+        &mut accounts,
+        parent_governance,
+        parent_authority,
+        parent_authority_scope,
+    );
+
+    Instruction {
+        program_id: *program_id,
+        data: PostInstruction::DissolveChildGovernance
+            .try_to_vec()
+            .unwrap(),
+        accounts,
+    }
+}
+
+pub fn query_child_governances(
+    program_id: &Pubkey,
+
+    // Accounts
+    parent_governance: &Pubkey,
+    child_governances: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![AccountMeta::new_readonly(*parent_governance, false)];
+    child_governances
+        .iter()
+        .for_each(|child| accounts.push(AccountMeta::new_readonly(*child, false)));
+
+    Instruction {
+        program_id: *program_id,
+        data: PostInstruction::QueryChildGovernances.try_to_vec().unwrap(),
+        accounts,
+    }
+}
+
+/// Casts votes signed off-chain, the relayer pays for the vote records.
+/// The signature of every vote is verified by an ed25519 program instruction of the transaction over SignedVote::to_message
+/// (see crate::shared::ed25519::new_ed25519_verify_instruction)
+pub fn cast_signed_votes(
+    program_id: &Pubkey,
+
+    // Accounts
+    payer: &Pubkey,
+
+    // Args
+    votes: &[CastSignedVote],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+    ];
+
+    let mut args = Vec::new();
+    for signed_vote in votes {
+        let vote = &signed_vote.vote;
+        let (vote_record, vote_record_bump_seed) = get_vote_record_address(
+            program_id,
+            &vote.proposal,
+            &signed_vote.token_record,
+            &vote.scope,
+        );
+        let (nonce_record, nonce_record_bump_seed) = get_signed_vote_nonce_record_address(
+            program_id,
+            &vote.proposal,
+            &signed_vote.governing_owner,
+        );
+        args.push(SignedVoteArgs {
+            signature_instruction_index: signed_vote.signature_instruction_index,
+            vote_record_bump_seed,
+            nonce_record_bump_seed,
+        });
+
+        accounts.push(AccountMeta::new(vote.proposal, false));
+        accounts.push(AccountMeta::new(vote_record, false));
+        accounts.push(AccountMeta::new(nonce_record, false));
+        accounts.push(AccountMeta::new(signed_vote.token_record, false));
+        accounts.push(AccountMeta::new_readonly(vote.scope, false));
+        accounts.push(AccountMeta::new_readonly(signed_vote.governance, false));
+        if let Some(last_vote) = &signed_vote.last_vote_record {
+            accounts.push(AccountMeta::new(*last_vote, false));
+        }
+        for option in &signed_vote.options {
+            accounts.push(AccountMeta::new(*option, false));
+        }
+    }
+
+    Instruction {
+        program_id: *program_id,
+        data: (PostInstruction::CastSignedVotes { votes: args })
+            .try_to_vec()
+            .unwrap(),
+        accounts,
+    }
+}
+
+pub fn start_voting(
+    program_id: &Pubkey,
+    // Accounts
+    proposal: &Pubkey,
+) -> Instruction {
+    let accounts = vec![AccountMeta::new(*proposal, false)];
+
+    Instruction {
+        program_id: *program_id,
+        data: PostInstruction::StartVoting.try_to_vec().unwrap(),
+        accounts,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_realm_registry(
+    program_id: &Pubkey,
+
+    // Accounts
+    payer: &Pubkey,
+
+    // Args
+    name: &str,
+    authority: Option<Pubkey>,
+    governing_mints: Vec<Pubkey>,
+    tag_record_factories: Vec<Pubkey>,
+    min_weight_to_create_governance: u64,
+) -> Instruction {
+    let (realm_address, bump_seed) = get_realm_address(program_id, name).unwrap();
+    let mut accounts = vec![
+        AccountMeta::new(realm_address, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    if let Some(authority) = &authority {
+        accounts.push(AccountMeta::new_readonly(*authority, true));
+    }
+
+    Instruction {
+        program_id: *program_id,
+        data: (PostInstruction::CreateRealmRegistry {
+            name: name.into(),
+            authority,
+            governing_mints,
+            tag_record_factories,
+            min_weight_to_create_governance,
+            bump_seed,
+        })
+        .try_to_vec()
+        .unwrap(),
+        accounts,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_realm(
+    program_id: &Pubkey,
+
+    // Accounts
+    realm: &Pubkey,
+    realm_authority: &Pubkey,
+    payer: &Pubkey,
+
+    // Args
+    authority: Option<Pubkey>,
+    governing_mints: Vec<Pubkey>,
+    tag_record_factories: Vec<Pubkey>,
+    min_weight_to_create_governance: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*realm, false),
+        AccountMeta::new_readonly(*realm_authority, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        data: (PostInstruction::UpdateRealm {
+            authority,
+            governing_mints,
+            tag_record_factories,
+            min_weight_to_create_governance,
+        })
+        .try_to_vec()
+        .unwrap(),
+        accounts,
+    }
+}
+
+pub fn add_realm_governance(
+    program_id: &Pubkey,
+
+    // Accounts
+    realm: &Pubkey,
+    governance: &Pubkey,
+    governance_authority: Option<&Pubkey>,
+    governance_authority_scope: Option<&Pubkey>,
+    realm_authority: &Pubkey,
+    creator: &Pubkey,
+    vote_power_origin_record: Option<&Pubkey>,
+    payer: &Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*realm, false),
+        AccountMeta::new_readonly(*governance, false),
+        AccountMeta::new_readonly(*realm_authority, true),
+        AccountMeta::new_readonly(*creator, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    push_governance_accounts(
+        &mut accounts,
+        governance,
+        governance_authority,
+        governance_authority_scope,
+    );
+    if let Some(vote_power_origin_record) = vote_power_origin_record {
+        accounts.push(AccountMeta::new_readonly(*vote_power_origin_record, false));
+    }
+
+    Instruction {
+        program_id: *program_id,
+        data: PostInstruction::AddRealmGovernance.try_to_vec().unwrap(),
+        accounts,
+    }
+}
+
+pub fn remove_realm_governance(
+    program_id: &Pubkey,
+
+    // Accounts
+    realm: &Pubkey,
+    governance: &Pubkey,
+    realm_authority: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*realm, false),
+        AccountMeta::new_readonly(*governance, false),
+        AccountMeta::new(*realm_authority, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        data: PostInstruction::RemoveRealmGovernance.try_to_vec().unwrap(),
+        accounts,
+    }
+}
+
+pub fn lookup_realm(
+    program_id: &Pubkey,
+
+    // Args
+    name: &str,
+) -> Instruction {
+    let realm_address = get_realm_address(program_id, name).unwrap().0;
+    let accounts = vec![AccountMeta::new_readonly(realm_address, false)];
+
+    Instruction {
+        program_id: *program_id,
+        data: (PostInstruction::LookupRealm { name: name.into() })
+            .try_to_vec()
+            .unwrap(),
+        accounts,
+    }
+}
+
+pub fn update_pause_config(
+    program_id: &Pubkey,
+
+    // Accounts
+    governance: &Pubkey,
+    governance_authority: Option<&Pubkey>,
+    governance_authority_scope: Option<&Pubkey>,
+
+    // Args
+    pause_config: PauseConfig,
+) -> Instruction {
+    let mut accounts = vec![AccountMeta::new(*governance, false)];
+    push_governance_accounts(
+        &mut accounts,
+        governance,
+        governance_authority,
+        governance_authority_scope,
+    );
+
+    Instruction {
+        program_id: *program_id,
+        data: (PostInstruction::UpdatePauseConfig { pause_config })
+            .try_to_vec()
+            .unwrap(),
+        accounts,
+    }
+}
+
+/// Pauses the governance, signed by the guardian, or by the governance when executed through a proposal
+pub fn pause_governance(
+    program_id: &Pubkey,
+
+    // Accounts
+    governance: &Pubkey,
+    guardian: Option<&Pubkey>,
+
+    // Args
+    duration: u64,
+) -> Instruction {
+    let mut accounts = vec![AccountMeta::new(*governance, false)];
+    push_governance_accounts(&mut accounts, governance, guardian, None);
+
+    Instruction {
+        program_id: *program_id,
+        data: (PostInstruction::PauseGovernance { duration })
+            .try_to_vec()
+            .unwrap(),
+        accounts,
+    }
+}
+
+/// Unpauses the governance, only executable through a proposal
+pub fn unpause_governance(
+    program_id: &Pubkey,
+
+    // Accounts
+    governance: &Pubkey,
+) -> Instruction {
+    let accounts = vec![AccountMeta::new(*governance, true)];
+
+    Instruction {
+        program_id: *program_id,
+        data: PostInstruction::UnpauseGovernance.try_to_vec().unwrap(),
+        accounts,
+    }
+}
+
+/// Removes a transaction from a proposal option while the proposal is in draft
+pub fn remove_transaction(
+    program_id: &Pubkey,
+
+    // Accounts
+    creator: &Pubkey,
+    proposal: &Pubkey,
+    beneficiary: &Pubkey,
+
+    // Args
+    option_index: u16,
+    instruction_index: u16,
+) -> Instruction {
+    let proposal_transaction_address = get_proposal_transaction_address(
+        program_id,
+        proposal,
+        &option_index.to_le_bytes(),
+        &instruction_index.to_le_bytes(),
+    );
+    let option_address =
+        get_proposal_option_program_address(program_id, proposal, &option_index.to_le_bytes()).0;
+    let accounts = vec![
+        AccountMeta::new_readonly(*proposal, false),
+        AccountMeta::new_readonly(*creator, true),
+        AccountMeta::new(proposal_transaction_address, false),
+        AccountMeta::new(option_address, false),
+        AccountMeta::new(*beneficiary, false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        data: PostInstruction::RemoveTransaction.try_to_vec().unwrap(),
+        accounts,
+    }
+}
+
+/// Removes the last option of a proposal while the proposal is in draft
+pub fn remove_proposal_option(
+    program_id: &Pubkey,
+
+    // Accounts
+    creator: &Pubkey,
+    proposal: &Pubkey,
+    beneficiary: &Pubkey,
+
+    // Args
+    option_index: u16,
+) -> Instruction {
+    let option_address =
+        get_proposal_option_program_address(program_id, proposal, &option_index.to_le_bytes()).0;
+    let accounts = vec![
+        AccountMeta::new(option_address, false),
+        AccountMeta::new(*proposal, false),
+        AccountMeta::new_readonly(*creator, true),
+        AccountMeta::new(*beneficiary, false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        data: PostInstruction::RemoveProposalOption.try_to_vec().unwrap(),
+        accounts,
+    }
+}
+
+/// Updates the source of a proposal while the proposal is in draft
+pub fn update_proposal_source(
+    program_id: &Pubkey,
+
+    // Accounts
+    creator: &Pubkey,
+    proposal: &Pubkey,
+    payer: &Pubkey,
+
+    // Args
+    source: &ContentSource,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*proposal, false),
+        AccountMeta::new_readonly(*creator, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        data: (PostInstruction::UpdateProposalSource {
+            source: source.clone(),
+        })
+        .try_to_vec()
+        .unwrap(),
+        accounts,
+    }
+}
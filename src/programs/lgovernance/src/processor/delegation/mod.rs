@@ -1,5 +1,6 @@
 pub mod process_create_delegatee;
 pub mod process_delegate;
 pub mod process_delegate_history;
+pub mod process_expire_tag_delegation;
 pub mod process_undelegate;
 pub mod process_undelegate_history;
@@ -1,14 +1,17 @@
 //! Program state processor
 
+use crate::processor::utils::verify_signed_owner_maybe_sign_for_me;
 use crate::state::{
     delegation::scope_delegation_record_account::ScopeDelegationRecordAccount,
+    enums::GovernanceAction, scopes::scope::VotePowerUnit,
     token_owner_budget_record::get_token_owner_budget_record_data_for_token_record,
-    vote_power_origin_record::get_vote_power_origin_record_data_for_owner,
+    vote_power_origin_record::get_vote_power_origin_record_data_for_verified_owner,
     vote_power_owner_record::get_vote_power_owner_record_data_for_delegation_activity,
 };
 use borsh::BorshSerialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     pubkey::Pubkey,
     rent::Rent,
@@ -35,14 +38,31 @@ pub fn process_delegate(
 
     let payer_info = next_account_info(accounts_iter)?;
     let system_info = next_account_info(accounts_iter)?;
+    let governing_owner = verify_signed_owner_maybe_sign_for_me(
+        governing_owner_info,
+        delegatee_governing_owner_info.key,
+        GovernanceAction::Delegate,
+        accounts_iter,
+    )?;
     let rent = Rent::get()?;
+    let clock = Clock::get()?;
 
     // Load token owner record
-    let token_origin_record = get_vote_power_origin_record_data_for_owner(
+    let token_origin_record = get_vote_power_origin_record_data_for_verified_owner(
         program_id,
         token_origin_record_info,
-        governing_owner_info,
+        &governing_owner,
     )?;
+    token_origin_record.assert_not_expired(clock.unix_timestamp)?;
+
+    // Tag deposits can only be delegated while the tag record backs them, the tag record follows the SignForMe accounts
+    if let VotePowerUnit::Tag { .. } = &token_origin_record.source {
+        token_origin_record.assert_backed_by_tag_record(
+            program_id,
+            token_origin_record_info,
+            next_account_info(accounts_iter)?,
+        )?;
+    }
 
     /*
     if token_owner_record.delegated_by_scope.is_some() {
@@ -57,15 +77,11 @@ pub fn process_delegate(
         token_owner_budget_record_info,
         &token_origin_record,
         token_origin_record_info,
-        governing_owner_info,
+        &governing_owner,
     )?;
+    token_owner_budget_record.spend(amount)?;
     let scope = &token_owner_budget_record.scope;
 
-    token_owner_budget_record.amount = token_owner_budget_record
-        .amount
-        .checked_sub(amount)
-        .unwrap();
-
     token_owner_budget_record.serialize(&mut *token_owner_budget_record_info.data.borrow_mut())?;
 
     // Load delegatee token owner record
@@ -108,15 +124,20 @@ pub fn process_delegate(
         delegation_record_bump_seed,
         &token_origin_record,
         token_origin_record_info,
-        governing_owner_info,
-        &delegatee_token_owner_record_data,
+        &governing_owner,
+        &mut delegatee_token_owner_record_data,
         delegatee_vote_power_owner_record_info,
         payer_info,
         system_info,
+        clock.unix_timestamp,
     )?;
 
-    delegatee_token_owner_record_data
-        .serialize(&mut *delegatee_vote_power_owner_record_info.data.borrow_mut())?;
+    delegatee_token_owner_record_data.resize_and_serialize(
+        payer_info,
+        delegatee_vote_power_owner_record_info,
+        system_info,
+        &rent,
+    )?;
 
     /*
     VotePowerOwnerRecord::add_amount(
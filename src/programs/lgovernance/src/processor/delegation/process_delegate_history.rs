@@ -11,9 +11,11 @@ use borsh::BorshSerialize;
 
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     program_error::ProgramError,
     pubkey::Pubkey,
+    sysvar::Sysvar,
 };
 
 /// When delegating tokens, you delegate to a "token record"/"user" that might already have a few active votes
@@ -53,6 +55,11 @@ pub fn process_delegate_history(program_id: &Pubkey, accounts: &[AccountInfo]) -
 
     let scope = get_scope_data_for_governance(program_id, scope_info, &proposal.governance)?;
 
+    // Expired delegations are not added to earlier votes anymore
+    if !scope_delegation_record_data.is_included_in_vote(Clock::get()?.unix_timestamp) {
+        return Err(GovernanceError::VotePowerExpired.into());
+    }
+
     // Get delegatee info token owner record info
     /*  let delegatee_vote_power_owner_record_info: &AccountInfo =
     match delegator_or_delegatee_vote_power_owner_record_info.key
@@ -111,16 +118,19 @@ pub fn process_delegate_history(program_id: &Pubkey, accounts: &[AccountInfo]) -
             return Err(GovernanceError::InvalidSyncDirection.into());
         };
 
-        vote_record_data.assert_vote_equals(&proposal.perform_voting(
-            program_id,
-            scope_delegation_record_data.amount,
-            true,
-            &delegator_token_origin_record_data.source,
-            scope_info.key,
-            &scope,
-            proposal_account_info.key,
-            accounts_iter,
-        )?)?;
+        // Votes cast after the delegation expired do not include it
+        if scope_delegation_record_data.is_included_in_vote(vote_record_data.cast_at) {
+            vote_record_data.assert_vote_equals(&proposal.perform_voting(
+                program_id,
+                scope_delegation_record_data.amount,
+                true,
+                &delegator_token_origin_record_data.source,
+                scope_info.key,
+                &scope,
+                proposal_account_info.key,
+                accounts_iter,
+            )?)?;
+        }
         scope_delegation_record_data.last_vote_head = scope_delegation_record_data.vote_head;
         scope_delegation_record_data.vote_head = Some(*vote_record_info.key);
         scope_delegation_record_data
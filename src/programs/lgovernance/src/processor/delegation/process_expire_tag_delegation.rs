@@ -0,0 +1,123 @@
+//! Program state processor
+
+use crate::{
+    error::GovernanceError,
+    state::{
+        delegation::scope_delegation_record_account::get_delegation_record_data_for_delegator_and_delegatee,
+        scopes::scope::VotePowerUnit,
+        vote_power_origin_record::{
+            get_indexed_vote_power_origin_record_address, get_vote_power_origin_record_address,
+            get_vote_power_origin_record_data,
+        },
+        vote_power_owner_record::get_vote_power_owner_record_data,
+    },
+};
+use borsh::BorshSerialize;
+use ltag::{get_indexed_tag_record_program_address, get_tag_record_program_address};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+/// Processes ExpireTagDelegation instruction
+pub fn process_expire_tag_delegation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    tag_record_index: Option<u64>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let delegation_record_info = next_account_info(accounts_iter)?;
+    let token_origin_record_info = next_account_info(accounts_iter)?;
+    let tag_record_info = next_account_info(accounts_iter)?;
+    let delegatee_vote_power_owner_record_info = next_account_info(accounts_iter)?;
+    let payer_info = next_account_info(accounts_iter)?;
+    let system_info = next_account_info(accounts_iter)?;
+    let rent = Rent::get()?;
+    let clock = Clock::get()?;
+
+    let mut token_origin_record =
+        get_vote_power_origin_record_data(program_id, token_origin_record_info)?;
+    let record_factory = match &token_origin_record.source {
+        VotePowerUnit::Tag { record_factory } => *record_factory,
+        VotePowerUnit::Mint(_) => return Err(GovernanceError::InvalidVotePowerSource.into()),
+    };
+
+    // The tag record has to be the one the deposit was made with, even if it has been closed since
+    let governing_owner = &token_origin_record.governing_owner;
+    let (token_origin_record_address, tag_record_address) = match tag_record_index {
+        Some(index) => (
+            get_indexed_vote_power_origin_record_address(
+                program_id,
+                &token_origin_record.source,
+                governing_owner,
+                index,
+            )
+            .0,
+            get_indexed_tag_record_program_address(
+                &ltag::id(),
+                &record_factory,
+                governing_owner,
+                index,
+            )
+            .0,
+        ),
+        None => (
+            get_vote_power_origin_record_address(
+                program_id,
+                &token_origin_record.source,
+                governing_owner,
+            )
+            .0,
+            get_tag_record_program_address(&ltag::id(), &record_factory, governing_owner).0,
+        ),
+    };
+    if &token_origin_record_address != token_origin_record_info.key
+        || &tag_record_address != tag_record_info.key
+    {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if token_origin_record
+        .assert_backed_by_tag_record(program_id, token_origin_record_info, tag_record_info)
+        .is_ok()
+    {
+        return Err(GovernanceError::TagDepositStillBacked.into());
+    }
+
+    // Votes cast in the same second already include the delegation
+    let expires_at = clock.unix_timestamp.checked_add(1).unwrap();
+
+    let mut delegation_record = get_delegation_record_data_for_delegator_and_delegatee(
+        program_id,
+        delegation_record_info,
+        token_origin_record_info,
+        delegatee_vote_power_owner_record_info,
+    )?;
+    if delegation_record.is_included_in_vote(clock.unix_timestamp) {
+        let mut delegatee_token_owner_record_data =
+            get_vote_power_owner_record_data(program_id, delegatee_vote_power_owner_record_info)?;
+        delegation_record.set_expiry(Some(expires_at), &mut delegatee_token_owner_record_data);
+
+        delegation_record.serialize(&mut *delegation_record_info.data.borrow_mut())?;
+        delegatee_token_owner_record_data.resize_and_serialize(
+            payer_info,
+            delegatee_vote_power_owner_record_info,
+            system_info,
+            &rent,
+        )?;
+    }
+
+    // The deposit can not be delegated again until it is backed and deposited again
+    if !matches!(token_origin_record.expires_at, Some(current) if current <= expires_at) {
+        token_origin_record.expires_at = Some(expires_at);
+        token_origin_record.serialize(&mut *token_origin_record_info.data.borrow_mut())?;
+    }
+
+    Ok(())
+}
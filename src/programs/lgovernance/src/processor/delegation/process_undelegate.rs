@@ -2,12 +2,14 @@
 
 use crate::{
     error::GovernanceError,
+    processor::utils::verify_signed_owner_maybe_sign_for_me,
     state::{
         delegation::scope_delegation_record_account::{
             get_scope_delegation_record_data, ScopeDelegationRecordAccount,
         },
+        enums::GovernanceAction,
         token_owner_budget_record::get_token_owner_budget_record_data_for_token_record,
-        vote_power_origin_record::get_vote_power_origin_record_data_for_owner,
+        vote_power_origin_record::get_vote_power_origin_record_data_for_verified_owner,
         vote_power_owner_record::{
             get_vote_power_owner_record_data,
             get_vote_power_owner_record_data_for_delegation_activity,
@@ -40,12 +42,18 @@ pub fn process_undelegate(
     let delegatee_governing_owner_info = next_account_info(accounts_iter)?;
 
     let beneficiary_info = next_account_info(accounts_iter)?;
+    let governing_owner = verify_signed_owner_maybe_sign_for_me(
+        governing_owner_info,
+        delegatee_governing_owner_info.key,
+        GovernanceAction::Delegate,
+        accounts_iter,
+    )?;
     msg!("X {}", token_origin_record_info.data_is_empty());
 
-    let token_origin_record = get_vote_power_origin_record_data_for_owner(
+    let token_origin_record = get_vote_power_origin_record_data_for_verified_owner(
         program_id,
         token_origin_record_info,
-        governing_owner_info,
+        &governing_owner,
     )?;
     msg!("XX {}", delegation_record_info.data_is_empty());
 
@@ -54,7 +62,7 @@ pub fn process_undelegate(
         delegation_record_info,
         &token_origin_record,
         token_origin_record_info,
-        governing_owner_info,
+        &governing_owner,
         delegatee_vote_power_owner_record_info,
     )?;
     msg!("XXX");
@@ -63,7 +71,7 @@ pub fn process_undelegate(
         token_owner_budget_record_info,
         &token_origin_record,
         token_origin_record_info,
-        governing_owner_info,
+        &governing_owner,
     )?;
 
     let delegatee_token_owner_record =
@@ -89,10 +97,7 @@ pub fn process_undelegate(
     */
 
     // Update budget
-    token_owner_budget_record.amount = token_owner_budget_record
-        .amount
-        .checked_add(amount)
-        .unwrap();
+    token_owner_budget_record.unspend(amount)?;
 
     token_owner_budget_record.serialize(&mut *token_owner_budget_record_info.data.borrow_mut())?;
 
@@ -124,9 +129,6 @@ pub fn process_undelegate(
         }
     }; */
 
-    delegatee_token_owner_record_data
-        .serialize(&mut *delegatee_vote_power_owner_record_info.data.borrow_mut())?;
-
     // Update delegation, might also dispose
     ScopeDelegationRecordAccount::undelegate(
         program_id,
@@ -134,12 +136,15 @@ pub fn process_undelegate(
         delegation_record_info,
         &token_origin_record,
         token_origin_record_info,
-        governing_owner_info,
-        &delegatee_token_owner_record,
+        &governing_owner,
+        &mut delegatee_token_owner_record_data,
         delegatee_vote_power_owner_record_info,
         beneficiary_info,
     )?;
 
+    delegatee_token_owner_record_data
+        .serialize(&mut *delegatee_vote_power_owner_record_info.data.borrow_mut())?;
+
     /*  VotePowerOwnerRecord::subtract_amount(
         program_id,
         delegatee_vote_power_owner_record_info,
@@ -130,16 +130,19 @@ pub fn process_undelegate_history(program_id: &Pubkey, accounts: &[AccountInfo])
         }
 
         // Update the casted amount
-        vote_record_data.assert_vote_equals(&proposal.perform_voting(
-            program_id,
-            scope_delegation_record_data.amount,
-            false,
-            &delegator_token_origin_record_data.source,
-            scope_info.key,
-            &scope,
-            proposal_account_info.key,
-            accounts_iter,
-        )?)?;
+        // Votes cast after the delegation expired do not include it
+        if scope_delegation_record_data.is_included_in_vote(vote_record_data.cast_at) {
+            vote_record_data.assert_vote_equals(&proposal.perform_voting(
+                program_id,
+                scope_delegation_record_data.amount,
+                false,
+                &delegator_token_origin_record_data.source,
+                scope_info.key,
+                &scope,
+                proposal_account_info.key,
+                accounts_iter,
+            )?)?;
+        }
         scope_delegation_record_data.last_vote_head = Some(*vote_record_info.key);
         scope_delegation_record_data.vote_head = vote_record_data.next_vote;
         scope_delegation_record_data
@@ -8,22 +8,44 @@
 use crate::processor::{
     delegation::{
         process_create_delegatee::process_create_delegatee, process_delegate::process_delegate,
-        process_delegate_history::process_delegate_history, process_undelegate::process_undelegate,
+        process_delegate_history::process_delegate_history,
+        process_expire_tag_delegation::process_expire_tag_delegation,
+        process_undelegate::process_undelegate,
         process_undelegate_history::process_undelegate_history,
     },
-    process_count_votes::{process_count_max_vote_weights, process_count_votes},
+    process_cast_signed_votes::process_cast_signed_votes,
+    process_child_governance::{
+        process_detach_child_governance, process_dissolve_child_governance,
+        process_freeze_child_governance, process_query_child_governances,
+        process_register_child_governance, process_update_child_governance,
+    },
+    process_count_votes::{
+        process_count_max_vote_weights, process_count_votes, process_count_votes_all,
+    },
     process_create_governance::process_create_governance,
     process_create_native_treasury::process_create_native_treasury,
     process_create_proposal::process_create_proposal,
     process_create_proposal_option::process_create_proposal_option,
     process_create_token_owner_budget_record::process_create_token_owner_budget_record,
     process_deposit_governing_tag::process_deposit_governing_tag,
+    process_edit_draft::{
+        process_remove_proposal_option, process_remove_transaction, process_update_proposal_source,
+    },
     process_execute_transaction::process_execute_transaction,
     process_finalize_draft::process_finalize_draft,
     process_insert_scope::process_insert_scope,
+    process_pause_governance::{
+        process_pause_governance, process_unpause_governance, process_update_pause_config,
+    },
+    process_realm::{
+        process_add_realm_governance, process_create_realm_registry, process_lookup_realm,
+        process_remove_realm_governance, process_update_realm,
+    },
     process_scopes::process_create_scope,
+    process_start_voting::process_start_voting,
     process_unvote::process_uncast_vote,
     process_update_governance_authority::process_update_governance_authority,
+    process_update_token_owner_budget::process_update_token_owner_budget,
     process_vote::process_cast_vote,
 };
 use solana_program::{
@@ -40,6 +62,8 @@ use self::{
 use super::instruction::PostInstruction;
 
 pub mod delegation;
+pub mod process_cast_signed_votes;
+pub mod process_child_governance;
 pub mod process_count_votes;
 pub mod process_create_governance;
 pub mod process_create_native_treasury;
@@ -49,14 +73,20 @@ pub mod process_create_realm;
 pub mod process_create_token_owner_budget_record;
 pub mod process_deposit_governing_tag;
 pub mod process_deposit_governing_tokens;
+pub mod process_edit_draft;
 pub mod process_execute_transaction;
 pub mod process_finalize_draft;
 pub mod process_insert_scope;
 pub mod process_insert_transaction;
+pub mod process_pause_governance;
+pub mod process_realm;
 pub mod process_scopes;
+pub mod process_start_voting;
 pub mod process_unvote;
 pub mod process_update_governance_authority;
+pub mod process_update_token_owner_budget;
 pub mod process_vote;
+pub mod utils;
 
 pub struct Processor {}
 impl Processor {
@@ -420,6 +450,7 @@ impl Processor {
                 bump_seed,
                 scopes_count,
                 source,
+                start_voting_at,
                 vote_type,
             } => {
                 msg!("Instruction: Create proposal");
@@ -429,6 +460,7 @@ impl Processor {
                     vote_type,
                     scopes_count,
                     source,
+                    start_voting_at,
                     bump_seed,
                 )
             }
@@ -471,7 +503,14 @@ impl Processor {
                 config,
             } => {
                 msg!("Instruction: Create scope");
-                process_create_scope(program_id, accounts, &id, config, bump_seed)
+                process_create_scope(
+                    program_id,
+                    accounts,
+                    instruction_data,
+                    &id,
+                    config,
+                    bump_seed,
+                )
             }
             PostInstruction::CreateProposalOption {
                 option_type,
@@ -527,12 +566,17 @@ impl Processor {
 
             PostInstruction::UpdateGovernanceAuthority { new_authority } => {
                 msg!("Instruction: Update governance authority");
-                process_update_governance_authority(program_id, accounts, new_authority)
+                process_update_governance_authority(
+                    program_id,
+                    accounts,
+                    instruction_data,
+                    new_authority,
+                )
             }
 
-            PostInstruction::FinalizeDraft => {
+            PostInstruction::FinalizeDraft { start_voting_at } => {
                 msg!("Instruction: Finalize draft");
-                process_finalize_draft(program_id, accounts)
+                process_finalize_draft(program_id, accounts, start_voting_at)
             }
 
             PostInstruction::CountMaxVoteWeights => {
@@ -544,6 +588,14 @@ impl Processor {
                 msg!("Instruction: Count votes");
                 process_count_votes(program_id, accounts)
             }
+            PostInstruction::CountVotesAll => {
+                msg!("Instruction: Count votes all");
+                process_count_votes_all(program_id, accounts)
+            }
+            PostInstruction::UpdateTokenOwnerBudget { budget } => {
+                msg!("Instruction: Update token owner budget");
+                process_update_token_owner_budget(program_id, accounts, budget)
+            }
             PostInstruction::CreateNativeTreasury => {
                 msg!("Instruction: Create native treasury");
                 process_create_native_treasury(program_id, accounts)
@@ -596,6 +648,148 @@ impl Processor {
                 msg!("Instruction: Undelegate history");
                 process_undelegate_history(program_id, accounts)
             }
+
+            PostInstruction::RegisterChildGovernance { delegated_scopes } => {
+                msg!("Instruction: Register child governance");
+                process_register_child_governance(
+                    program_id,
+                    accounts,
+                    instruction_data,
+                    delegated_scopes,
+                )
+            }
+
+            PostInstruction::DetachChildGovernance => {
+                msg!("Instruction: Detach child governance");
+                process_detach_child_governance(program_id, accounts, instruction_data)
+            }
+
+            PostInstruction::FreezeChildGovernance { frozen } => {
+                msg!("Instruction: Freeze child governance");
+                process_freeze_child_governance(program_id, accounts, instruction_data, frozen)
+            }
+
+            PostInstruction::UpdateChildGovernance {
+                delegated_scopes,
+                optional_authority,
+            } => {
+                msg!("Instruction: Update child governance");
+                process_update_child_governance(
+                    program_id,
+                    accounts,
+                    instruction_data,
+                    delegated_scopes,
+                    optional_authority,
+                )
+            }
+
+            PostInstruction::DissolveChildGovernance => {
+                msg!("Instruction: Dissolve child governance");
+                process_dissolve_child_governance(program_id, accounts, instruction_data)
+            }
+
+            PostInstruction::QueryChildGovernances => {
+                msg!("Instruction: Query child governances");
+                process_query_child_governances(program_id, accounts)
+            }
+
+            PostInstruction::CastSignedVotes { votes } => {
+                msg!("Instruction: Cast signed votes");
+                process_cast_signed_votes(program_id, accounts, votes)
+            }
+
+            PostInstruction::StartVoting => {
+                msg!("Instruction: Start voting");
+                process_start_voting(program_id, accounts)
+            }
+
+            PostInstruction::CreateRealmRegistry {
+                name,
+                authority,
+                governing_mints,
+                tag_record_factories,
+                min_weight_to_create_governance,
+                bump_seed,
+            } => {
+                msg!("Instruction: Create realm registry");
+                process_create_realm_registry(
+                    program_id,
+                    accounts,
+                    name,
+                    authority,
+                    governing_mints,
+                    tag_record_factories,
+                    min_weight_to_create_governance,
+                    bump_seed,
+                )
+            }
+
+            PostInstruction::UpdateRealm {
+                authority,
+                governing_mints,
+                tag_record_factories,
+                min_weight_to_create_governance,
+            } => {
+                msg!("Instruction: Update realm");
+                process_update_realm(
+                    program_id,
+                    accounts,
+                    authority,
+                    governing_mints,
+                    tag_record_factories,
+                    min_weight_to_create_governance,
+                )
+            }
+
+            PostInstruction::AddRealmGovernance => {
+                msg!("Instruction: Add realm governance");
+                process_add_realm_governance(program_id, accounts, instruction_data)
+            }
+
+            PostInstruction::RemoveRealmGovernance => {
+                msg!("Instruction: Remove realm governance");
+                process_remove_realm_governance(program_id, accounts)
+            }
+
+            PostInstruction::LookupRealm { name } => {
+                msg!("Instruction: Lookup realm");
+                process_lookup_realm(program_id, accounts, name)
+            }
+
+            PostInstruction::UpdatePauseConfig { pause_config } => {
+                msg!("Instruction: Update pause config");
+                process_update_pause_config(program_id, accounts, instruction_data, pause_config)
+            }
+
+            PostInstruction::PauseGovernance { duration } => {
+                msg!("Instruction: Pause governance");
+                process_pause_governance(program_id, accounts, duration)
+            }
+
+            PostInstruction::UnpauseGovernance => {
+                msg!("Instruction: Unpause governance");
+                process_unpause_governance(program_id, accounts)
+            }
+
+            PostInstruction::RemoveTransaction => {
+                msg!("Instruction: Remove transaction");
+                process_remove_transaction(program_id, accounts)
+            }
+
+            PostInstruction::RemoveProposalOption => {
+                msg!("Instruction: Remove proposal option");
+                process_remove_proposal_option(program_id, accounts)
+            }
+
+            PostInstruction::UpdateProposalSource { source } => {
+                msg!("Instruction: Update proposal source");
+                process_update_proposal_source(program_id, accounts, source)
+            }
+
+            PostInstruction::ExpireTagDelegation { tag_record_index } => {
+                msg!("Instruction: Expire tag delegation");
+                process_expire_tag_delegation(program_id, accounts, tag_record_index)
+            }
         }
     }
 }
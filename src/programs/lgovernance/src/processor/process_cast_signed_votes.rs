@@ -0,0 +1,133 @@
+use crate::{
+    accounts::AccountType,
+    error::GovernanceError,
+    instruction::SignedVoteArgs,
+    processor::process_vote::cast_vote,
+    shared::ed25519::get_verified_message,
+    state::{
+        signed_vote::{
+            get_signed_vote_nonce_record_address_seeds, get_signed_vote_nonce_record_data,
+            SignedVote, SignedVoteNonceRecord,
+        },
+        vote_power_owner_record::get_vote_power_owner_record_data,
+    },
+};
+
+use borsh::BorshSerialize;
+use shared::account::create_and_serialize_account_verify_with_bump;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::{self, Sysvar},
+};
+
+/// Processes CastSignedVotes instruction
+/// Every vote is signed off-chain by the governing owner and verified by the ed25519 program instruction
+/// at the index given with the vote, so several instructions can cast signed votes in one transaction
+pub fn process_cast_signed_votes(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    votes: Vec<SignedVoteArgs>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let payer_info = next_account_info(accounts_iter)?;
+    let system_info = next_account_info(accounts_iter)?;
+    let instructions_sysvar_info = next_account_info(accounts_iter)?;
+
+    if !sysvar::instructions::check_id(instructions_sysvar_info.key) {
+        return Err(ProgramError::UnsupportedSysvar);
+    }
+
+    let rent = Rent::get()?;
+    for args in votes {
+        let verified_message =
+            get_verified_message(instructions_sysvar_info, args.signature_instruction_index)?;
+        let signed_vote = SignedVote::from_message(program_id, &verified_message.message)?;
+        let governing_owner = &verified_message.signer;
+        if signed_vote.options.is_empty() {
+            return Err(GovernanceError::InvalidVote.into());
+        }
+
+        let proposal_info = next_account_info(accounts_iter)?;
+        let vote_record_info = next_account_info(accounts_iter)?;
+        let nonce_record_info = next_account_info(accounts_iter)?;
+        let vote_power_owner_record_info = next_account_info(accounts_iter)?;
+        let scope_info = next_account_info(accounts_iter)?;
+        let governance_info = next_account_info(accounts_iter)?;
+
+        if &signed_vote.proposal != proposal_info.key || &signed_vote.scope != scope_info.key {
+            return Err(GovernanceError::InvalidSignedVote.into());
+        }
+
+        // Block replays of signed votes for the same voter and proposal
+        if nonce_record_info.data_is_empty() {
+            create_and_serialize_account_verify_with_bump(
+                payer_info,
+                nonce_record_info,
+                &SignedVoteNonceRecord {
+                    account_type: AccountType::SignedVoteNonceRecord,
+                    proposal: *proposal_info.key,
+                    governing_owner: *governing_owner,
+                    nonce: signed_vote.nonce,
+                },
+                &get_signed_vote_nonce_record_address_seeds(
+                    proposal_info.key,
+                    governing_owner,
+                    &[args.nonce_record_bump_seed],
+                ),
+                program_id,
+                system_info,
+                &rent,
+            )?;
+        } else {
+            let mut nonce_record_data = get_signed_vote_nonce_record_data(
+                program_id,
+                nonce_record_info,
+                proposal_info.key,
+                governing_owner,
+            )?;
+            nonce_record_data.use_nonce(signed_vote.nonce)?;
+            nonce_record_data.serialize(&mut *nonce_record_info.data.borrow_mut())?;
+        }
+
+        let token_owner_record_data =
+            get_vote_power_owner_record_data(program_id, vote_power_owner_record_info)?;
+
+        // The latest vote record (if any) and the options belong to this vote
+        let vote_accounts_count = signed_vote.options.len()
+            + if token_owner_record_data.latest_vote.is_some() {
+                1
+            } else {
+                0
+            };
+        let vote_accounts = accounts_iter
+            .as_slice()
+            .get(..vote_accounts_count)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        accounts_iter.nth(vote_accounts_count - 1);
+
+        let vote = cast_vote(
+            program_id,
+            proposal_info,
+            vote_record_info,
+            vote_power_owner_record_info,
+            token_owner_record_data,
+            governing_owner,
+            scope_info,
+            governance_info,
+            payer_info,
+            system_info,
+            args.vote_record_bump_seed,
+            &mut vote_accounts.iter(),
+        )?;
+
+        if vote != signed_vote.options {
+            return Err(GovernanceError::InvalidSignedVote.into());
+        }
+    }
+
+    Ok(())
+}
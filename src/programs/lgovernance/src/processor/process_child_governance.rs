@@ -0,0 +1,296 @@
+//! Program state processor
+
+use crate::{
+    error::GovernanceError,
+    state::{
+        governance::{
+            get_child_governance_data_for_parent, get_governance_data, ChildGovernance,
+            GovernanceV2, MAX_DELEGATED_SCOPES,
+        },
+        proposal::proposal_transaction::InstructionData,
+        scopes::scope::get_scope_data_for_governance,
+    },
+};
+use borsh::BorshSerialize;
+use shared::account::dispose_account;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::set_return_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Checks that every delegated scope is a scope of the parent governance
+fn assert_valid_delegated_scopes(
+    program_id: &Pubkey,
+    account_info_iter: &mut std::slice::Iter<AccountInfo>,
+    parent: &Pubkey,
+    delegated_scopes: &[Pubkey],
+) -> Result<(), ProgramError> {
+    if delegated_scopes.len() > MAX_DELEGATED_SCOPES {
+        return Err(GovernanceError::TooManyDelegatedScopes.into());
+    }
+    for scope in delegated_scopes {
+        let scope_info = next_account_info(account_info_iter)?;
+        if scope != scope_info.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        get_scope_data_for_governance(program_id, scope_info, parent)?;
+    }
+    Ok(())
+}
+
+/// Processes RegisterChildGovernance instruction
+pub fn process_register_child_governance(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+    delegated_scopes: Vec<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let instruction = InstructionData::from_account_infos(program_id, accounts, instruction_data);
+    let child_governance_info = next_account_info(account_info_iter)?;
+    let parent_governance_info = next_account_info(account_info_iter)?;
+
+    let mut child_governance_data = get_governance_data(program_id, child_governance_info)?;
+    let mut parent_governance_data = get_governance_data(program_id, parent_governance_info)?;
+
+    // Both the child and the parent have to agree on the relationship
+    child_governance_data.assert_governance_signed(
+        program_id,
+        child_governance_info,
+        &instruction,
+        account_info_iter,
+    )?;
+    parent_governance_data.assert_governance_signed(
+        program_id,
+        parent_governance_info,
+        &instruction,
+        account_info_iter,
+    )?;
+
+    if child_governance_data.parent.is_some() {
+        return Err(GovernanceError::GovernanceAlreadyHasParent.into());
+    }
+
+    if child_governance_info.key == parent_governance_info.key {
+        return Err(GovernanceError::InvalidParentGovernance.into());
+    }
+
+    assert_valid_delegated_scopes(
+        program_id,
+        account_info_iter,
+        parent_governance_info.key,
+        &delegated_scopes,
+    )?;
+
+    // Walk the ancestors of the parent to prevent cycles
+    let mut ancestor = parent_governance_data.parent;
+    while let Some(ancestor_key) = ancestor {
+        let ancestor_info = next_account_info(account_info_iter)?;
+        if &ancestor_key != ancestor_info.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if ancestor_info.key == child_governance_info.key {
+            return Err(GovernanceError::InvalidParentGovernance.into());
+        }
+        ancestor = get_governance_data(program_id, ancestor_info)?.parent;
+    }
+
+    child_governance_data.parent = Some(*parent_governance_info.key);
+    child_governance_data.delegated_scopes = delegated_scopes;
+    child_governance_data.frozen = false;
+    child_governance_data.serialize(&mut *child_governance_info.data.borrow_mut())?;
+
+    parent_governance_data.children_count = parent_governance_data
+        .children_count
+        .checked_add(1)
+        .unwrap();
+    parent_governance_data.serialize(&mut *parent_governance_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+/// Processes DetachChildGovernance instruction
+pub fn process_detach_child_governance(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let instruction = InstructionData::from_account_infos(program_id, accounts, instruction_data);
+    let child_governance_info = next_account_info(account_info_iter)?;
+    let parent_governance_info = next_account_info(account_info_iter)?;
+
+    let mut child_governance_data = get_child_governance_data_for_parent(
+        program_id,
+        child_governance_info,
+        parent_governance_info.key,
+    )?;
+    let mut parent_governance_data = get_governance_data(program_id, parent_governance_info)?;
+    parent_governance_data.assert_governance_signed(
+        program_id,
+        parent_governance_info,
+        &instruction,
+        account_info_iter,
+    )?;
+
+    child_governance_data.parent = None;
+    child_governance_data.delegated_scopes = Vec::new();
+    child_governance_data.frozen = false;
+    child_governance_data.serialize(&mut *child_governance_info.data.borrow_mut())?;
+
+    parent_governance_data.children_count = parent_governance_data
+        .children_count
+        .checked_sub(1)
+        .unwrap();
+    parent_governance_data.serialize(&mut *parent_governance_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+/// Processes FreezeChildGovernance instruction
+pub fn process_freeze_child_governance(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+    frozen: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let instruction = InstructionData::from_account_infos(program_id, accounts, instruction_data);
+    let child_governance_info = next_account_info(account_info_iter)?;
+    let parent_governance_info = next_account_info(account_info_iter)?;
+
+    let mut child_governance_data = get_child_governance_data_for_parent(
+        program_id,
+        child_governance_info,
+        parent_governance_info.key,
+    )?;
+    let parent_governance_data = get_governance_data(program_id, parent_governance_info)?;
+    parent_governance_data.assert_governance_signed(
+        program_id,
+        parent_governance_info,
+        &instruction,
+        account_info_iter,
+    )?;
+
+    child_governance_data.frozen = frozen;
+    child_governance_data.serialize(&mut *child_governance_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+/// Processes UpdateChildGovernance instruction
+pub fn process_update_child_governance(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+    delegated_scopes: Vec<Pubkey>,
+    optional_authority: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let instruction = InstructionData::from_account_infos(program_id, accounts, instruction_data);
+    let child_governance_info = next_account_info(account_info_iter)?;
+    let parent_governance_info = next_account_info(account_info_iter)?;
+
+    let mut child_governance_data = get_child_governance_data_for_parent(
+        program_id,
+        child_governance_info,
+        parent_governance_info.key,
+    )?;
+    let parent_governance_data = get_governance_data(program_id, parent_governance_info)?;
+    parent_governance_data.assert_governance_signed(
+        program_id,
+        parent_governance_info,
+        &instruction,
+        account_info_iter,
+    )?;
+
+    assert_valid_delegated_scopes(
+        program_id,
+        account_info_iter,
+        parent_governance_info.key,
+        &delegated_scopes,
+    )?;
+
+    child_governance_data.delegated_scopes = delegated_scopes;
+    child_governance_data.optional_authority = optional_authority;
+    child_governance_data.serialize(&mut *child_governance_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+/// Processes DissolveChildGovernance instruction
+pub fn process_dissolve_child_governance(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let instruction = InstructionData::from_account_infos(program_id, accounts, instruction_data);
+    let child_governance_info = next_account_info(account_info_iter)?;
+    let parent_governance_info = next_account_info(account_info_iter)?;
+    let beneficiary_info = next_account_info(account_info_iter)?;
+
+    let child_governance_data = get_child_governance_data_for_parent(
+        program_id,
+        child_governance_info,
+        parent_governance_info.key,
+    )?;
+    let mut parent_governance_data = get_governance_data(program_id, parent_governance_info)?;
+    parent_governance_data.assert_governance_signed(
+        program_id,
+        parent_governance_info,
+        &instruction,
+        account_info_iter,
+    )?;
+
+    if child_governance_data.children_count > 0 {
+        return Err(GovernanceError::GovernanceHasChildren.into());
+    }
+
+    // A frozen governance can not finalize or execute proposals,
+    // so proposals that are still open can not act on behalf of the child anymore
+    if !child_governance_data.frozen {
+        return Err(GovernanceError::ChildGovernanceNotFrozen.into());
+    }
+
+    parent_governance_data.children_count = parent_governance_data
+        .children_count
+        .checked_sub(1)
+        .unwrap();
+    parent_governance_data.serialize(&mut *parent_governance_info.data.borrow_mut())?;
+
+    dispose_account(child_governance_info, beneficiary_info);
+
+    Ok(())
+}
+
+/// Processes QueryChildGovernances instruction
+/// Verifies that all the provided governances are children of the parent and returns them as return data
+pub fn process_query_child_governances(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let parent_governance_info = next_account_info(account_info_iter)?;
+    get_governance_data(program_id, parent_governance_info)?;
+
+    let mut children: Vec<ChildGovernance> = Vec::new();
+    for child_governance_info in account_info_iter {
+        let child_governance_data: GovernanceV2 = get_child_governance_data_for_parent(
+            program_id,
+            child_governance_info,
+            parent_governance_info.key,
+        )?;
+        children.push(ChildGovernance {
+            governance: *child_governance_info.key,
+            delegated_scopes: child_governance_data.delegated_scopes,
+            frozen: child_governance_data.frozen,
+        });
+    }
+
+    set_return_data(&children.try_to_vec()?);
+    Ok(())
+}
@@ -4,9 +4,11 @@ use crate::{
     state::{
         enums::VoteTipping,
         proposal::{
-            get_proposal_data, proposal_option::get_proposal_option_data, OptionVoteResult,
+            get_proposal_data,
+            proposal_option::{get_proposal_option_data, ProposalOption},
+            OptionVoteResult, ProposalV2,
         },
-        scopes::scope::{get_scope_data, get_scope_data_for_governance, VotePowerUnit},
+        scopes::scope::{get_scope_data, get_scope_data_for_governance, Scope, VotePowerUnit},
     },
     tokens::spl_utils::get_spl_token_mint_supply,
 };
@@ -16,12 +18,14 @@ use ltag::state::TagRecordFactoryAccount;
 use shared::account::get_account_data;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    clock::Clock,
+    clock::{Clock, UnixTimestamp},
     entrypoint::ProgramResult,
     msg,
+    program_error::ProgramError,
     pubkey::Pubkey,
     sysvar::Sysvar,
 };
+use std::slice::Iter;
 
 // This process will do final vote count, or can be used for vote tipping
 pub fn process_count_votes(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
@@ -31,6 +35,13 @@ pub fn process_count_votes(program_id: &Pubkey, accounts: &[AccountInfo]) -> Pro
     let proposal_deny_option_info = next_account_info(accounts_iter)?;
     let mut proposal = get_proposal_data(program_id, proposal_account_info)?;
     let current_unix_timestamp = Clock::get()?.unix_timestamp;
+    if proposal.state == ProposalState::Scheduled {
+        return Err(GovernanceError::VotingNotStarted.into());
+    }
+    // A resolved proposal can not be counted again
+    if proposal.state != ProposalState::Voting {
+        return Err(GovernanceError::InvalidProposalState.into());
+    }
     let mut deny_option = match proposal.deny_option {
         Some(key) => {
             if proposal_deny_option_info.key == proposal_option_info.key {
@@ -61,7 +72,144 @@ pub fn process_count_votes(program_id: &Pubkey, accounts: &[AccountInfo]) -> Pro
     if proposal.max_vote_weights_calculated_at.is_none() {
         return Err(GovernanceError::MaxWeightsNotCalculated.into());
     }
-    for (i, max_scope_weight) in proposal.scopes_max_vote_weight.iter().enumerate() {
+
+    let scopes = get_proposal_scopes_data(program_id, &proposal, accounts_iter)?;
+    let deny_option_index = match &deny_option {
+        Some((_, data)) => Some(data.index),
+        None if proposal.deny_option.is_some() => Some(proposal_option_data.index),
+        None => None,
+    };
+
+    count_option_votes(
+        &mut proposal,
+        &mut proposal_option_data,
+        &mut deny_option,
+        &scopes,
+        current_unix_timestamp,
+    )?;
+    proposal.try_resolve_vote_state(deny_option_index, current_unix_timestamp);
+
+    proposal.serialize(&mut *proposal_account_info.data.borrow_mut())?;
+    proposal_option_data.serialize(&mut *proposal_option_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+/// Counts the votes of every provided option in one instruction
+/// Options that already have a result are skipped, so if all options do not fit in one transaction
+/// they can be counted in several batches, the proposal is resolved when the last option is counted
+pub fn process_count_votes_all(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let proposal_account_info = next_account_info(accounts_iter)?;
+    let mut proposal = get_proposal_data(program_id, proposal_account_info)?;
+    let current_unix_timestamp = Clock::get()?.unix_timestamp;
+    if proposal.state == ProposalState::Scheduled {
+        return Err(GovernanceError::VotingNotStarted.into());
+    }
+    // A resolved proposal can not be counted again
+    if proposal.state != ProposalState::Voting {
+        return Err(GovernanceError::InvalidProposalState.into());
+    }
+    if proposal.max_vote_weights_calculated_at.is_none() {
+        return Err(GovernanceError::MaxWeightsNotCalculated.into());
+    }
+
+    let mut deny_option = match proposal.deny_option {
+        Some(key) => {
+            let proposal_deny_option_info = next_account_info(accounts_iter)?;
+            if &key != proposal_deny_option_info.key {
+                return Err(GovernanceError::InvalidDenyOptionForProposal.into());
+            }
+            let proposal_deny_option_data = get_proposal_option_data(
+                program_id,
+                proposal_deny_option_info,
+                proposal_account_info.key,
+            )?;
+            Some((proposal_deny_option_info, proposal_deny_option_data))
+        }
+        None => None,
+    };
+    let deny_option_index = deny_option.as_ref().map(|(_, data)| data.index);
+
+    let scopes = get_proposal_scopes_data(program_id, &proposal, accounts_iter)?;
+
+    for proposal_option_info in accounts_iter {
+        // The deny option is counted last
+        if proposal.deny_option.as_ref() == Some(proposal_option_info.key) {
+            continue;
+        }
+        let mut proposal_option_data =
+            get_proposal_option_data(program_id, proposal_option_info, proposal_account_info.key)?;
+        if proposal_option_data.vote_result != OptionVoteResult::None {
+            continue;
+        }
+        count_option_votes(
+            &mut proposal,
+            &mut proposal_option_data,
+            &mut deny_option,
+            &scopes,
+            current_unix_timestamp,
+        )?;
+        proposal_option_data.serialize(&mut *proposal_option_info.data.borrow_mut())?;
+    }
+
+    if let Some((proposal_deny_option_info, proposal_deny_option_data)) = &mut deny_option {
+        if proposal_deny_option_data.vote_result == OptionVoteResult::None {
+            count_option_votes(
+                &mut proposal,
+                proposal_deny_option_data,
+                &mut None,
+                &scopes,
+                current_unix_timestamp,
+            )?;
+            proposal_deny_option_data
+                .serialize(&mut *proposal_deny_option_info.data.borrow_mut())?;
+        }
+    }
+
+    proposal.try_resolve_vote_state(deny_option_index, current_unix_timestamp);
+    proposal.serialize(&mut *proposal_account_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+/// Reads the scope accounts of the proposal, in the order of the proposal scopes
+fn get_proposal_scopes_data(
+    program_id: &Pubkey,
+    proposal: &ProposalV2,
+    accounts_iter: &mut Iter<AccountInfo>,
+) -> Result<Vec<Scope>, ProgramError> {
+    let mut scopes = Vec::new();
+    for max_scope_weight in &proposal.scopes_max_vote_weight {
+        let scope_info = next_account_info(accounts_iter)?;
+        if scope_info.key != &max_scope_weight.scope {
+            return Err(GovernanceError::InvalidVotescope.into());
+        }
+        scopes.push(get_scope_data_for_governance(
+            program_id,
+            scope_info,
+            &proposal.governance,
+        )?);
+    }
+    Ok(scopes)
+}
+
+/// Counts the votes of an option against the deny option and records the option result on the proposal
+/// The deny option is None when the deny option itself is counted
+fn count_option_votes(
+    proposal: &mut ProposalV2,
+    proposal_option_data: &mut ProposalOption,
+    deny_option: &mut Option<(&AccountInfo, ProposalOption)>,
+    scopes: &[Scope],
+    current_unix_timestamp: UnixTimestamp,
+) -> ProgramResult {
+    let mut vote_time_ended = true;
+    for (i, (max_scope_weight, scope)) in proposal
+        .scopes_max_vote_weight
+        .iter()
+        .zip(scopes)
+        .enumerate()
+    {
         let option_vote_weight = proposal_option_data.vote_weights.get(i).unwrap();
         let deny_vote_weight = match &deny_option {
             Some((_, data)) => {
@@ -76,26 +224,12 @@ pub fn process_count_votes(program_id: &Pubkey, accounts: &[AccountInfo]) -> Pro
             None => 0,
         };
 
-        let scope_info = next_account_info(accounts_iter)?;
-        let scope = get_scope_data_for_governance(program_id, scope_info, &proposal.governance)?;
-
         // If not approved by one scope, proposal is defauted
-        /*
-        msg!(
-            "RESULT {} {} {} {} {}",
-            proposal_deny_option_info.key == proposal_option_info.key,
-            proposal_option_data.vote_result == OptionVoteResult::Defeated,
-            option_vote_weight.weight,
-            max_scope_weight.weight,
-            scope.config.vote_config.is_approved(
-                option_vote_weight.weight,
-                Some(deny_vote_weight),
-                max_scope_weight.weight,
-        )
-        );
-        */
         let has_vote_time_ended =
             proposal.has_vote_time_ended(&scope.config.time_config, current_unix_timestamp);
+        if !has_vote_time_ended {
+            vote_time_ended = false;
+        }
 
         // vote tipping should be done here
         if has_vote_time_ended {
@@ -149,11 +283,13 @@ pub fn process_count_votes(program_id: &Pubkey, accounts: &[AccountInfo]) -> Pro
                     {
                         proposal_option_data.vote_result = OptionVoteResult::Succeeded;
 
-                        match &mut deny_option {
+                        match deny_option {
                             Some((info, data)) => {
                                 if data.vote_result == OptionVoteResult::None {
                                     data.vote_result = OptionVoteResult::Defeated;
                                     proposal.defeated_options.push(data.index);
+                                    proposal.options_counted_count =
+                                        proposal.options_counted_count.checked_add(1).unwrap();
                                     data.serialize(&mut *info.data.borrow_mut())?;
                                 }
                             }
@@ -182,115 +318,23 @@ pub fn process_count_votes(program_id: &Pubkey, accounts: &[AccountInfo]) -> Pro
         }
     }
 
-    /*    if has_vote_time_ended {
-        if proposal_option_data.vote_result != OptionVoteResult::Defeated {
-            proposal_option_data.vote_result = OptionVoteResult::Succeeded;
-        }
+    // So if not defeated when the vote time has ended in every scope, it must have succeeded
+    if vote_time_ended && proposal_option_data.vote_result == OptionVoteResult::None {
+        proposal_option_data.vote_result = OptionVoteResult::Succeeded;
     }
-    else { // vote time has not ended
-        if proposal_option_data.vote_result != OptionVoteResult::Succeeded
-        {
-            proposal_option_data.vote_result = OptionVoteResult::None
-        }
-    } */
-    // So if not defeated, it must have succeeded
-    if proposal_option_data.vote_result == OptionVoteResult::Succeeded {
-        proposal.winning_options.push(proposal_option_data.index);
-    } else if proposal_option_data.vote_result == OptionVoteResult::Defeated {
-        proposal.defeated_options.push(proposal_option_data.index)
-    }
-
-    // Try finalize
-    msg!(
-        "{} {} {}",
-        proposal.winning_options.len(),
-        proposal.defeated_options.len(),
-        proposal.options_count
-    ); // OPTION SUCCEEDDED BUT NOT PROPOSAL?
-
-    if (proposal.defeated_options.len() + proposal.winning_options.len()) as u16
-        == proposal.options_count
-    {
-        // Done
-        match &mut proposal.vote_type {
-            VoteType::SingleChoice {} => {
-                if proposal.winning_options.len() != 1 {
-                    // We cant have two winning options for a singe choice proposal, hence this will be defeated
-                    proposal.set_completed_voting_state(
-                        ProposalState::Defeated,
-                        current_unix_timestamp,
-                    );
-                } else if let Some((_, data)) = &deny_option {
-                    if proposal.winning_options[0] == data.index {
-                        proposal.set_completed_voting_state(
-                            ProposalState::Defeated,
-                            current_unix_timestamp,
-                        );
-                    } else {
-                        proposal.set_completed_voting_state(
-                            ProposalState::Succeeded,
-                            current_unix_timestamp,
-                        );
-                    }
-                } else {
-                    proposal.set_completed_voting_state(
-                        ProposalState::Succeeded,
-                        current_unix_timestamp,
-                    );
-                }
-            }
-            VoteType::MultiChoice {
-                max_winning_options,
-                ..
-            } => {
-                if let Some(max_winning_options) = max_winning_options {
-                    if (*max_winning_options as usize) < proposal.winning_options.len() {
-                        proposal.state = ProposalState::Defeated
-                    } else {
-                        // check if deny option is in winning options
-                        if let Some((_, data)) = deny_option {
-                            if proposal
-                                .winning_options
-                                .iter()
-                                .any(|option| option == &data.index)
-                            {
-                                // Deny option can not win and also some other option
-                                proposal.set_completed_voting_state(
-                                    ProposalState::Defeated,
-                                    current_unix_timestamp,
-                                );
-                            } else {
-                                proposal.set_completed_voting_state(
-                                    ProposalState::Succeeded,
-                                    current_unix_timestamp,
-                                );
-                            }
-                        } else {
-                            proposal.set_completed_voting_state(
-                                ProposalState::Succeeded,
-                                current_unix_timestamp,
-                            );
-                        }
-                    }
-                }
-            }
+    match proposal_option_data.vote_result {
+        OptionVoteResult::Succeeded => {
+            let weights = proposal_option_data
+                .vote_weights
+                .iter()
+                .map(|scope_weight| scope_weight.weight)
+                .collect();
+            proposal.add_winning_option(proposal_option_data.index, weights)
         }
+        OptionVoteResult::Defeated => proposal.defeated_options.push(proposal_option_data.index),
+        OptionVoteResult::None => return Ok(()),
     }
-    /*
-    msg!(
-        "XXXX {} {} {}",
-        proposal_deny_option_info.key == proposal_option_info.key,
-        proposal_option_data.vote_result != OptionVoteResult::Defeated
-    );
-    */
-
-    /* if !has_vote_time_ended && proposal_option_data.vote_result == OptionVoteResult::Defeated {
-        proposal_option_data.vote_result = OptionVoteResult::None // Set to none, since we can not defeat before vote time ending
-    } */
-
     proposal.options_counted_count = proposal.options_counted_count.checked_add(1).unwrap();
-    proposal.serialize(&mut *proposal_account_info.data.borrow_mut())?;
-    proposal_option_data.serialize(&mut *proposal_option_info.data.borrow_mut())?;
 
     Ok(())
 }
@@ -302,6 +346,9 @@ pub fn process_count_max_vote_weights(
     let accounts_iter = &mut accounts.iter();
     let proposal_account_info = next_account_info(accounts_iter)?;
     let mut proposal = get_proposal_data(program_id, proposal_account_info)?;
+    if proposal.state != ProposalState::Voting {
+        return Err(GovernanceError::InvalidProposalState.into());
+    }
     proposal.max_vote_weights_calculated_at = Some(Clock::get()?.unix_timestamp);
 
     for vote_weight in &mut proposal.scopes_max_vote_weight {
@@ -2,7 +2,7 @@
 
 use crate::{
     accounts::AccountType,
-    state::governance::{get_governance_address_seeds, GovernanceV2},
+    state::governance::{get_governance_address_seeds, GovernanceV2, PauseConfig},
 };
 use shared::account::create_and_serialize_account_verify_with_bump;
 use solana_program::{
@@ -34,6 +34,13 @@ pub fn process_create_governance(
         proposals_count: 0,
         voting_proposal_count: 0,
         seed,
+        parent: None,
+        delegated_scopes: Vec::new(),
+        frozen: false,
+        children_count: 0,
+        pause_config: PauseConfig::default(),
+        paused_until: None,
+        guardian_cooldown_until: None,
     };
 
     create_and_serialize_account_verify_with_bump::<GovernanceV2>(
@@ -1,12 +1,13 @@
 use crate::{
     accounts::AccountType,
     error::GovernanceError,
+    processor::utils::verify_signed_owner_maybe_sign_for_me,
     state::{
         governance::GovernanceV2,
         proposal::{get_proposal_address_seeds},
     },
     state::{
-        enums::{InstructionExecutionFlags, ProposalState},
+        enums::{GovernanceAction, InstructionExecutionFlags, ProposalState},
         proposal::{ProposalV2, VoteType},
     },
 };
@@ -19,9 +20,8 @@ use shared::{
 };
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    clock::Clock,
+    clock::{Clock, UnixTimestamp},
     entrypoint::ProgramResult,
-    program_error::ProgramError,
     pubkey::Pubkey,
     rent::Rent,
     sysvar::Sysvar,
@@ -33,6 +33,7 @@ pub fn process_create_proposal(
     vote_type: VoteType,
     scopes_count: u8,
     source: ContentSource,
+    start_voting_at: Option<UnixTimestamp>,
     bump_seed: u8,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
@@ -41,7 +42,17 @@ pub fn process_create_proposal(
     let creator_info = next_account_info(accounts_iter)?;
     let payer_account = next_account_info(accounts_iter)?;
     let system_account = next_account_info(accounts_iter)?;
+    let creator = verify_signed_owner_maybe_sign_for_me(
+        creator_info,
+        governance_account_info.key,
+        GovernanceAction::CreateProposal,
+        accounts_iter,
+    )?;
     let governance_data = get_account_data::<GovernanceV2>(program_id, governance_account_info)?;
+    governance_data.assert_not_frozen()?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    governance_data.assert_not_paused(timestamp)?;
 
     if !proposal_account_info.data_is_empty() {
         return Err(GovernanceError::ProposalAlreadyExists.into());
@@ -50,11 +61,11 @@ pub fn process_create_proposal(
     check_account_owner(governance_account_info, program_id)?;
     check_system_program(system_account.key)?;
 
-    if !creator_info.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+    if let Some(start_voting_at) = start_voting_at {
+        if start_voting_at <= timestamp {
+            return Err(GovernanceError::InvalidStartVotingTime.into());
+        }
     }
-
-    let timestamp = Clock::get()?.unix_timestamp;
     let rent = Rent::get()?;
 
     // Create proposal
@@ -66,10 +77,10 @@ pub fn process_create_proposal(
             state: ProposalState::Draft,
             vote_type,
             governance: *governance_account_info.key,
-            creator: *creator_info.key,
+            creator,
             signatories_count: 0,
             signatories_signed_off_count: 0,
-            start_voting_at: None,
+            start_voting_at,
             draft_at: timestamp,
             signing_off_at: None,
             voting_at: None,
@@ -91,6 +102,7 @@ pub fn process_create_proposal(
             scopes_max_vote_weight: Vec::new(),
 
             source,
+            winning_options_weights: Vec::new(),
         },
         &get_proposal_address_seeds(
             governance_account_info.key,
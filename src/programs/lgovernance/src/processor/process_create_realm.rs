@@ -11,9 +11,7 @@ use crate::{
     state::realm::{
         get_realm_mint_authority_program_address, get_realm_mint_program_address_seeds,
     },
-    tokens::spl_utils::{
-        create_spl_token_account_signed_with_bump,
-    },
+    tokens::spl_utils::create_spl_token_account_signed_with_bump,
 };
 
 /// Processes create realm
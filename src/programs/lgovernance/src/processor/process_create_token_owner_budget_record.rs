@@ -56,6 +56,7 @@ pub fn process_create_token_owner_budget_record(
                     VoteSource::Tag { .. } => 1, // Binary, 1 if not delegated, else 0
                 }, */
                 scope,
+                budget: token_origin_record.amount,
                 amount: token_origin_record.amount,
                 token_owner_record: *token_origin_record_info.key,
             },
@@ -1,10 +1,12 @@
 //! Program state processor
 
+use borsh::BorshSerialize;
 use ltag::state::{get_tag_record_data_with_factory_and_signed_owner, TagRecordFactoryAccount};
 use shared::account::get_account_data;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
+    program_error::ProgramError,
     pubkey::Pubkey,
     rent::Rent,
     sysvar::Sysvar,
@@ -12,7 +14,13 @@ use solana_program::{
 
 use crate::{
     error::GovernanceError,
-    state::{scopes::scope::VotePowerUnit, vote_power_origin_record::VotePowerOriginRecord},
+    state::{
+        scopes::scope::VotePowerUnit,
+        vote_power_origin_record::{
+            get_indexed_vote_power_origin_record_address, get_vote_power_origin_record_address,
+            get_vote_power_origin_record_data_for_owner, VotePowerOriginRecord,
+        },
+    },
 };
 
 /// Processes DepositGoverningTag instruction
@@ -49,18 +57,49 @@ pub fn process_deposit_governing_tag(
         return Err(GovernanceError::InvalidTagRecordFactory.into());
     }
 
+    let source = VotePowerUnit::Tag {
+        record_factory: *tag_record_factory_info.key,
+    };
+
+    // Depositing a renewed record again refreshes the expiry of the deposit
+    if !token_origin_record_info.data_is_empty() {
+        let token_origin_record_address = match tag_record_data.index {
+            Some(index) => get_indexed_vote_power_origin_record_address(
+                program_id,
+                &source,
+                tag_record_owner_info.key,
+                index,
+            ),
+            None => {
+                get_vote_power_origin_record_address(program_id, &source, tag_record_owner_info.key)
+            }
+        }
+        .0;
+        if &token_origin_record_address != token_origin_record_info.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let mut token_origin_record = get_vote_power_origin_record_data_for_owner(
+            program_id,
+            token_origin_record_info,
+            tag_record_owner_info,
+        )?;
+        token_origin_record.expires_at = tag_record_data.expires_at;
+        token_origin_record.serialize(&mut *token_origin_record_info.data.borrow_mut())?;
+        return Ok(());
+    }
+
     VotePowerOriginRecord::create(
         program_id,
-        VotePowerUnit::Tag {
-            record_factory: *tag_record_factory_info.key,
-        },
-        1,
+        source,
+        tag_record_data.quantity,
         &rent,
         token_origin_record_info,
         token_origin_record_bump_seed,
         tag_record_owner_info,
         payer_info,
         system_info,
+        tag_record_data.index,
+        tag_record_data.expires_at,
     )?;
     Ok(())
 }
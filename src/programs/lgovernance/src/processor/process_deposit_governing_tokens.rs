@@ -56,6 +56,8 @@ pub fn process_deposit_governing_tokens(
         governing_owner_info,
         payer_info,
         system_info,
+        None,
+        None,
     )?;
     Ok(())
 }
@@ -0,0 +1,140 @@
+//! Program state processor
+
+use crate::{
+    error::GovernanceError,
+    state::proposal::{
+        get_proposal_data,
+        proposal_option::{get_proposal_option_data, ProposalOptionType},
+        proposal_transaction::get_proposal_transaction_data_for_proposal,
+    },
+};
+use borsh::BorshSerialize;
+use shared::{
+    account::{dispose_account, resize_and_serialize_account_with_padding},
+    content::ContentSource,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+/// Processes RemoveTransaction instruction
+/// The transaction index is left as a gap, so a corrected transaction can be inserted at the same index
+pub fn process_remove_transaction(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let proposal_info = next_account_info(account_info_iter)?;
+    let creator_info = next_account_info(account_info_iter)?;
+    let proposal_transaction_info = next_account_info(account_info_iter)?;
+    let option_info = next_account_info(account_info_iter)?;
+    let beneficiary_info = next_account_info(account_info_iter)?;
+
+    let proposal_data = get_proposal_data(program_id, proposal_info)?;
+    proposal_data.assert_can_edit_instructions(creator_info)?;
+
+    let proposal_transaction_data = get_proposal_transaction_data_for_proposal(
+        program_id,
+        proposal_transaction_info,
+        proposal_info.key,
+    )?;
+
+    let mut option_data = get_proposal_option_data(program_id, option_info, proposal_info.key)?;
+    if option_data.index != proposal_transaction_data.option_index {
+        return Err(GovernanceError::InvalidOptionForTransaction.into());
+    }
+
+    if let ProposalOptionType::Instruction {
+        transactions_count, ..
+    } = &mut option_data.option_type
+    {
+        *transactions_count = transactions_count.checked_sub(1).unwrap();
+    } else {
+        return Err(GovernanceError::InvalidOptionForInstructions.into());
+    }
+    option_data.serialize(&mut *option_info.data.borrow_mut())?;
+
+    dispose_account(proposal_transaction_info, beneficiary_info);
+
+    Ok(())
+}
+
+/// Processes RemoveProposalOption instruction
+/// Only the last option can be removed, so the option indices stay contiguous
+pub fn process_remove_proposal_option(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let proposal_option_info = next_account_info(account_info_iter)?;
+    let proposal_info = next_account_info(account_info_iter)?;
+    let creator_info = next_account_info(account_info_iter)?;
+    let beneficiary_info = next_account_info(account_info_iter)?;
+
+    let mut proposal_data = get_proposal_data(program_id, proposal_info)?;
+    proposal_data.assert_can_edit_options(creator_info)?;
+
+    let option_data =
+        get_proposal_option_data(program_id, proposal_option_info, proposal_info.key)?;
+
+    if option_data.index.checked_add(1) != Some(proposal_data.options_count) {
+        return Err(GovernanceError::CanOnlyRemoveLastOption.into());
+    }
+
+    match &option_data.option_type {
+        ProposalOptionType::Instruction {
+            transactions_count, ..
+        } => {
+            if *transactions_count > 0 {
+                return Err(GovernanceError::OptionHasTransactions.into());
+            }
+        }
+        ProposalOptionType::Deny => {
+            // Options with transactions are only allowed while there is a deny option
+            if proposal_data.options_count > 1 {
+                return Err(GovernanceError::CannotRemoveDenyOption.into());
+            }
+            proposal_data.deny_option = None;
+        }
+    }
+
+    proposal_data.options_count = proposal_data.options_count.checked_sub(1).unwrap();
+    proposal_data.serialize(&mut *proposal_info.data.borrow_mut())?;
+
+    dispose_account(proposal_option_info, beneficiary_info);
+
+    Ok(())
+}
+
+/// Processes UpdateProposalSource instruction
+/// The proposal account is resized to the new source, the payer tops up the rent if it grows
+pub fn process_update_proposal_source(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    source: ContentSource,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let proposal_info = next_account_info(account_info_iter)?;
+    let creator_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+
+    let mut proposal_data = get_proposal_data(program_id, proposal_info)?;
+    proposal_data.assert_can_edit_source(creator_info)?;
+
+    // The space left for the fields that grow while voting is kept
+    let padding = proposal_info
+        .data_len()
+        .saturating_sub(proposal_data.try_to_vec()?.len());
+
+    proposal_data.source = source;
+    resize_and_serialize_account_with_padding(
+        payer_info,
+        proposal_info,
+        &proposal_data,
+        padding,
+        system_info,
+        &Rent::get()?,
+    )
+}
@@ -15,6 +15,7 @@ use solana_program::{
 
 use crate::{
     error::GovernanceError,
+    processor::process_pause_governance::assert_can_execute_paused,
     state::{
         enums::{ProposalState, TransactionExecutionStatus},
         governance::{get_governance_address_seeds, get_governance_data},
@@ -25,6 +26,7 @@ use crate::{
             proposal_transaction::get_proposal_transaction_data_for_proposal,
             VoteType,
         },
+        scopes::scope::get_scope_data_for_governance,
     },
 };
 
@@ -47,6 +49,7 @@ pub fn process_execute_transaction(
         get_proposal_data_for_governance(program_id, proposal_info, governance_info.key)?;
 
     let governance_data = get_governance_data(program_id, governance_info)?;
+    governance_data.assert_not_frozen()?;
 
     let mut proposal_transaction_data = get_proposal_transaction_data_for_proposal(
         program_id,
@@ -62,6 +65,32 @@ pub fn process_execute_transaction(
         clock.unix_timestamp,
     )?;
 
+    for conditioned_instruction in &proposal_transaction_data.instructions {
+        assert_can_execute_paused(
+            program_id,
+            governance_info.key,
+            &governance_data,
+            conditioned_instruction,
+            clock.unix_timestamp,
+        )?;
+    }
+
+    // The parent can revoke delegated scopes after the transaction was inserted,
+    // so every instruction has to be applicable to one of the currently delegated parent scopes
+    if let Some(parent) = &governance_data.parent {
+        for conditioned_instruction in &proposal_transaction_data.instructions {
+            let parent_scope_info = next_account_info(account_info_iter)?;
+            if !governance_data
+                .delegated_scopes
+                .contains(parent_scope_info.key)
+            {
+                return Err(GovernanceError::ScopeNotDelegatedByParent.into());
+            }
+            get_scope_data_for_governance(program_id, parent_scope_info, parent)?
+                .scope_applicable(&conditioned_instruction.instruction_data)?;
+        }
+    }
+
     // Execute instruction with Governance PDA as signer
     let instructions = proposal_transaction_data
         .instructions
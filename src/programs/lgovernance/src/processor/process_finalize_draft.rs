@@ -2,30 +2,44 @@
 
 use crate::{
     error::GovernanceError,
+    processor::utils::verify_signed_owner_maybe_sign_for_me,
     state::{
-        enums::ProposalState, governance::get_governance_data,
+        enums::GovernanceAction, governance::get_governance_data,
         proposal::get_proposal_data_for_creator, scopes::scope::get_scope_data_for_governance,
     },
 };
 use borsh::BorshSerialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    clock::Clock,
+    clock::{Clock, UnixTimestamp},
     entrypoint::ProgramResult,
     pubkey::Pubkey,
     sysvar::Sysvar,
 };
 
-/// Processes DepositGoverningTokens instruction
-pub fn process_finalize_draft(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+/// Processes FinalizeDraft instruction
+pub fn process_finalize_draft(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    start_voting_at: Option<UnixTimestamp>,
+) -> ProgramResult {
     // verify proposal
     let account_info_iter = &mut accounts.iter();
     let proposal_info = next_account_info(account_info_iter)?;
     let governance_info = next_account_info(account_info_iter)?;
     let creator_info = next_account_info(account_info_iter)?;
-    let mut proposal_data = get_proposal_data_for_creator(program_id, proposal_info, creator_info)?;
+    let creator = verify_signed_owner_maybe_sign_for_me(
+        creator_info,
+        governance_info.key,
+        GovernanceAction::CreateProposal,
+        account_info_iter,
+    )?;
+    let mut proposal_data = get_proposal_data_for_creator(program_id, proposal_info, &creator)?;
 
-    proposal_data.assert_can_finalize_draft(creator_info)?;
+    proposal_data.assert_can_finalize_draft(&creator)?;
+
+    // Owners are verified once, so the criteria of several scopes only take one SignForMe use
+    let mut verified_owners = vec![creator];
 
     if proposal_data.scopes_count != proposal_data.scopes_max_vote_weight.len() as u8 {
         return Err(GovernanceError::MissingscopesForProposal.into());
@@ -34,12 +48,18 @@ pub fn process_finalize_draft(program_id: &Pubkey, accounts: &[AccountInfo]) ->
         return Err(GovernanceError::InvalidGovernanceForProposal.into());
     }
 
+    let clock = Clock::get()?;
+
     let mut governance_data = get_governance_data(program_id, governance_info)?;
+    governance_data.assert_not_frozen()?;
     for scope_weight in &proposal_data.scopes_max_vote_weight {
         let scope_info = next_account_info(account_info_iter)?;
         if scope_info.key != &scope_weight.scope {
             return Err(GovernanceError::InvalidVotescope.into());
         }
+
+        // While paused, only proposals through the unpause scope can be put to vote
+        governance_data.assert_not_paused_for_scope(clock.unix_timestamp, scope_info.key)?;
         let scope =
             get_scope_data_for_governance(program_id, scope_info, &proposal_data.governance)?;
 
@@ -47,14 +67,18 @@ pub fn process_finalize_draft(program_id: &Pubkey, accounts: &[AccountInfo]) ->
             program_id,
             &proposal_data,
             account_info_iter,
+            &mut verified_owners,
         )?;
     }
 
-    let clock = Clock::get()?;
-
-    proposal_data.voting_at = Some(clock.unix_timestamp);
-    proposal_data.voting_at_slot = Some(clock.slot);
-    proposal_data.state = ProposalState::Voting;
+    // Overrides the start time given when the proposal was created
+    if let Some(start_voting_at) = start_voting_at {
+        if start_voting_at <= clock.unix_timestamp {
+            return Err(GovernanceError::InvalidStartVotingTime.into());
+        }
+        proposal_data.start_voting_at = Some(start_voting_at);
+    }
+    proposal_data.start_voting_or_schedule(clock.unix_timestamp, clock.slot);
 
     proposal_data.serialize(&mut *proposal_info.data.borrow_mut())?;
 
@@ -3,6 +3,7 @@ use crate::{
     error::GovernanceError,
     state::{
         enums::TransactionExecutionStatus,
+        governance::get_governance_data,
         proposal::{
             get_proposal_data,
             proposal_option::{get_proposal_option_data, ProposalOptionType},
@@ -11,7 +12,7 @@ use crate::{
                 ProposalTransactionV2,
             },
         },
-        scopes::scope::Scope,
+        scopes::scope::{get_scope_data_for_governance, Scope},
     },
 };
 use std::cmp::Ordering;
@@ -44,6 +45,7 @@ pub fn process_insert_transaction(
     let system_info = next_account_info(account_info_iter)?;
     let rent_sysvar_info = next_account_info(account_info_iter)?;
     let rent = &Rent::from_account_info(rent_sysvar_info)?;
+    let governance_info = next_account_info(account_info_iter)?;
 
     if !proposal_transaction_info.data_is_empty() {
         return Err(GovernanceError::TransactionAlreadyExists.into());
@@ -53,6 +55,27 @@ pub fn process_insert_transaction(
 
     proposal_data.assert_can_edit_instructions(creator_info)?;
 
+    if &proposal_data.governance != governance_info.key {
+        return Err(GovernanceError::InvalidGovernanceForProposal.into());
+    }
+
+    // A child governance can only act within the scopes delegated by its parent,
+    // every instruction has to be applicable to one of the delegated parent scopes
+    let governance_data = get_governance_data(program_id, governance_info)?;
+    if let Some(parent) = &governance_data.parent {
+        for instruction in &instructions {
+            let parent_scope_info = next_account_info(account_info_iter)?;
+            if !governance_data
+                .delegated_scopes
+                .contains(parent_scope_info.key)
+            {
+                return Err(GovernanceError::ScopeNotDelegatedByParent.into());
+            }
+            get_scope_data_for_governance(program_id, parent_scope_info, parent)?
+                .scope_applicable(&instruction.instruction_data)?;
+        }
+    }
+
     let mut scope_info = next_account_info(account_info_iter)?;
     // Make sure that hold up time is ok by all the scopes
     for instruction in &instructions {
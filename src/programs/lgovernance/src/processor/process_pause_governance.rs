@@ -0,0 +1,156 @@
+//! Program state processor
+
+use crate::{
+    error::GovernanceError,
+    instruction::PostInstruction,
+    state::{
+        governance::{get_governance_data, GovernanceV2, PauseConfig},
+        proposal::proposal_transaction::{ConditionedInstruction, InstructionData},
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::{Clock, UnixTimestamp},
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+/// Processes UpdatePauseConfig instruction
+pub fn process_update_pause_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+    pause_config: PauseConfig,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let instruction = InstructionData::from_account_infos(program_id, accounts, instruction_data);
+    let governance_info = next_account_info(account_info_iter)?;
+
+    let mut governance_data = get_governance_data(program_id, governance_info)?;
+    governance_data.assert_governance_signed(
+        program_id,
+        governance_info,
+        &instruction,
+        account_info_iter,
+    )?;
+
+    governance_data.pause_config = pause_config;
+    governance_data.serialize(&mut *governance_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+/// Processes PauseGovernance instruction
+/// Signed either by the guardian, or by the governance through a proposal from the pause scope
+pub fn process_pause_governance(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    duration: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let governance_info = next_account_info(account_info_iter)?;
+
+    let mut governance_data = get_governance_data(program_id, governance_info)?;
+
+    // The pause scope is verified when the proposal transaction is executed
+    let paused_by_guardian = !governance_info.is_signer;
+    if paused_by_guardian {
+        let guardian_info = next_account_info(account_info_iter)?;
+        if governance_data.pause_config.guardian.as_ref() != Some(guardian_info.key) {
+            return Err(GovernanceError::InvalidGuardianForGovernance.into());
+        }
+        if !guardian_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+    }
+
+    if duration == 0 || duration > governance_data.pause_config.max_pause_duration {
+        return Err(GovernanceError::InvalidPauseDuration.into());
+    }
+
+    // A pause can not be extended, it has to expire first
+    let now = Clock::get()?.unix_timestamp;
+    governance_data.assert_not_paused(now)?;
+
+    let paused_until = now.checked_add(duration as UnixTimestamp).unwrap();
+    if paused_by_guardian {
+        // Otherwise the guardian could keep the governance paused by pausing again as soon as a pause expires
+        if matches!(governance_data.guardian_cooldown_until, Some(cooldown_until) if cooldown_until > now)
+        {
+            return Err(GovernanceError::GuardianPauseCooldown.into());
+        }
+        governance_data.guardian_cooldown_until = Some(
+            paused_until
+                .checked_add(governance_data.pause_config.max_pause_duration as UnixTimestamp)
+                .unwrap(),
+        );
+    }
+
+    governance_data.paused_until = Some(paused_until);
+    governance_data.serialize(&mut *governance_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+/// Processes UnpauseGovernance instruction
+/// Signed by the governance through a proposal from the unpause scope
+pub fn process_unpause_governance(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let governance_info = next_account_info(account_info_iter)?;
+
+    let mut governance_data = get_governance_data(program_id, governance_info)?;
+
+    // The unpause scope is verified when the proposal transaction is executed
+    if !governance_info.is_signer {
+        return Err(GovernanceError::GovernancePdaMustSign.into());
+    }
+
+    governance_data.paused_until = None;
+    governance_data.serialize(&mut *governance_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+/// Checks a proposal instruction can be executed by the governance with respect to the pause.
+/// Pause and unpause instructions have to come from the pause and unpause scopes,
+/// and while paused only instructions from the unpause scope can be executed
+pub fn assert_can_execute_paused(
+    program_id: &Pubkey,
+    governance: &Pubkey,
+    governance_data: &GovernanceV2,
+    conditioned_instruction: &ConditionedInstruction,
+    current_unix_timestamp: UnixTimestamp,
+) -> Result<(), ProgramError> {
+    let instruction = Instruction::from(conditioned_instruction);
+    let scope = &conditioned_instruction.scope;
+    let pause_config = &governance_data.pause_config;
+
+    let targets_governance = &instruction.program_id == program_id
+        && instruction
+            .accounts
+            .first()
+            .map(|account| &account.pubkey == governance)
+            .unwrap_or(false);
+
+    if targets_governance {
+        match PostInstruction::try_from_slice(&instruction.data) {
+            Ok(PostInstruction::PauseGovernance { .. }) => {
+                if pause_config.pause_scope.as_ref() != Some(scope) {
+                    return Err(GovernanceError::InvalidScopeForPause.into());
+                }
+            }
+            Ok(PostInstruction::UnpauseGovernance) => {
+                if pause_config.unpause_scope.as_ref() != Some(scope) {
+                    return Err(GovernanceError::InvalidScopeForPause.into());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    governance_data.assert_not_paused_for_scope(current_unix_timestamp, scope)
+}
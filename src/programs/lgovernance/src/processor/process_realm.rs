@@ -0,0 +1,230 @@
+//! Program state processor
+
+use crate::{
+    accounts::AccountType,
+    error::GovernanceError,
+    shared::names::entity_name_is_valid,
+    state::{
+        governance::get_governance_data,
+        proposal::proposal_transaction::InstructionData,
+        realm::{get_realm_address_seeds, get_realm_data, Realm},
+        vote_power_origin_record::get_vote_power_origin_record_data,
+    },
+};
+use borsh::BorshSerialize;
+use shared::account::{
+    create_and_serialize_account_verify_with_bump, resize_and_serialize_account,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::set_return_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+/// Processes CreateRealmRegistry instruction
+#[allow(clippy::too_many_arguments)]
+pub fn process_create_realm_registry(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+    authority: Option<Pubkey>,
+    governing_mints: Vec<Pubkey>,
+    tag_record_factories: Vec<Pubkey>,
+    min_weight_to_create_governance: u64,
+    bump_seed: u8,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let realm_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+
+    // The authority has to agree to govern the realm
+    if let Some(authority) = &authority {
+        let realm_authority_info = next_account_info(account_info_iter)?;
+        if authority != realm_authority_info.key {
+            return Err(GovernanceError::InvalidAuthorityForRealm.into());
+        }
+        if !realm_authority_info.is_signer {
+            return Err(GovernanceError::RealmAuthorityMustSign.into());
+        }
+    }
+
+    if !entity_name_is_valid(&name) {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if !realm_info.data_is_empty() {
+        return Err(GovernanceError::RealmAlreadyExists.into());
+    }
+
+    let mut seeds = get_realm_address_seeds(&name).map_err(|_| ProgramError::InvalidArgument)?;
+    seeds.push(vec![bump_seed]);
+    let seed_slice = &seeds.iter().map(|x| &x[..]).collect::<Vec<&[u8]>>()[..];
+
+    create_and_serialize_account_verify_with_bump(
+        payer_info,
+        realm_info,
+        &Realm {
+            account_type: AccountType::Realm,
+            name,
+            authority,
+            governing_mints,
+            tag_record_factories,
+            min_weight_to_create_governance,
+            governances: Vec::new(),
+        },
+        seed_slice,
+        program_id,
+        system_info,
+        &Rent::get()?,
+    )?;
+
+    Ok(())
+}
+
+/// Processes UpdateRealm instruction
+pub fn process_update_realm(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    authority: Option<Pubkey>,
+    governing_mints: Vec<Pubkey>,
+    tag_record_factories: Vec<Pubkey>,
+    min_weight_to_create_governance: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let realm_info = next_account_info(account_info_iter)?;
+    let realm_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+
+    let mut realm_data = get_realm_data(program_id, realm_info)?;
+    realm_data.assert_authority_signed(realm_authority_info)?;
+
+    realm_data.authority = authority;
+    realm_data.governing_mints = governing_mints;
+    realm_data.tag_record_factories = tag_record_factories;
+    realm_data.min_weight_to_create_governance = min_weight_to_create_governance;
+
+    resize_and_serialize_account(
+        payer_info,
+        realm_info,
+        &realm_data,
+        system_info,
+        &Rent::get()?,
+    )
+}
+
+/// Processes AddRealmGovernance instruction
+/// The Realm authority and the governance have to agree, and the creator has to hold enough weight of a vote power unit accepted by the Realm
+pub fn process_add_realm_governance(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let instruction = InstructionData::from_account_infos(program_id, accounts, instruction_data);
+    let realm_info = next_account_info(account_info_iter)?;
+    let governance_info = next_account_info(account_info_iter)?;
+    let realm_authority_info = next_account_info(account_info_iter)?;
+    let creator_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+
+    let mut realm_data = get_realm_data(program_id, realm_info)?;
+    realm_data.assert_authority_signed(realm_authority_info)?;
+
+    let governance_data = get_governance_data(program_id, governance_info)?;
+    governance_data.assert_governance_signed(
+        program_id,
+        governance_info,
+        &instruction,
+        account_info_iter,
+    )?;
+
+    if !creator_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if realm_data.min_weight_to_create_governance > 0 {
+        let vote_power_origin_record_info = next_account_info(account_info_iter)?;
+        let vote_power_origin_record_data =
+            get_vote_power_origin_record_data(program_id, vote_power_origin_record_info)?;
+        if &vote_power_origin_record_data.governing_owner != creator_info.key {
+            return Err(GovernanceError::InvalidTokenOwner.into());
+        }
+        if !realm_data.accepts(&vote_power_origin_record_data.source) {
+            return Err(GovernanceError::InvalidGoverningTokenMint.into());
+        }
+        if vote_power_origin_record_data.amount < realm_data.min_weight_to_create_governance {
+            return Err(GovernanceError::NotEnoughTokensToCreateGovernance.into());
+        }
+    }
+
+    if realm_data.governances.contains(governance_info.key) {
+        return Err(GovernanceError::GovernanceAlreadyInRealm.into());
+    }
+    realm_data.governances.push(*governance_info.key);
+
+    resize_and_serialize_account(
+        payer_info,
+        realm_info,
+        &realm_data,
+        system_info,
+        &Rent::get()?,
+    )
+}
+
+/// Processes RemoveRealmGovernance instruction
+pub fn process_remove_realm_governance(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let realm_info = next_account_info(account_info_iter)?;
+    let governance_info = next_account_info(account_info_iter)?;
+    let realm_authority_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+
+    let mut realm_data = get_realm_data(program_id, realm_info)?;
+    realm_data.assert_authority_signed(realm_authority_info)?;
+
+    let index = realm_data
+        .governances
+        .iter()
+        .position(|governance| governance == governance_info.key)
+        .ok_or(GovernanceError::InvalidRealmForGovernance)?;
+    realm_data.governances.remove(index);
+
+    // The account only shrinks, so the authority never pays
+    resize_and_serialize_account(
+        realm_authority_info,
+        realm_info,
+        &realm_data,
+        system_info,
+        &Rent::get()?,
+    )
+}
+
+/// Processes LookupRealm instruction
+/// Verifies the Realm belongs to the name and returns it as return data
+pub fn process_lookup_realm(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let realm_info = next_account_info(account_info_iter)?;
+    let realm_data = get_realm_data(program_id, realm_info)?;
+
+    // The Realm address is derived from the lowercase name
+    if realm_data.name.to_lowercase() != name.to_lowercase() {
+        return Err(GovernanceError::InvalidRealm.into());
+    }
+
+    set_return_data(&realm_data.try_to_vec()?);
+    Ok(())
+}
@@ -1,8 +1,8 @@
 use crate::{
     accounts::AccountType,
-    error::GovernanceError,
     state::{
         governance::GovernanceV2,
+        proposal::proposal_transaction::InstructionData,
         scopes::scope::{get_scope_program_address_seeds, Scope, ScopeConfig},
     },
 };
@@ -12,7 +12,6 @@ use shared::account::get_account_data;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
-    program_error::ProgramError,
     pubkey::Pubkey,
     rent::Rent,
     sysvar::Sysvar,
@@ -21,6 +20,7 @@ use solana_program::{
 pub fn process_create_scope(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
+    instruction_data: &[u8],
     scope_id: &Pubkey,
     config: ScopeConfig,
     new_scope_bump_seed: u8,
@@ -62,19 +62,12 @@ pub fn process_create_scope(
             return Err(ProgramError::InvalidAccountData);
         } */
 
-        if let Some(option_authority) = &governance_data.optional_authority {
-            let authority_info = next_account_info(accounts_iter)?;
-
-            if option_authority != authority_info.key {
-                return Err(GovernanceError::InvalidAuthorityForGovernance.into());
-            }
-
-            if !authority_info.is_signer {
-                return Err(ProgramError::MissingRequiredSignature);
-            }
-        } else {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
+        governance_data.assert_governance_signed(
+            program_id,
+            governance_info,
+            &InstructionData::from_account_infos(program_id, accounts, instruction_data),
+            accounts_iter,
+        )?;
     }
 
     check_system_program(system_info.key)?;
@@ -0,0 +1,26 @@
+//! Program state processor
+
+use crate::state::proposal::get_proposal_data;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+/// Processes StartVoting instruction
+/// Permissionless crank moving a Scheduled proposal to Voting once start_voting_at has been reached
+pub fn process_start_voting(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let proposal_info = next_account_info(account_info_iter)?;
+    let mut proposal_data = get_proposal_data(program_id, proposal_info)?;
+
+    let clock = Clock::get()?;
+    proposal_data.assert_is_scheduled_state()?;
+    proposal_data.try_start_scheduled_voting(clock.unix_timestamp, clock.slot)?;
+
+    proposal_data.serialize(&mut *proposal_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -1,10 +1,11 @@
 use crate::{
     error::GovernanceError,
+    processor::utils::verify_signed_owner_maybe_sign_for_me,
     state::{
-        enums::ProposalState,
+        enums::{GovernanceAction, ProposalState},
         proposal::get_proposal_data,
         scopes::scope::get_scope_data_for_governance,
-        vote_power_owner_record::get_vote_power_owner_record_data_for_owner,
+        vote_power_owner_record::get_vote_power_owner_record_data_for_verified_owner,
         vote_record::{get_vote_record_data_for_proposal_and_token_owner, VoteRecordV2},
     },
 };
@@ -27,18 +28,24 @@ pub fn process_uncast_vote(program_id: &Pubkey, accounts: &[AccountInfo]) -> Pro
     let scope_info = next_account_info(accounts_iter)?;
     let proposal = get_proposal_data(program_id, proposal_info)?;
     let scope = get_scope_data_for_governance(program_id, scope_info, &proposal.governance)?;
+    let governing_owner = verify_signed_owner_maybe_sign_for_me(
+        governing_owner_record_info,
+        &proposal.governance,
+        GovernanceAction::Vote,
+        accounts_iter,
+    )?;
 
-    let mut token_owner_record_data = get_vote_power_owner_record_data_for_owner(
+    let mut token_owner_record_data = get_vote_power_owner_record_data_for_verified_owner(
         program_id,
         vote_power_owner_record_info,
-        governing_owner_record_info,
+        &governing_owner,
     )?;
 
     let mut vote_record_data = get_vote_record_data_for_proposal_and_token_owner(
         program_id,
         vote_record_info,
         proposal_info.key,
-        governing_owner_record_info,
+        &governing_owner,
     )?;
 
     vote_record_data.assert_can_relinquish_vote()?;
@@ -53,11 +60,6 @@ pub fn process_uncast_vote(program_id: &Pubkey, accounts: &[AccountInfo]) -> Pro
     {
         let beneficiary_info = next_account_info(accounts_iter)?;
 
-        // Note: It's only required to sign by governing_authority if relinquishing the vote results in vote change
-        // If the Proposal is already decided then anybody can prune active votes for token owner
-        token_owner_record_data
-            .assert_token_owner_or_delegate_is_signer(governing_owner_record_info)?;
-
         vote_record_data.assert_vote_equals(&proposal.perform_voting(
             program_id,
             vote_record_data.vote_weight,
@@ -0,0 +1,44 @@
+//! Program state processor
+
+use crate::state::{
+    token_owner_budget_record::get_token_owner_budget_record_data_for_token_record,
+    vote_power_origin_record::get_vote_power_origin_record_data_for_owner,
+};
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+/// Processes UpdateTokenOwnerBudget instruction
+/// Sets the share of the deposit the governing owner gives to the scope of the budget
+pub fn process_update_token_owner_budget(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    budget: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let token_origin_record_info = next_account_info(accounts_iter)?;
+    let token_owner_budget_record_info = next_account_info(accounts_iter)?;
+    let governing_owner_info = next_account_info(accounts_iter)?;
+
+    let token_origin_record = get_vote_power_origin_record_data_for_owner(
+        program_id,
+        token_origin_record_info,
+        governing_owner_info,
+    )?;
+
+    let mut token_owner_budget_record = get_token_owner_budget_record_data_for_token_record(
+        program_id,
+        token_owner_budget_record_info,
+        &token_origin_record,
+        token_origin_record_info,
+        governing_owner_info.key,
+    )?;
+
+    token_owner_budget_record.set_budget(budget, token_origin_record.amount)?;
+    token_owner_budget_record.serialize(&mut *token_owner_budget_record_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -1,13 +1,16 @@
 use crate::{
     accounts::AccountType,
     error::GovernanceError,
+    processor::utils::verify_signed_owner_maybe_sign_for_me,
     state::{
+        enums::GovernanceAction,
+        governance::get_governance_data,
         proposal::get_proposal_data,
         scopes::scope::get_scope_data_for_governance,
-        vote_power_owner_record::get_vote_power_owner_record_data_for_owner,
+        vote_power_owner_record::{get_vote_power_owner_record_data, VotePowerOwnerRecord},
         vote_record::{
-            get_vote_record_address_seeds, get_vote_record_data_for_proposal_and_token_owner,
-            VoteRecordV2,
+            get_vote_record_address_seeds,
+            get_vote_record_data_for_proposal_and_unsigned_token_owner, Vote, VoteRecordV2,
         },
     },
 };
@@ -15,8 +18,9 @@ use crate::{
 use shared::account::create_and_serialize_account_verify_with_bump;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
-    msg,
+    program_error::ProgramError,
     pubkey::Pubkey,
     rent::Rent,
     sysvar::Sysvar,
@@ -33,25 +37,80 @@ pub fn process_cast_vote(
     let vote_power_owner_record_info = next_account_info(accounts_iter)?;
     let governing_owner_info = next_account_info(accounts_iter)?;
     let scope_info = next_account_info(accounts_iter)?;
+    let governance_info = next_account_info(accounts_iter)?;
     let payer_info = next_account_info(accounts_iter)?;
     let system_info = next_account_info(accounts_iter)?;
+
+    let governing_owner = verify_signed_owner_maybe_sign_for_me(
+        governing_owner_info,
+        governance_info.key,
+        GovernanceAction::Vote,
+        accounts_iter,
+    )?;
+
+    let token_owner_record_data =
+        get_vote_power_owner_record_data(program_id, vote_power_owner_record_info)?;
+
+    cast_vote(
+        program_id,
+        proposal_account_info,
+        vote_record_info,
+        vote_power_owner_record_info,
+        token_owner_record_data,
+        &governing_owner,
+        scope_info,
+        governance_info,
+        payer_info,
+        system_info,
+        vote_record_bump_seed,
+        accounts_iter,
+    )?;
+
+    Ok(())
+}
+
+/// Casts a vote for a governing owner whose authority has already been verified by the caller.
+/// The accounts iterator is expected to yield the latest vote record of the owner (if any) followed by the voted options
+#[allow(clippy::too_many_arguments)]
+pub fn cast_vote<'a>(
+    program_id: &Pubkey,
+    proposal_account_info: &AccountInfo<'a>,
+    vote_record_info: &AccountInfo<'a>,
+    vote_power_owner_record_info: &AccountInfo<'a>,
+    mut token_owner_record_data: VotePowerOwnerRecord,
+    governing_owner: &Pubkey,
+    scope_info: &AccountInfo<'a>,
+    governance_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    system_info: &AccountInfo<'a>,
+    vote_record_bump_seed: u8,
+    accounts_iter: &mut std::slice::Iter<AccountInfo<'a>>,
+) -> Result<Vote, ProgramError> {
     let rent = Rent::get()?;
 
     // TODO: More granular check proposal data?
     // TODO fix - Vote and delegate after voting???
-    let proposal = get_proposal_data(program_id, proposal_account_info)?;
+    let mut proposal = get_proposal_data(program_id, proposal_account_info)?;
     let scope = get_scope_data_for_governance(program_id, scope_info, &proposal.governance)?;
 
-    let mut token_owner_record_data = get_vote_power_owner_record_data_for_owner(
-        program_id,
-        vote_power_owner_record_info,
-        governing_owner_info,
-    )?;
+    // The first vote after start_voting_at opens a scheduled proposal
+    let clock = Clock::get()?;
+    proposal.try_start_scheduled_voting(clock.unix_timestamp, clock.slot)?;
+    proposal.assert_can_cast_vote(&scope.config.time_config, clock.unix_timestamp)?;
+
+    if governance_info.key != &proposal.governance {
+        return Err(GovernanceError::InvalidGovernanceForProposal.into());
+    }
+    get_governance_data(program_id, governance_info)?
+        .assert_not_paused_for_scope(clock.unix_timestamp, scope_info.key)?;
+
+    if &token_owner_record_data.governing_owner != governing_owner {
+        return Err(GovernanceError::InvalidTokenOwner.into());
+    }
 
     if &token_owner_record_data.delegated_by_scope != scope_info.key {
         return Err(GovernanceError::InvalidScopeVoteRecord.into());
     }
-
     /* let vote_weight = match token_owner_record_data.delegated_by_scope {
         Some(_scope) => match &token_owner_record_data.source {
             VotePowerSource::Token {
@@ -73,87 +132,82 @@ pub fn process_cast_vote(
             token_owner_budget_record_data.amount
         }
     }; */
-    let vote_weight = token_owner_record_data.amount;
+    let vote_weight = token_owner_record_data.get_vote_weight(clock.unix_timestamp)?;
     /* match &token_owner_record_data.source {
         VoteSource::Token(governing_token_deposit_amount) => *governing_token_deposit_amount,
         VoteSource::Tag { amount, .. } => *amount,
     }; */
-    msg!("XXX");
 
     // TODO: CHECK OWNER OF POST, CHECK MINTS,
     if !vote_record_info.data_is_empty() {
         return Err(GovernanceError::VoteAlreadyExists.into());
-    } else {
-        // Update last vote record to link to this new vote
-        let last_vote_record_key = if let Some(vote) = token_owner_record_data.latest_vote {
-            let last_vote_record_info = next_account_info(accounts_iter)?;
-            msg!(
-                "LAST VOTE RECORD: {}",
-                last_vote_record_info.data_is_empty()
-            );
-            if &vote != last_vote_record_info.key {
-                return Err(GovernanceError::InvalidVoteRecord.into());
-            }
-
-            let mut last_vote_data = get_vote_record_data_for_proposal_and_token_owner(
-                program_id,
-                last_vote_record_info,
-                proposal_account_info.key,
-                governing_owner_info,
-            )?;
-            msg!("??");
-            if last_vote_data.next_vote.is_some() {
-                // Expecting head
-                return Err(GovernanceError::InvalidVoteRecord.into());
-            }
-            last_vote_data.next_vote = Some(*vote_record_info.key);
-            last_vote_data.serialize(&mut *last_vote_record_info.data.borrow_mut())?;
-            Some(*last_vote_record_info.key)
-        } else {
-            None
-        };
-
-        let vote = proposal.perform_voting(
+    }
+
+    // Update last vote record to link to this new vote
+    let last_vote_record_key = if let Some(vote) = token_owner_record_data.latest_vote {
+        let last_vote_record_info = next_account_info(accounts_iter)?;
+        if &vote != last_vote_record_info.key {
+            return Err(GovernanceError::InvalidVoteRecord.into());
+        }
+
+        let mut last_vote_data = get_vote_record_data_for_proposal_and_unsigned_token_owner(
             program_id,
-            vote_weight,
-            true,
-            &token_owner_record_data.source,
-            scope_info.key,
-            &scope,
+            last_vote_record_info,
             proposal_account_info.key,
-            accounts_iter,
+            governing_owner,
         )?;
+        if last_vote_data.next_vote.is_some() {
+            // Expecting head
+            return Err(GovernanceError::InvalidVoteRecord.into());
+        }
+        last_vote_data.next_vote = Some(*vote_record_info.key);
+        last_vote_data.serialize(&mut *last_vote_record_info.data.borrow_mut())?;
+        Some(*last_vote_record_info.key)
+    } else {
+        None
+    };
 
-        // Update last vote record to point to the new one
-
-        // Add vote record so we can not vote again through the same scope
-        let vote_record_data = VoteRecordV2 {
-            account_type: AccountType::VoteRecordV2,
-            proposal: *proposal_account_info.key,
-            governing_owner: *governing_owner_info.key,
-            vote,
-            vote_weight,
-            scope: *scope_info.key,
-            is_relinquished: false,
-            previous_vote: last_vote_record_key, // move vote in top of the "stack"
-            next_vote: None,
-        };
-
-        create_and_serialize_account_verify_with_bump::<VoteRecordV2>(
-            payer_info,
-            vote_record_info,
-            &vote_record_data,
-            &get_vote_record_address_seeds(
-                proposal_account_info.key,
-                vote_power_owner_record_info.key,
-                scope_info.key,
-                &[vote_record_bump_seed],
-            ),
-            program_id,
-            system_info,
-            &rent,
-        )?;
-    }
+    let vote = proposal.perform_voting(
+        program_id,
+        vote_weight,
+        true,
+        &token_owner_record_data.source,
+        scope_info.key,
+        &scope,
+        proposal_account_info.key,
+        accounts_iter,
+    )?;
+
+    // Update last vote record to point to the new one
+
+    // Add vote record so we can not vote again through the same scope
+    let vote_record_data = VoteRecordV2 {
+        account_type: AccountType::VoteRecordV2,
+        proposal: *proposal_account_info.key,
+        governing_owner: *governing_owner,
+        vote: vote.clone(),
+        vote_weight,
+        scope: *scope_info.key,
+        is_relinquished: false,
+        previous_vote: last_vote_record_key, // move vote in top of the "stack"
+        next_vote: None,
+        cast_at: clock.unix_timestamp,
+    };
+
+    create_and_serialize_account_verify_with_bump::<VoteRecordV2>(
+        payer_info,
+        vote_record_info,
+        &vote_record_data,
+        &get_vote_record_address_seeds(
+            proposal_account_info.key,
+            vote_power_owner_record_info.key,
+            scope_info.key,
+            &[vote_record_bump_seed],
+        ),
+        program_id,
+        system_info,
+        &rent,
+    )?;
 
     // Update TokenOwnerRecord vote counts
     token_owner_record_data.unrelinquished_votes_count = token_owner_record_data
@@ -174,5 +228,5 @@ pub fn process_cast_vote(
     proposal.serialize(&mut *proposal_account_info.data.borrow_mut())?;
     token_owner_record_data.serialize(&mut *vote_power_owner_record_info.data.borrow_mut())?;
 
-    Ok(())
+    Ok(vote)
 }
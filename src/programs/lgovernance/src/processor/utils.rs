@@ -0,0 +1,42 @@
+use std::slice::Iter;
+
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::state::enums::GovernanceAction;
+
+/// Verifies the owner signed, or signers of a SignForMe record of the owner can do the action on the resource.
+/// Returns the verified owner, which has to be passed explicitly to the owner checks of this program
+pub fn verify_signed_owner_maybe_sign_for_me<'a>(
+    owner_info: &AccountInfo<'a>,
+    resource: &Pubkey,
+    action: GovernanceAction,
+    accounts_iter: &mut Iter<AccountInfo<'a>>,
+) -> Result<Pubkey, ProgramError> {
+    lsignforme::state::verify_signed_owner_maybe_sign_for_me(
+        owner_info,
+        &crate::id(),
+        resource,
+        action.action(),
+        accounts_iter,
+    )?;
+
+    Ok(*owner_info.key)
+}
+
+/// Same as verify_signed_owner_maybe_sign_for_me, but owners already verified in the instruction are not verified again.
+/// The SignForMe accounts are then not expected and no additional use of the SignForMe record is taken
+pub fn verify_signed_owner_maybe_sign_for_me_once<'a>(
+    owner_info: &AccountInfo<'a>,
+    resource: &Pubkey,
+    action: GovernanceAction,
+    accounts_iter: &mut Iter<AccountInfo<'a>>,
+    verified_owners: &mut Vec<Pubkey>,
+) -> Result<Pubkey, ProgramError> {
+    if verified_owners.contains(owner_info.key) {
+        return Ok(*owner_info.key);
+    }
+
+    let owner = verify_signed_owner_maybe_sign_for_me(owner_info, resource, action, accounts_iter)?;
+    verified_owners.push(owner);
+    Ok(owner)
+}
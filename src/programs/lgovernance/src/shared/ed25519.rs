@@ -0,0 +1,107 @@
+use solana_program::{
+    account_info::AccountInfo,
+    ed25519_program,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::instructions::load_instruction_at_checked,
+};
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+const SIGNATURE_SERIALIZED_SIZE: usize = 64;
+const PUBKEY_SERIALIZED_SIZE: usize = 32;
+
+/// Offsets refering to the ed25519 instruction itself
+const CURRENT_INSTRUCTION_INDEX: u16 = u16::MAX;
+
+/// A message with a signature verified by the ed25519 program
+pub struct VerifiedMessage {
+    pub signer: Pubkey,
+    pub message: Vec<u8>,
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, ProgramError> {
+    let bytes = data
+        .get(offset..offset + 2)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_slice(data: &[u8], offset: u16, len: usize) -> Result<&[u8], ProgramError> {
+    data.get(offset as usize..offset as usize + len)
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+/// Returns the message verified by the ed25519 program instruction at the index of the transaction.
+/// The instruction has to verify a single signature, where public key, signature and message are all contained in the instruction itself
+pub fn get_verified_message(
+    instructions_sysvar_info: &AccountInfo,
+    instruction_index: u16,
+) -> Result<VerifiedMessage, ProgramError> {
+    let instruction =
+        load_instruction_at_checked(instruction_index as usize, instructions_sysvar_info)?;
+    if instruction.program_id != ed25519_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let data = &instruction.data;
+    let signatures_count = *data.get(0).ok_or(ProgramError::InvalidInstructionData)?;
+    if signatures_count != 1 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let start = SIGNATURE_OFFSETS_START;
+    let signature_instruction_index = read_u16(data, start + 2)?;
+    let public_key_offset = read_u16(data, start + 4)?;
+    let public_key_instruction_index = read_u16(data, start + 6)?;
+    let message_data_offset = read_u16(data, start + 8)?;
+    let message_data_size = read_u16(data, start + 10)?;
+    let message_instruction_index = read_u16(data, start + 12)?;
+
+    if signature_instruction_index != CURRENT_INSTRUCTION_INDEX
+        || public_key_instruction_index != CURRENT_INSTRUCTION_INDEX
+        || message_instruction_index != CURRENT_INSTRUCTION_INDEX
+    {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(VerifiedMessage {
+        signer: Pubkey::new(read_slice(data, public_key_offset, PUBKEY_SERIALIZED_SIZE)?),
+        message: read_slice(data, message_data_offset, message_data_size as usize)?.to_vec(),
+    })
+}
+
+/// Creates an ed25519 program instruction verifying a single signature over a message
+pub fn new_ed25519_verify_instruction(
+    signer: &Pubkey,
+    signature: &[u8; SIGNATURE_SERIALIZED_SIZE],
+    message: &[u8],
+) -> Instruction {
+    let public_key_offset = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+    let signature_offset = public_key_offset + PUBKEY_SERIALIZED_SIZE;
+    let message_data_offset = signature_offset + SIGNATURE_SERIALIZED_SIZE;
+
+    let mut data = Vec::with_capacity(message_data_offset + message.len());
+    data.push(1); // signatures count
+    data.push(0); // padding
+    for value in [
+        signature_offset as u16,
+        CURRENT_INSTRUCTION_INDEX,
+        public_key_offset as u16,
+        CURRENT_INSTRUCTION_INDEX,
+        message_data_offset as u16,
+        message.len() as u16,
+        CURRENT_INSTRUCTION_INDEX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: ed25519_program::id(),
+        accounts: Vec::<AccountMeta>::new(),
+        data,
+    }
+}
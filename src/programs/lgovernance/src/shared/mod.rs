@@ -1,4 +1,5 @@
 /* pub mod accounts;
  */
+pub mod ed25519;
 pub mod io_utils;
 pub mod names;
@@ -3,8 +3,8 @@ use shared::account::{
     create_and_serialize_account_verify_with_bump, dispose_account, get_account_data, MaxSize,
 };
 use solana_program::{
-    account_info::AccountInfo, msg, program_error::ProgramError, program_pack::IsInitialized,
-    pubkey::Pubkey, rent::Rent,
+    account_info::AccountInfo, clock::UnixTimestamp, msg, program_error::ProgramError,
+    program_pack::IsInitialized, pubkey::Pubkey, rent::Rent,
 };
 
 use crate::{
@@ -31,11 +31,15 @@ pub struct ScopeDelegationRecordAccount {
     pub vote_head: Option<Pubkey>,
 
     pub last_vote_head: Option<Pubkey>,
+
+    /// Expiry of the delegated tag deposit
+    /// Votes cast from this time on do not include the delegation
+    pub expires_at: Option<UnixTimestamp>,
 }
 
 impl MaxSize for ScopeDelegationRecordAccount {
     fn get_max_size(&self) -> Option<usize> {
-        Some(1 + 32 + 32 + 8 + 1 + 32 + 1 + 32)
+        Some(1 + 32 + 32 + 8 + 1 + 32 + 1 + 32 + 1 + 8)
     }
 }
 impl IsInitialized for ScopeDelegationRecordAccount {
@@ -54,11 +58,12 @@ impl ScopeDelegationRecordAccount {
         scope_delegation_record_bump_seed: u8,
         token_origin_record: &VotePowerOriginRecord,
         token_origin_record_info: &AccountInfo<'a>,
-        governing_owner_info: &AccountInfo<'a>,
-        delegatee_token_owner_record: &VotePowerOwnerRecord,
+        governing_owner: &Pubkey,
+        delegatee_token_owner_record: &mut VotePowerOwnerRecord,
         delegatee_vote_power_owner_record_info: &AccountInfo<'a>,
         payer_info: &AccountInfo<'a>,
         system_info: &AccountInfo<'a>,
+        current_unix_timestamp: UnixTimestamp,
     ) -> Result<(), ProgramError> {
         msg!("Z");
 
@@ -82,6 +87,7 @@ impl ScopeDelegationRecordAccount {
                     delegatee_token_owner_record: *delegatee_vote_power_owner_record_info.key,
                     vote_head: None,
                     last_vote_head: delegatee_token_owner_record.latest_vote,
+                    expires_at: token_origin_record.expires_at,
                 },
                 &seeds,
                 program_id,
@@ -94,7 +100,7 @@ impl ScopeDelegationRecordAccount {
                 scope_delegation_record_info,
                 token_origin_record,
                 token_origin_record_info,
-                governing_owner_info,
+                governing_owner,
                 delegatee_vote_power_owner_record_info,
             )?;
 
@@ -110,11 +116,23 @@ impl ScopeDelegationRecordAccount {
                 return Err(GovernanceError::InvalidDelegationStateForUpdates.into());
             }
 
+            // Delegating a renewed deposit moves the whole delegation to the new expiry
+            if scope_delegation_record.expires_at != token_origin_record.expires_at {
+                if matches!(scope_delegation_record.expires_at, Some(expires_at) if current_unix_timestamp >= expires_at)
+                {
+                    // Votes cast since the expiry do not include the delegation, so it has to be undelegated first
+                    return Err(GovernanceError::VotePowerExpired.into());
+                }
+                scope_delegation_record
+                    .set_expiry(token_origin_record.expires_at, delegatee_token_owner_record);
+            }
+
             scope_delegation_record.amount =
                 scope_delegation_record.amount.checked_add(amount).unwrap();
             scope_delegation_record
                 .serialize(&mut *scope_delegation_record_info.data.borrow_mut())?;
         }
+        delegatee_token_owner_record.add_expiring_amount(token_origin_record.expires_at, amount);
         Ok(())
     }
 
@@ -124,8 +142,8 @@ impl ScopeDelegationRecordAccount {
         scope_delegation_record_info: &AccountInfo<'a>,
         token_origin_record: &VotePowerOriginRecord,
         token_origin_record_info: &AccountInfo<'a>,
-        governing_owner_info: &AccountInfo<'a>,
-        delegatee_token_owner_record: &VotePowerOwnerRecord,
+        governing_owner: &Pubkey,
+        delegatee_token_owner_record: &mut VotePowerOwnerRecord,
         delegatee_vote_power_owner_record_info: &AccountInfo<'a>,
         beneficiary_info: &AccountInfo<'a>,
     ) -> Result<(), ProgramError> {
@@ -135,7 +153,7 @@ impl ScopeDelegationRecordAccount {
             scope_delegation_record_info,
             token_origin_record,
             token_origin_record_info,
-            governing_owner_info,
+            governing_owner,
             delegatee_vote_power_owner_record_info,
         )?;
 
@@ -148,6 +166,8 @@ impl ScopeDelegationRecordAccount {
 
         scope_delegation_record.amount =
             scope_delegation_record.amount.checked_sub(amount).unwrap();
+        delegatee_token_owner_record
+            .remove_expiring_amount(scope_delegation_record.expires_at, amount);
 
         if scope_delegation_record.amount == 0 {
             dispose_account(scope_delegation_record_info, beneficiary_info);
@@ -157,6 +177,22 @@ impl ScopeDelegationRecordAccount {
         }
         Ok(())
     }
+
+    /// Moves the delegated amount of the delegatee to the new expiry
+    pub fn set_expiry(
+        &mut self,
+        expires_at: Option<UnixTimestamp>,
+        delegatee_token_owner_record: &mut VotePowerOwnerRecord,
+    ) {
+        delegatee_token_owner_record.remove_expiring_amount(self.expires_at, self.amount);
+        delegatee_token_owner_record.add_expiring_amount(expires_at, self.amount);
+        self.expires_at = expires_at;
+    }
+
+    /// Whether the delegation was part of the vote weight of a vote cast at the given time
+    pub fn is_included_in_vote(&self, vote_cast_at: UnixTimestamp) -> bool {
+        !matches!(self.expires_at, Some(expires_at) if vote_cast_at >= expires_at)
+    }
 }
 
 pub fn get_delegation_record_data_for_delegator_and_delegatee(
@@ -213,13 +249,10 @@ pub fn get_scope_delegation_record_data(
     delegation_record_info: &AccountInfo,
     token_origin_record: &VotePowerOriginRecord,
     token_origin_record_info: &AccountInfo,
-    governing_owner_info: &AccountInfo,
+    governing_owner: &Pubkey,
     delegatee_vote_power_owner_record_info: &AccountInfo,
 ) -> Result<ScopeDelegationRecordAccount, ProgramError> {
-    if !governing_owner_info.is_signer {
-        return Err(GovernanceError::GoverningTokenOwnerMustSign.into());
-    }
-    if &token_origin_record.governing_owner != governing_owner_info.key {
+    if &token_origin_record.governing_owner != governing_owner {
         return Err(GovernanceError::InvalidTokenOwner.into());
     }
 
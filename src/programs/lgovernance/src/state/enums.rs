@@ -52,6 +52,9 @@ pub enum ProposalState {
     /// Same as Executing but indicates some instructions failed to execute
     /// Proposal can't be transitioned from ExecutingWithErrors to Completed state
     ExecutingWithErrors,
+
+    /// Draft has been finalized but voting starts at a later time (start_voting_at)
+    Scheduled,
 }
 
 impl Default for ProposalState {
@@ -146,3 +149,22 @@ impl MintMaxVoteWeightSource {
     pub const FULL_SUPPLY_FRACTION: MintMaxVoteWeightSource =
         MintMaxVoteWeightSource::SupplyFraction(MintMaxVoteWeightSource::SUPPLY_FRACTION_BASE);
 }
+
+/// Actions of governing owners a SignForMe record can allow, as bits of the action mask of its scope
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GovernanceAction {
+    /// Cast and uncast votes, the resource is the governance
+    Vote,
+
+    /// Delegate and undelegate vote power, the resource is the delegatee
+    Delegate,
+
+    /// Create and finalize proposals, the resource is the governance
+    CreateProposal,
+}
+
+impl GovernanceAction {
+    pub fn action(&self) -> u64 {
+        1 << (*self as u64)
+    }
+}
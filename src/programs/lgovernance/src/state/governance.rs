@@ -2,13 +2,26 @@
 
 use shared::account::{get_account_data, MaxSize};
 
-use crate::accounts::AccountType;
+use crate::{
+    accounts::AccountType,
+    error::GovernanceError,
+    state::{
+        proposal::proposal_transaction::InstructionData,
+        scopes::scope::get_scope_data_for_governance,
+    },
+};
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
-    account_info::AccountInfo, program_error::ProgramError, program_pack::IsInitialized,
+    account_info::{next_account_info, AccountInfo},
+    clock::UnixTimestamp,
+    program_error::ProgramError,
+    program_pack::IsInitialized,
     pubkey::Pubkey,
 };
 
+/// Max number of parent scopes a child governance can be delegated
+pub const MAX_DELEGATED_SCOPES: usize = 8;
+
 /// Governance Account
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
@@ -37,20 +50,160 @@ pub struct GovernanceV2 {
 
     /// Authory that can be used for signing without creating a proposal
     pub optional_authority: Option<Pubkey>,
+
+    /// Parent governance this governance is a sub-dao of
+    pub parent: Option<Pubkey>,
+
+    /// Scopes of the parent governance this governance is allowed to act within
+    pub delegated_scopes: Vec<Pubkey>,
+
+    /// Whether the parent governance has frozen this governance
+    pub frozen: bool,
+
+    /// Number of registered child governances
+    pub children_count: u32,
+
+    /// Who can pause the governance, and for how long
+    pub pause_config: PauseConfig,
+
+    /// The governance is paused until this time
+    pub paused_until: Option<UnixTimestamp>,
+
+    /// The guardian can not pause the governance again before this time
+    pub guardian_cooldown_until: Option<UnixTimestamp>,
 }
 
 impl MaxSize for GovernanceV2 {
     fn get_max_size(&self) -> Option<usize> {
-        Some(1 + 32 + 8 + 4 + 1 + 32)
+        let pause_size = PauseConfig::MAX_SIZE + 1 + 8 + 1 + 8;
+        Some(1 + 32 + 8 + 4 + 1 + 32 + 1 + 32 + 4 + 32 * MAX_DELEGATED_SCOPES + 1 + 4 + pause_size)
     }
 }
 
+/// Emergency pause configuration of a governance
+/// While paused, proposals can not be created,
+/// and can only be finalized, voted on or executed through the unpause scope
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct PauseConfig {
+    /// Key that can pause the governance without a proposal
+    pub guardian: Option<Pubkey>,
+
+    /// Scope whose proposals can pause the governance
+    pub pause_scope: Option<Pubkey>,
+
+    /// Scope whose proposals can unpause the governance before the pause expires
+    pub unpause_scope: Option<Pubkey>,
+
+    /// Max duration of a pause in seconds.
+    /// After a guardian pause the guardian has to wait as long again before pausing another time
+    pub max_pause_duration: u64,
+}
+
+impl PauseConfig {
+    pub const MAX_SIZE: usize = 1 + 32 + 1 + 32 + 1 + 32 + 8;
+}
+
 impl IsInitialized for GovernanceV2 {
     fn is_initialized(&self) -> bool {
         self.account_type == AccountType::Governance
     }
 }
 
+/// Summary of a child governance, returned when querying the children of a governance
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ChildGovernance {
+    /// The child governance
+    pub governance: Pubkey,
+
+    /// Scopes of the parent the child is allowed to act within
+    pub delegated_scopes: Vec<Pubkey>,
+
+    /// Whether the child is frozen
+    pub frozen: bool,
+}
+
+impl GovernanceV2 {
+    /// Asserts the governance is signing, either as PDA (through a proposal) or through its optional authority.
+    /// The optional authority account is read from the iterator only if the governance itself is not a signer.
+    /// Like the proposals of a child governance, its authority can only act within the scopes delegated by the parent,
+    /// so a delegated parent scope applicable to the instruction is read after the authority
+    pub fn assert_governance_signed<'a>(
+        &self,
+        program_id: &Pubkey,
+        governance_info: &AccountInfo<'a>,
+        instruction: &InstructionData,
+        account_info_iter: &mut std::slice::Iter<AccountInfo<'a>>,
+    ) -> Result<(), ProgramError> {
+        if governance_info.is_signer {
+            return Ok(());
+        }
+        if let Some(authority) = &self.optional_authority {
+            let governance_authority = next_account_info(account_info_iter)?;
+            if authority != governance_authority.key {
+                return Err(GovernanceError::InvalidAuthorityForGovernance.into());
+            }
+            if !governance_authority.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if let Some(parent) = &self.parent {
+                let parent_scope_info = next_account_info(account_info_iter)?;
+                if !self.delegated_scopes.contains(parent_scope_info.key) {
+                    return Err(GovernanceError::ScopeNotDelegatedByParent.into());
+                }
+                get_scope_data_for_governance(program_id, parent_scope_info, parent)?
+                    .scope_applicable(instruction)?;
+            }
+            Ok(())
+        } else {
+            Err(ProgramError::MissingRequiredSignature)
+        }
+    }
+
+    /// Asserts the governance has not been frozen by its parent
+    pub fn assert_not_frozen(&self) -> Result<(), ProgramError> {
+        if self.frozen {
+            return Err(GovernanceError::GovernanceFrozen.into());
+        }
+        Ok(())
+    }
+
+    /// Whether the governance is paused at the given time
+    pub fn is_paused(&self, current_unix_timestamp: UnixTimestamp) -> bool {
+        matches!(self.paused_until, Some(paused_until) if paused_until > current_unix_timestamp)
+    }
+
+    /// Asserts the governance is not paused
+    pub fn assert_not_paused(
+        &self,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> Result<(), ProgramError> {
+        if self.is_paused(current_unix_timestamp) {
+            return Err(GovernanceError::GovernancePaused.into());
+        }
+        Ok(())
+    }
+
+    /// Asserts the governance is not paused, unless acting through the unpause scope
+    pub fn assert_not_paused_for_scope(
+        &self,
+        current_unix_timestamp: UnixTimestamp,
+        scope: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if self.pause_config.unpause_scope.as_ref() == Some(scope) {
+            return Ok(());
+        }
+        self.assert_not_paused(current_unix_timestamp)
+    }
+
+    /// Asserts the governance is a child of the parent
+    pub fn assert_is_child_of(&self, parent: &Pubkey) -> Result<(), ProgramError> {
+        if self.parent.as_ref() != Some(parent) {
+            return Err(GovernanceError::InvalidParentGovernance.into());
+        }
+        Ok(())
+    }
+}
+
 /// Deserializes Governance account and checks owner program
 pub fn get_governance_data(
     program_id: &Pubkey,
@@ -58,6 +211,17 @@ pub fn get_governance_data(
 ) -> Result<GovernanceV2, ProgramError> {
     get_account_data::<GovernanceV2>(program_id, governance_info)
 }
+
+/// Deserializes child Governance account and checks that it belongs to the parent
+pub fn get_child_governance_data_for_parent(
+    program_id: &Pubkey,
+    child_governance_info: &AccountInfo,
+    parent: &Pubkey,
+) -> Result<GovernanceV2, ProgramError> {
+    let data = get_governance_data(program_id, child_governance_info)?;
+    data.assert_is_child_of(parent)?;
+    Ok(data)
+}
 /// Returns Governance PDA seeds
 pub fn get_governance_address_seeds<'a>(
     seed: &'a Pubkey,
@@ -7,6 +7,7 @@ pub mod native_treasury;
 pub mod proposal;
 pub mod realm;
 pub mod scopes;
+pub mod signed_vote;
 pub mod token_owner_budget_record;
 pub mod vote_power_origin_record;
 pub mod vote_power_owner_record;
@@ -55,13 +55,11 @@ pub enum VoteType {
     MultiChoice {
         /// The max number of options a voter can choose
         /// By default it equals to the number of available options
-        #[allow(dead_code)]
         max_voter_options: Option<u8>,
 
         /// The max number of wining options
         /// For executable proposals it limits how many options can be executed for a Proposal
         /// By default it equals to the number of available options
-        #[allow(dead_code)]
         max_winning_options: Option<u8>,
     },
 }
@@ -127,7 +125,7 @@ pub struct ProposalV2 {
     /// Does deny option exist if this has some value
     pub deny_option: Option<Pubkey>,
 
-    /// Winning options
+    /// Winning options, ranked by their vote weights per scope with the lowest index first on ties
     pub winning_options: Vec<u16>,
 
     pub defeated_options: Vec<u16>,
@@ -152,8 +150,8 @@ pub struct ProposalV2 {
     /// Note: Abstain is not supported in the current version
     pub abstain_vote_weight: Option<u64>, */
 
-    /// Optional start time if the Proposal should not enter voting state immediately after being signed off
-    /// Note: start_at is not supported in the current version
+    /// Optional start time if the Proposal should not enter voting state immediately after the draft is finalized
+    /// Until then the Proposal is in Scheduled state
     pub start_voting_at: Option<UnixTimestamp>,
 
     /// When the Proposal was created and entered Draft state
@@ -207,6 +205,10 @@ pub struct ProposalV2 {
 
     /// Info
     pub source: ContentSource,
+
+    /// Vote weights of each winning option, in the same order as winning_options
+    /// Every entry has one weight per scope, in the order of the proposal scopes
+    pub winning_options_weights: Vec<Vec<u64>>,
 }
 
 impl IsInitialized for ProposalV2 {
@@ -241,8 +243,8 @@ impl ProposalV2 {
         }
     } */
 
-    pub fn assert_can_finalize_draft(&self, creator: &AccountInfo) -> Result<(), ProgramError> {
-        self.assert_edit_authority(creator)?;
+    pub fn assert_can_finalize_draft(&self, creator: &Pubkey) -> Result<(), ProgramError> {
+        self.assert_creator(creator)?;
         if self.state != ProposalState::Draft {
             return Err(GovernanceError::InvalidStateCannotFinalizeDraft.into());
         }
@@ -263,7 +265,12 @@ impl ProposalV2 {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
-        if &self.creator != creator.key {
+        self.assert_creator(creator.key)
+    }
+
+    // Checks the verified creator
+    fn assert_creator(&self, creator: &Pubkey) -> Result<(), ProgramError> {
+        if &self.creator != creator {
             return Err(GovernanceError::InvalidCreatorForProposal.into());
         }
         Ok(())
@@ -279,26 +286,144 @@ impl ProposalV2 {
     }
 
     /// Checks if Proposal can be voted on
-    /*   pub fn assert_can_cast_vote(
-           &self,
-           current_unix_timestamp: UnixTimestamp,
-       ) -> Result<(), ProgramError> {
-           self.assert_is_voting_state()
-               .map_err(|_| PostError::InvalidStateCannotVote)?;
-
-           // Check if we are still within the configured max_voting_time period
-           if self.has_vote_time_ended(&self.strictest_time_config, current_unix_timestamp) {
-               return Err(PostError::ProposalVotingTimeExpired.into());
-           }
-
-           Ok(())
-       }
-    */
+    pub fn assert_can_cast_vote(
+        &self,
+        config: &ScopeTimeConfig,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> Result<(), ProgramError> {
+        if self.state == ProposalState::Scheduled {
+            return Err(GovernanceError::VotingNotStarted.into());
+        }
+
+        self.assert_is_voting_state()
+            .map_err(|_| GovernanceError::InvalidStateCannotVote)?;
+
+        // Check if we are still within the configured max_voting_time period
+        if self.has_vote_time_ended(config, current_unix_timestamp) {
+            return Err(GovernanceError::ProposalVotingTimeExpired.into());
+        }
+
+        Ok(())
+    }
+
+    /// Moves a finalized draft to Voting, or to Scheduled if start_voting_at is in the future
+    pub fn start_voting_or_schedule(&mut self, current_unix_timestamp: UnixTimestamp, slot: Slot) {
+        match self.start_voting_at {
+            Some(start_voting_at) if start_voting_at > current_unix_timestamp => {
+                self.state = ProposalState::Scheduled;
+            }
+            _ => {
+                self.state = ProposalState::Voting;
+                self.voting_at = Some(current_unix_timestamp);
+                self.voting_at_slot = Some(slot);
+            }
+        }
+    }
+
+    /// Checks the Proposal is waiting for voting to start
+    pub fn assert_is_scheduled_state(&self) -> Result<(), ProgramError> {
+        if self.state != ProposalState::Scheduled {
+            return Err(GovernanceError::InvalidProposalState.into());
+        }
+
+        Ok(())
+    }
+
+    /// Moves a Scheduled Proposal to Voting if start_voting_at has been reached
+    /// Voting time is counted from start_voting_at, no matter when the transition is made
+    pub fn try_start_scheduled_voting(
+        &mut self,
+        current_unix_timestamp: UnixTimestamp,
+        slot: Slot,
+    ) -> Result<(), ProgramError> {
+        if self.state != ProposalState::Scheduled {
+            return Ok(());
+        }
+
+        let start_voting_at = self.start_voting_at.unwrap();
+        if start_voting_at > current_unix_timestamp {
+            return Err(GovernanceError::VotingNotStarted.into());
+        }
+
+        self.state = ProposalState::Voting;
+        self.voting_at = Some(start_voting_at);
+        self.voting_at_slot = Some(slot);
+        Ok(())
+    }
 
     pub fn set_completed_voting_state(&mut self, state: ProposalState, unix_timestamp: i64) {
         self.state = state;
         self.voting_completed_at = Some(unix_timestamp);
     }
+    /// Adds a winning option, keeping the winning options ranked by their vote weights per scope
+    /// Weights of different scopes can be of different units (tokens, tags) so they are never summed,
+    /// the scopes are compared one by one in the order of the proposal scopes and the first scope that differs decides.
+    /// Options with the same weights are ranked by their index, so the ranking is deterministic
+    pub fn add_winning_option(&mut self, option_index: u16, weights: Vec<u64>) {
+        let position = self
+            .winning_options
+            .iter()
+            .zip(&self.winning_options_weights)
+            .position(|(index, winning_weights)| {
+                weights > *winning_weights || (weights == *winning_weights && option_index < *index)
+            })
+            .unwrap_or(self.winning_options.len());
+        self.winning_options.insert(position, option_index);
+        self.winning_options_weights.insert(position, weights);
+    }
+
+    /// The max number of options that can win the Proposal
+    pub fn get_max_winning_options(&self) -> usize {
+        match &self.vote_type {
+            VoteType::SingleChoice => 1,
+            VoteType::MultiChoice {
+                max_winning_options,
+                ..
+            } => max_winning_options
+                .map(|max| max as usize)
+                .unwrap_or(self.options_count as usize),
+        }
+    }
+
+    /// Resolves the final proposal state once every option has been counted
+    /// Only the max_winning_options best ranked options win, the rest are defeated.
+    /// A SingleChoice proposal is defeated if the best options have the same weight,
+    /// and any proposal is defeated if the deny option wins or no option wins.
+    /// Only a proposal in Voting state is resolved, so a decided proposal keeps its state and completion time
+    pub fn try_resolve_vote_state(
+        &mut self,
+        deny_option_index: Option<u16>,
+        current_unix_timestamp: UnixTimestamp,
+    ) {
+        if self.state != ProposalState::Voting {
+            return;
+        }
+        if (self.defeated_options.len() + self.winning_options.len()) as u16 != self.options_count {
+            return;
+        }
+
+        let deny_option_won = deny_option_index
+            .map(|index| self.winning_options.contains(&index))
+            .unwrap_or(false);
+        let single_choice_tie = self.vote_type == VoteType::SingleChoice
+            && self.winning_options_weights.len() > 1
+            && self.winning_options_weights[0] == self.winning_options_weights[1];
+
+        let max_winning_options = self.get_max_winning_options();
+        if self.winning_options.len() > max_winning_options {
+            let mut outranked = self.winning_options.split_off(max_winning_options);
+            self.winning_options_weights.truncate(max_winning_options);
+            self.defeated_options.append(&mut outranked);
+        }
+
+        let state = if deny_option_won || single_choice_tie || self.winning_options.is_empty() {
+            ProposalState::Defeated
+        } else {
+            ProposalState::Succeeded
+        };
+        self.set_completed_voting_state(state, current_unix_timestamp);
+    }
+
     /// Checks whether the voting time has ended for the proposal
     pub fn has_vote_time_ended(
         &self,
@@ -306,11 +431,16 @@ impl ProposalV2 {
         current_unix_timestamp: UnixTimestamp,
     ) -> bool {
         // Check if we passed vote_end_time determined by the configured max_voting_time period
-        self.voting_at
-            .unwrap()
-            .checked_add(config.max_voting_time as i64)
-            .unwrap()
-            < current_unix_timestamp
+        // Voting has not ended if it has not started
+        match self.voting_at {
+            Some(voting_at) => {
+                voting_at
+                    .checked_add(config.max_voting_time as i64)
+                    .unwrap()
+                    < current_unix_timestamp
+            }
+            None => false,
+        }
     }
 
     /// Checks if Proposal can be finalized
@@ -633,6 +763,16 @@ impl ProposalV2 {
         Ok(())
     }
 
+    /// Checks if the source can be edited for the Proposal
+    pub fn assert_can_edit_source(&self, creator_info: &AccountInfo) -> Result<(), ProgramError> {
+        self.assert_edit_authority(creator_info)?;
+
+        if self.assert_is_draft_state().is_err() {
+            return Err(GovernanceError::InvalidStateCannotEditSource.into());
+        }
+        Ok(())
+    }
+
     /// Checks if Instructions can be executed for the Proposal in the given state
     pub fn assert_can_execute_transaction(
         &self,
@@ -645,6 +785,7 @@ impl ProposalV2 {
             | ProposalState::Executing
             | ProposalState::ExecutingWithErrors => {}
             ProposalState::Draft
+            | ProposalState::Scheduled
             | ProposalState::Completed
             | ProposalState::Voting
             | ProposalState::Cancelled
@@ -661,6 +802,11 @@ impl ProposalV2 {
             return Err(GovernanceError::CannotExecuteDefeatedOption.into());
         }
 
+        // Options that succeeded but were outranked by max_winning_options are not winners
+        if !self.winning_options.contains(&proposal_option_data.index) {
+            return Err(GovernanceError::OptionNotWinning.into());
+        }
+
         if self
             .voting_completed_at
             .unwrap()
@@ -724,10 +870,19 @@ impl ProposalV2 {
             VoteType::MultiChoice {
                 max_voter_options, ..
             } => {
-                if let Some(max_options) = max_voter_options {
-                    if vote.len() > max_options as usize {
-                        return Err(GovernanceError::InvalidVote.into());
-                    }
+                let max_options = max_voter_options
+                    .map(|max| max as usize)
+                    .unwrap_or(self.options_count as usize);
+                if vote.len() > max_options {
+                    return Err(GovernanceError::InvalidVote.into());
+                }
+
+                // Every option can only be chosen once
+                let mut options = vote.clone();
+                options.sort_unstable();
+                options.dedup();
+                if options.len() != vote.len() {
+                    return Err(GovernanceError::InvalidVote.into());
                 }
             }
         }
@@ -847,19 +1002,15 @@ pub fn get_proposal_data_for_governance(
     Ok(proposal_data)
 }
 
-/// Deserializes Proposal and validates it belongs to the given Creator
+/// Deserializes Proposal and validates it belongs to the given verified Creator
 pub fn get_proposal_data_for_creator(
     program_id: &Pubkey,
     proposal_info: &AccountInfo,
-    creator_info: &AccountInfo,
+    creator: &Pubkey,
 ) -> Result<ProposalV2, ProgramError> {
-    if !creator_info.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
-
     let proposal_data = get_proposal_data(program_id, proposal_info)?;
 
-    if &proposal_data.creator != creator_info.key {
+    if &proposal_data.creator != creator {
         return Err(GovernanceError::InvalidProposalOwnerAccount.into());
     }
 
@@ -1920,3 +2071,136 @@ mod test {
     }
 }
  */
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_proposal(vote_type: VoteType, options_count: u16) -> ProposalV2 {
+        ProposalV2 {
+            account_type: AccountType::Proposal,
+            governance: Pubkey::new_unique(),
+            state: ProposalState::Voting,
+            creator: Pubkey::new_unique(),
+            signatories_count: 0,
+            signatories_signed_off_count: 0,
+            vote_type,
+            options_count,
+            options_counted_count: 0,
+            options_executed_count: 0,
+            scopes_count: 0,
+            scopes_max_vote_weight: Vec::new(),
+            deny_option: None,
+            winning_options: Vec::new(),
+            defeated_options: Vec::new(),
+            start_voting_at: None,
+            draft_at: 0,
+            signing_off_at: None,
+            voting_at: Some(0),
+            max_vote_weights_calculated_at: None,
+            voting_at_slot: None,
+            voting_completed_at: None,
+            executing_at: None,
+            closed_at: None,
+            execution_flags: InstructionExecutionFlags::None,
+            max_voting_time: None,
+            vote_threshold_percentage: None,
+            source: ContentSource::String("Info".into()),
+            winning_options_weights: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_max_winning_options_ranks_winners() {
+        let mut proposal = create_test_proposal(
+            VoteType::MultiChoice {
+                max_voter_options: None,
+                max_winning_options: Some(2),
+            },
+            4,
+        );
+
+        proposal.add_winning_option(3, vec![10]);
+        proposal.add_winning_option(0, vec![5]);
+        proposal.add_winning_option(2, vec![20]);
+        proposal.defeated_options.push(1);
+        proposal.try_resolve_vote_state(None, 1);
+
+        assert_eq!(proposal.state, ProposalState::Succeeded);
+        assert_eq!(proposal.winning_options, vec![2, 3]);
+        assert_eq!(proposal.winning_options_weights, vec![vec![20], vec![10]]);
+        assert_eq!(proposal.defeated_options, vec![1, 0]);
+    }
+
+    #[test]
+    fn test_max_winning_options_ranks_per_scope() {
+        let mut proposal = create_test_proposal(
+            VoteType::MultiChoice {
+                max_voter_options: None,
+                max_winning_options: Some(1),
+            },
+            3,
+        );
+
+        // The weights of the second scope are not added to the first scope
+        proposal.add_winning_option(0, vec![5, 100]);
+        proposal.add_winning_option(1, vec![10, 0]);
+        proposal.add_winning_option(2, vec![10, 1]);
+        proposal.try_resolve_vote_state(None, 1);
+
+        assert_eq!(proposal.state, ProposalState::Succeeded);
+        assert_eq!(proposal.winning_options, vec![2]);
+        assert_eq!(proposal.defeated_options, vec![1, 0]);
+    }
+
+    #[test]
+    fn test_max_winning_options_tie_break_by_index() {
+        let mut proposal = create_test_proposal(
+            VoteType::MultiChoice {
+                max_voter_options: None,
+                max_winning_options: Some(1),
+            },
+            2,
+        );
+
+        proposal.add_winning_option(1, vec![10]);
+        proposal.add_winning_option(0, vec![10]);
+        proposal.try_resolve_vote_state(None, 1);
+
+        assert_eq!(proposal.state, ProposalState::Succeeded);
+        assert_eq!(proposal.winning_options, vec![0]);
+        assert_eq!(proposal.defeated_options, vec![1]);
+    }
+
+    #[test]
+    fn test_single_choice_tie_is_defeated() {
+        let mut proposal = create_test_proposal(VoteType::SingleChoice, 2);
+
+        proposal.add_winning_option(0, vec![10]);
+        proposal.add_winning_option(1, vec![10]);
+        proposal.try_resolve_vote_state(None, 1);
+
+        assert_eq!(proposal.state, ProposalState::Defeated);
+    }
+
+    #[test]
+    fn test_assert_valid_vote_max_voter_options() {
+        let proposal = create_test_proposal(
+            VoteType::MultiChoice {
+                max_voter_options: Some(2),
+                max_winning_options: None,
+            },
+            3,
+        );
+
+        assert!(proposal.assert_valid_vote(&vec![0, 2]).is_ok());
+        assert_eq!(
+            proposal.assert_valid_vote(&vec![0, 1, 2]),
+            Err(GovernanceError::InvalidVote.into())
+        );
+        assert_eq!(
+            proposal.assert_valid_vote(&vec![1, 1]),
+            Err(GovernanceError::InvalidVote.into())
+        );
+    }
+}
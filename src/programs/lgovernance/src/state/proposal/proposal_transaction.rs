@@ -40,6 +40,24 @@ pub struct AccountMetaData {
     pub is_writable: bool,
 }
 
+impl InstructionData {
+    /// The instruction being processed, as it would be inserted into a proposal
+    pub fn from_account_infos(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> Self {
+        InstructionData {
+            program_id: *program_id,
+            accounts: accounts
+                .iter()
+                .map(|account_info| AccountMetaData {
+                    pubkey: *account_info.key,
+                    is_signer: account_info.is_signer,
+                    is_writable: account_info.is_writable,
+                })
+                .collect(),
+            data: data.to_vec(),
+        }
+    }
+}
+
 impl From<Instruction> for InstructionData {
     fn from(instruction: Instruction) -> Self {
         InstructionData {
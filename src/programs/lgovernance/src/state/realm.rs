@@ -1,28 +1,118 @@
-use solana_program::pubkey::Pubkey;
-/*
-use crate::accounts::AccountType;
+//! Realm Account
 
-// The root of an org
-#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
+use shared::{
+    account::{get_account_data, MaxSize},
+    seeds::generate_seeds_from_string,
+};
+
+use crate::{accounts::AccountType, error::GovernanceError};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    program_pack::IsInitialized,
+    pubkey::{Pubkey, PubkeyError, MAX_SEEDS},
+};
+
+use super::scopes::scope::VotePowerUnit;
+
+/// The root of an org, registry of everything belonging to it
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct Realm {
-    acccount_type: AccountType,
+    /// Governance account type
+    pub account_type: AccountType,
+
+    /// Name of the Realm, the Realm address is derived from it
+    pub name: String,
+
+    /// Authority that can update the Realm
+    pub authority: Option<Pubkey>,
+
+    /// Governing token mints accepted by the Realm
+    pub governing_mints: Vec<Pubkey>,
+
+    /// Tag record factories accepted by the Realm
+    pub tag_record_factories: Vec<Pubkey>,
+
+    /// Min weight of an accepted vote power unit required to add a governance to the Realm
+    pub min_weight_to_create_governance: u64,
+
+    /// Governances of the Realm
+    pub governances: Vec<Pubkey>,
 }
 
-pub fn create_realm_program_address_seeds(name: &str) -> Result<Vec<Vec<u8>>, PubkeyError> {
-    let mut seeds = generate_seeds_from_string(name)?;
-    seeds.push(b"realm".to_vec());
-    seeds.reverse();
-    Ok(seeds)
+impl MaxSize for Realm {
+    fn get_max_size(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl IsInitialized for Realm {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::Realm
+    }
 }
-pub fn find_realm_program_address(
-    name: &str,
+
+impl Realm {
+    /// Checks the Realm authority signed the transaction
+    pub fn assert_authority_signed(
+        &self,
+        authority_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        match self.authority {
+            Some(authority) => {
+                if &authority != authority_info.key {
+                    return Err(GovernanceError::InvalidAuthorityForRealm.into());
+                }
+                if !authority_info.is_signer {
+                    return Err(GovernanceError::RealmAuthorityMustSign.into());
+                }
+                Ok(())
+            }
+            None => Err(GovernanceError::RealmHasNoAuthority.into()),
+        }
+    }
+
+    /// Whether the vote power unit is accepted by the Realm
+    pub fn accepts(&self, unit: &VotePowerUnit) -> bool {
+        match unit {
+            VotePowerUnit::Mint(mint) => self.governing_mints.contains(mint),
+            VotePowerUnit::Tag { record_factory } => {
+                self.tag_record_factories.contains(record_factory)
+            }
+        }
+    }
+}
+
+/// Deserializes Realm account and checks owner program
+pub fn get_realm_data(
     program_id: &Pubkey,
-) -> Result<(Pubkey, u8), PubkeyError> {
-    let seeds = create_realm_program_address_seeds(name)?;
+    realm_info: &AccountInfo,
+) -> Result<Realm, ProgramError> {
+    get_account_data::<Realm>(program_id, realm_info)
+}
+
+/// Returns Realm PDA seeds, without bump seed
+pub fn get_realm_address_seeds(name: &str) -> Result<Vec<Vec<u8>>, PubkeyError> {
+    let mut seeds = vec![REALM_ACCOUNT_SEED.to_vec()];
+    seeds.append(&mut generate_seeds_from_string(name)?);
+
+    // Leave room for the bump seed
+    if seeds.len() >= MAX_SEEDS {
+        return Err(PubkeyError::MaxSeedLengthExceeded);
+    }
+    Ok(seeds)
+}
+
+/// Returns Realm PDA address
+pub fn get_realm_address(program_id: &Pubkey, name: &str) -> Result<(Pubkey, u8), PubkeyError> {
+    let seeds = get_realm_address_seeds(name)?;
     let seed_slice = &seeds.iter().map(|x| &x[..]).collect::<Vec<&[u8]>>()[..];
     Ok(Pubkey::find_program_address(seed_slice, program_id))
 }
- */
+
+const REALM_ACCOUNT_SEED: &[u8] = b"realm_account";
 const REALM_SEED: &[u8] = b"realm";
 const REALM_AUTHORITY_SEED: &[u8] = b"realm_authority";
 
@@ -1,7 +1,10 @@
 use std::slice::Iter;
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
-use ltag::state::get_tag_record_data_with_factory_and_signed_owner;
+use ltag::{
+    condition::{assert_tag_condition, TagCondition},
+    state::get_tag_record_data_with_factory_and_owner,
+};
 use shared::{
     account::{get_account_data, MaxSize},
     content::ContentSource,
@@ -16,9 +19,11 @@ use solana_program::{
 use crate::{
     accounts::AccountType,
     error::GovernanceError,
+    processor::utils::verify_signed_owner_maybe_sign_for_me_once,
     state::{
+        enums::GovernanceAction,
         proposal::{proposal_transaction::InstructionData, ProposalV2},
-        vote_power_owner_record::get_vote_power_owner_record_data_for_owner,
+        vote_power_owner_record::get_vote_power_owner_record_data_for_verified_owner,
     },
 };
 
@@ -177,31 +182,44 @@ pub struct ScopeProposalConfig {
 }
 
 impl ScopeProposalConfig {
-    pub fn assert_can_create_proposal(
+    pub fn assert_can_create_proposal<'a>(
         &self,
         program_id: &Pubkey,
-        _proposal: &ProposalV2,
-        accounts: &mut Iter<AccountInfo>,
+        proposal: &ProposalV2,
+        accounts: &mut Iter<AccountInfo<'a>>,
+        verified_owners: &mut Vec<Pubkey>,
     ) -> Result<(), ProgramError> {
         match &self.create_proposal_criteria {
             CreateProposalCriteria::Tag { record_factory } => {
                 let tag_record_info = next_account_info(accounts)?;
-                let tag_record_owner = next_account_info(accounts)?;
-                let _tag_record_data = get_tag_record_data_with_factory_and_signed_owner(
+                let tag_record_owner = verify_signed_owner_maybe_sign_for_me_once(
+                    next_account_info(accounts)?,
+                    &proposal.governance,
+                    GovernanceAction::CreateProposal,
+                    accounts,
+                    verified_owners,
+                )?;
+                let _tag_record_data = get_tag_record_data_with_factory_and_owner(
                     &ltag::id(),
                     tag_record_info,
                     record_factory,
-                    tag_record_owner,
+                    &tag_record_owner,
                 )?;
                 Ok(())
             }
             CreateProposalCriteria::Token { amount, mint } => {
                 let token_owner_record = next_account_info(accounts)?;
-                let governing_owner_info = next_account_info(accounts)?;
-                let token_owner_record_data = get_vote_power_owner_record_data_for_owner(
+                let governing_owner = verify_signed_owner_maybe_sign_for_me_once(
+                    next_account_info(accounts)?,
+                    &proposal.governance,
+                    GovernanceAction::CreateProposal,
+                    accounts,
+                    verified_owners,
+                )?;
+                let token_owner_record_data = get_vote_power_owner_record_data_for_verified_owner(
                     program_id,
                     token_owner_record,
-                    governing_owner_info,
+                    &governing_owner,
                 )?;
 
                 if let VotePowerUnit::Mint(governing_token_mint) = &token_owner_record_data.source {
@@ -217,6 +235,16 @@ impl ScopeProposalConfig {
                     Err(GovernanceError::InvalidVotePowerSource.into())
                 }
             }
+            CreateProposalCriteria::Tags(condition) => {
+                let tag_record_owner = verify_signed_owner_maybe_sign_for_me_once(
+                    next_account_info(accounts)?,
+                    &proposal.governance,
+                    GovernanceAction::CreateProposal,
+                    accounts,
+                    verified_owners,
+                )?;
+                assert_tag_condition(&ltag::id(), condition, &tag_record_owner, accounts)
+            }
         }
     }
 }
@@ -288,8 +316,16 @@ impl ScopeConfig {
 }
 #[derive(Clone, Debug, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq)]
 pub enum CreateProposalCriteria {
-    Tag { record_factory: Pubkey },
-    Token { mint: Pubkey, amount: u64 },
+    Tag {
+        record_factory: Pubkey,
+    },
+    Token {
+        mint: Pubkey,
+        amount: u64,
+    },
+
+    /// Creator satisfying a condition over tag records
+    Tags(TagCondition),
 }
 
 #[derive(Clone, Debug, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq)]
@@ -0,0 +1,139 @@
+//! Signed Vote
+//! Votes signed off-chain by the governing owner and submitted by a relayer
+
+use shared::account::{get_account_data, MaxSize};
+
+use crate::{accounts::AccountType, error::GovernanceError};
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, program_pack::IsInitialized,
+    pubkey::Pubkey,
+};
+
+use super::vote_record::Vote;
+
+/// The canonical message a governing owner signs to vote off-chain
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct SignedVote {
+    /// Proposal to vote on
+    pub proposal: Pubkey,
+
+    /// Indices of the options to vote for
+    pub options: Vote,
+
+    /// The scope to vote through
+    pub scope: Pubkey,
+
+    /// Nonce, has to be greater than the last nonce used by the governing owner for the proposal
+    pub nonce: u64,
+}
+
+/// Domain tag of signed vote messages, so a signature over a vote can not be used as another kind of message
+pub const SIGNED_VOTE_DOMAIN: &[u8] = b"lgovernance:signed_vote";
+
+impl SignedVote {
+    /// The message to sign, the serialized vote prefixed by the domain tag and the governance program id
+    pub fn to_message(&self, program_id: &Pubkey) -> Vec<u8> {
+        let mut message = [SIGNED_VOTE_DOMAIN, program_id.as_ref()].concat();
+        message.extend_from_slice(&self.try_to_vec().unwrap());
+        message
+    }
+
+    /// Deserializes the vote of a message signed for the governance program
+    pub fn from_message(program_id: &Pubkey, message: &[u8]) -> Result<Self, ProgramError> {
+        message
+            .strip_prefix(SIGNED_VOTE_DOMAIN)
+            .and_then(|message| message.strip_prefix(program_id.as_ref()))
+            .and_then(|vote| Self::try_from_slice(vote).ok())
+            .ok_or_else(|| GovernanceError::InvalidSignedVote.into())
+    }
+}
+
+/// Record of the last signed vote nonce used by a governing owner for a proposal
+/// Prevents signed votes from being replayed
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct SignedVoteNonceRecord {
+    /// Governance account type
+    pub account_type: AccountType,
+
+    /// The proposal
+    pub proposal: Pubkey,
+
+    /// The governing owner who signed the votes
+    pub governing_owner: Pubkey,
+
+    /// Last used nonce
+    pub nonce: u64,
+}
+
+impl MaxSize for SignedVoteNonceRecord {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 32 + 32 + 8)
+    }
+}
+
+impl IsInitialized for SignedVoteNonceRecord {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::SignedVoteNonceRecord
+    }
+}
+
+impl SignedVoteNonceRecord {
+    /// Asserts the nonce has not been used and consumes it
+    pub fn use_nonce(&mut self, nonce: u64) -> Result<(), ProgramError> {
+        if nonce <= self.nonce {
+            return Err(GovernanceError::SignedVoteNonceAlreadyUsed.into());
+        }
+        self.nonce = nonce;
+        Ok(())
+    }
+}
+
+/// Returns SignedVoteNonceRecord PDA address
+pub fn get_signed_vote_nonce_record_address(
+    program_id: &Pubkey,
+    proposal: &Pubkey,
+    governing_owner: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"signed_vote_nonce",
+            proposal.as_ref(),
+            governing_owner.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Returns SignedVoteNonceRecord PDA seeds
+pub fn get_signed_vote_nonce_record_address_seeds<'a>(
+    proposal: &'a Pubkey,
+    governing_owner: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 4] {
+    [
+        b"signed_vote_nonce",
+        proposal.as_ref(),
+        governing_owner.as_ref(),
+        bump_seed,
+    ]
+}
+
+/// Deserializes SignedVoteNonceRecord account and checks it belongs to the proposal and governing owner
+pub fn get_signed_vote_nonce_record_data(
+    program_id: &Pubkey,
+    nonce_record_info: &AccountInfo,
+    proposal: &Pubkey,
+    governing_owner: &Pubkey,
+) -> Result<SignedVoteNonceRecord, ProgramError> {
+    let data = get_account_data::<SignedVoteNonceRecord>(program_id, nonce_record_info)?;
+    if &data.proposal != proposal {
+        return Err(GovernanceError::InvalidProposalForVoterRecord.into());
+    }
+    if &data.governing_owner != governing_owner {
+        return Err(GovernanceError::InvalidGoverningTokenOwnerForVoteRecord.into());
+    }
+    Ok(data)
+}
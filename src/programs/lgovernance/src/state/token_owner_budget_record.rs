@@ -25,53 +25,54 @@ pub struct TokenOwnerBudgetRecord {
     /// Budget for scope
     pub scope: Pubkey,
 
-    /// The budget
+    /// The share of the deposit given to the scope
+    pub budget: u64,
+
+    /// The part of the budget that is not delegated yet
+    /// Direct voting is done by delegating to oneself, so the whole budget is spent through delegations
     pub amount: u64,
 }
 
 impl TokenOwnerBudgetRecord {
-    /*   pub fn spend<'a>(
-    program_id: &Pubkey,
-    amount: u64,
-    token_owner_budget_record: &mut TokenOwnerBudgetRecord,
-    token_owner_budget_record_info: &AccountInfo<'a>, */
-    /*  token_owner_record: &VotePowerOwnerRecord,
-    vote_power_owner_record_info: &AccountInfo<'a>,
-    governing_owner_info: &AccountInfo<'a>, */
-    /*    ) -> Result<(), ProgramError> {
-        token_owner_budget_record.amount = token_owner_budget_record
+    /// Spends from the budget when delegating in the scope
+    pub fn spend(&mut self, amount: u64) -> Result<(), ProgramError> {
+        self.amount = self
             .amount
             .checked_sub(amount)
-            .unwrap();
+            .ok_or(GovernanceError::NotEnoughBudget)?;
+        Ok(())
+    }
 
-        if token_owner_budget_record.amount < 0 {
-            return Err(ProgramError::InvalidArgument);
+    /// Returns to the budget when undelegating in the scope
+    pub fn unspend(&mut self, amount: u64) -> Result<(), ProgramError> {
+        let amount = self.amount.checked_add(amount).unwrap();
+        if amount > self.budget {
+            return Err(GovernanceError::InvalidBudget.into());
         }
-
-        token_owner_budget_record
-            .serialize(&mut *token_owner_budget_record_info.data.borrow_mut())?;
-
+        self.amount = amount;
         Ok(())
-    } */
+    }
 
-    /*   pub fn unspend<'a>(
-    program_id: &Pubkey,
-    amount: u64,
-    token_owner_budget_record: &mut TokenOwnerBudgetRecord, */
-    /* token_owner_budget_record_info: &AccountInfo<'a>,
-    token_owner_record: &VotePowerOwnerRecord,
-     vote_power_owner_record_info: &AccountInfo<'a>,
-     governing_owner_info: &AccountInfo<'a>, */
-    /*     ) -> Result<(), ProgramError> {
-        token_owner_budget_record.amount = token_owner_budget_record
-            .amount
-            .checked_add(amount)
-            .unwrap();
+    /// The part of the budget that is delegated, and can be committed to active votes
+    pub fn committed_amount(&self) -> u64 {
+        self.budget.checked_sub(self.amount).unwrap()
+    }
 
-        token_owner_budget_record
-            .serialize(&mut *token_owner_budget_record_info.data.borrow_mut())?;
+    /// Changes the share of the deposit given to the scope
+    /// The committed part can only be released by undelegating, which is blocked while
+    /// the delegation is used in active votes
+    pub fn set_budget(&mut self, budget: u64, deposit: u64) -> Result<(), ProgramError> {
+        if budget > deposit {
+            return Err(GovernanceError::InvalidBudget.into());
+        }
+        let committed_amount = self.committed_amount();
+        if budget < committed_amount {
+            return Err(GovernanceError::BudgetCommitted.into());
+        }
+        self.budget = budget;
+        self.amount = budget - committed_amount;
         Ok(())
-    } */
+    }
 }
 
 impl MaxSize for TokenOwnerBudgetRecord {
@@ -140,12 +141,9 @@ pub fn get_token_owner_budget_record_data_for_token_record(
     token_owner_budget_record_info: &AccountInfo,
     token_origin_record: &VotePowerOriginRecord,
     token_origin_record_info: &AccountInfo,
-    governing_owner_info: &AccountInfo,
+    governing_owner: &Pubkey,
 ) -> Result<TokenOwnerBudgetRecord, ProgramError> {
-    if !governing_owner_info.is_signer {
-        return Err(GovernanceError::GoverningTokenOwnerMustSign.into());
-    }
-    if &token_origin_record.governing_owner != governing_owner_info.key {
+    if &token_origin_record.governing_owner != governing_owner {
         return Err(GovernanceError::InvalidTokenOwner.into());
     }
 
@@ -1,3 +1,4 @@
+use ltag::state::get_tag_record_data_with_factory_and_owner;
 use shared::account::{create_and_serialize_account_verify_with_bump, get_account_data, MaxSize};
 
 use crate::{
@@ -7,8 +8,8 @@ use crate::{
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
-    account_info::AccountInfo, program_error::ProgramError, program_pack::IsInitialized,
-    pubkey::Pubkey, rent::Rent,
+    account_info::AccountInfo, clock::UnixTimestamp, entrypoint::ProgramResult,
+    program_error::ProgramError, program_pack::IsInitialized, pubkey::Pubkey, rent::Rent,
 };
 /*
 #[repr(C)]
@@ -84,11 +85,14 @@ pub struct VotePowerOriginRecord {
     /// The owner (either single or multisig) of the deposited governing SPL Tokens
     /// This is who can authorize a withdrawal of the tokens
     pub governing_owner: Pubkey,
+
+    /// Expiry of the deposited tag record, refreshed by depositing the renewed record again
+    pub expires_at: Option<UnixTimestamp>,
 }
 
 impl MaxSize for VotePowerOriginRecord {
     fn get_max_size(&self) -> Option<usize> {
-        Some(1 + 1 + 32 + 8 + 32)
+        Some(1 + 1 + 32 + 8 + 32 + 1 + 8)
     }
 }
 
@@ -99,6 +103,57 @@ impl IsInitialized for VotePowerOriginRecord {
 }
 
 impl VotePowerOriginRecord {
+    pub fn assert_not_expired(&self, current_unix_timestamp: UnixTimestamp) -> ProgramResult {
+        if matches!(self.expires_at, Some(expires_at) if current_unix_timestamp >= expires_at) {
+            return Err(GovernanceError::VotePowerExpired.into());
+        }
+        Ok(())
+    }
+
+    /// Verifies the live tag record still backs the deposit.
+    /// Tag records can be revoked, suspended or reclaimed after they are deposited,
+    /// so the record is checked again whenever the deposit is used
+    pub fn assert_backed_by_tag_record(
+        &self,
+        program_id: &Pubkey,
+        vote_power_origin_record_info: &AccountInfo,
+        tag_record_info: &AccountInfo,
+    ) -> ProgramResult {
+        let record_factory = match &self.source {
+            VotePowerUnit::Tag { record_factory } => record_factory,
+            VotePowerUnit::Mint(_) => return Ok(()),
+        };
+
+        let tag_record = get_tag_record_data_with_factory_and_owner(
+            &ltag::id(),
+            tag_record_info,
+            record_factory,
+            &self.governing_owner,
+        )?;
+
+        let vote_power_origin_record_address = match tag_record.index {
+            Some(index) => get_indexed_vote_power_origin_record_address(
+                program_id,
+                &self.source,
+                &self.governing_owner,
+                index,
+            ),
+            None => get_vote_power_origin_record_address(
+                program_id,
+                &self.source,
+                &self.governing_owner,
+            ),
+        }
+        .0;
+
+        if &vote_power_origin_record_address != vote_power_origin_record_info.key
+            || tag_record.quantity < self.amount
+        {
+            return Err(GovernanceError::TagDepositNotBacked.into());
+        }
+        Ok(())
+    }
+
     pub fn create<'a>(
         program_id: &Pubkey,
         source: VotePowerUnit,
@@ -110,13 +165,26 @@ impl VotePowerOriginRecord {
         // governing_token_mint: &Pubkey,
         payer_info: &AccountInfo<'a>,
         system_info: &AccountInfo<'a>,
+        index: Option<u64>,
+        expires_at: Option<UnixTimestamp>,
     ) -> Result<(), ProgramError> {
         let bump_seeds = [token_origin_record_bump_seed];
-        let vote_power_owner_record_address_seeds = get_vote_power_origin_record_address_seeds(
-            &source,
-            governing_owner_info.key,
-            &bump_seeds,
-        );
+        let index_bytes = index.map(|index| index.to_le_bytes());
+        let vote_power_owner_record_address_seeds = match &index_bytes {
+            Some(index_bytes) => get_indexed_vote_power_origin_record_address_seeds(
+                &source,
+                governing_owner_info.key,
+                index_bytes,
+                &bump_seeds,
+            )
+            .to_vec(),
+            None => get_vote_power_origin_record_address_seeds(
+                &source,
+                governing_owner_info.key,
+                &bump_seeds,
+            )
+            .to_vec(),
+        };
 
         if token_origin_record_info.data_is_empty() {
             if !(governing_owner_info.is_signer) {
@@ -128,6 +196,7 @@ impl VotePowerOriginRecord {
                 governing_owner: *governing_owner_info.key,
                 source: source.clone(),
                 amount,
+                expires_at,
             };
 
             create_and_serialize_account_verify_with_bump(
@@ -192,6 +261,58 @@ pub fn get_vote_power_origin_record_address_seeds<'a>(
     }
 }
 
+/// Returns the PDA address of a deposited indexed tag record, every index is deposited separately
+pub fn get_indexed_vote_power_origin_record_address(
+    program_id: &Pubkey,
+    source: &VotePowerUnit,
+    governing_owner: &Pubkey,
+    index: u64,
+) -> (Pubkey, u8) {
+    let index_bytes = index.to_le_bytes();
+    Pubkey::find_program_address(
+        &match source {
+            VotePowerUnit::Mint(governing_token_mint) => [
+                PROGRAM_AUTHORITY_SEED,
+                governing_token_mint.as_ref(),
+                governing_owner.as_ref(),
+                &index_bytes,
+            ],
+            VotePowerUnit::Tag { record_factory } => [
+                PROGRAM_AUTHORITY_SEED,
+                record_factory.as_ref(),
+                governing_owner.as_ref(),
+                &index_bytes,
+            ],
+        },
+        program_id,
+    )
+}
+
+/// Returns the PDA seeds of a deposited indexed tag record
+pub fn get_indexed_vote_power_origin_record_address_seeds<'a>(
+    source: &'a VotePowerUnit,
+    governing_owner: &'a Pubkey,
+    index: &'a [u8; 8],
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 5] {
+    match source {
+        VotePowerUnit::Mint(governing_token_mint) => [
+            PROGRAM_AUTHORITY_SEED,
+            governing_token_mint.as_ref(),
+            governing_owner.as_ref(),
+            index,
+            bump_seed,
+        ],
+        VotePowerUnit::Tag { record_factory } => [
+            PROGRAM_AUTHORITY_SEED,
+            record_factory.as_ref(),
+            governing_owner.as_ref(),
+            index,
+            bump_seed,
+        ],
+    }
+}
+
 pub fn get_vote_power_origin_record_data(
     program_id: &Pubkey,
     vote_power_origin_record_info: &AccountInfo,
@@ -208,9 +329,23 @@ pub fn get_vote_power_origin_record_data_for_owner(
         return Err(GovernanceError::GoverningTokenOwnerMustSign.into());
     }
 
+    get_vote_power_origin_record_data_for_verified_owner(
+        program_id,
+        vote_power_origin_record_info,
+        governing_owner_info.key,
+    )
+}
+
+/// Deserializes the record and asserts it belongs to the governing owner.
+/// The governing owner has to be verified by the caller
+pub fn get_vote_power_origin_record_data_for_verified_owner(
+    program_id: &Pubkey,
+    vote_power_origin_record_info: &AccountInfo,
+    governing_owner: &Pubkey,
+) -> Result<VotePowerOriginRecord, ProgramError> {
     let vote_power_origin_record_data =
         get_vote_power_origin_record_data(program_id, vote_power_origin_record_info)?;
-    if &vote_power_origin_record_data.governing_owner != governing_owner_info.key {
+    if &vote_power_origin_record_data.governing_owner != governing_owner {
         return Err(GovernanceError::InvalidTokenOwner.into());
     }
     Ok(vote_power_origin_record_data)
@@ -1,6 +1,9 @@
 //! Token Owner Record Account
 use borsh::maybestd::io::Write;
-use shared::account::{create_and_serialize_account_verify_with_bump, get_account_data, MaxSize};
+use shared::account::{
+    create_and_serialize_account_verify_with_bump, get_account_data,
+    resize_and_serialize_account_with_padding, MaxSize,
+};
 
 use crate::{
     accounts::AccountType, error::GovernanceError, state::scopes::scope::VotePowerUnit,
@@ -9,10 +12,18 @@ use crate::{
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
-    account_info::AccountInfo, msg, program_error::ProgramError, program_pack::IsInitialized,
-    pubkey::Pubkey, rent::Rent,
+    account_info::AccountInfo, clock::UnixTimestamp, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, program_pack::IsInitialized, pubkey::Pubkey, rent::Rent,
 };
 
+/// Delegated amount that stops counting as vote power at the given time
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ExpiringAmount {
+    pub expires_at: UnixTimestamp,
+    pub amount: u64,
+}
+
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct VotePowerOwnerRecord {
@@ -50,11 +61,30 @@ pub struct VotePowerOwnerRecord {
 
     /// Latest vote using the token owner record
     pub latest_vote: Option<Pubkey>,
+
+    /// Delegated tag deposits grouped by their expiry, expired amounts are not part of the vote weight
+    pub expiring_amounts: Vec<ExpiringAmount>,
 }
 
 impl MaxSize for VotePowerOwnerRecord {
     fn get_max_size(&self) -> Option<usize> {
-        Some(1 + 32 + 32 + 8 + 8 + 4 + 4 + 1 + 1 + 32 + 1 + 32 + 1 + 32)
+        Some(
+            1 + 32
+                + 32
+                + 8
+                + 8
+                + 4
+                + 4
+                + 1
+                + 1
+                + 32
+                + 1
+                + 32
+                + 1
+                + 32
+                + 4
+                + self.expiring_amounts.len() * (8 + 8),
+        )
     }
 }
 
@@ -65,6 +95,81 @@ impl IsInitialized for VotePowerOwnerRecord {
 }
 
 impl VotePowerOwnerRecord {
+    /// Returns the vote weight at the given time
+    /// Delegated tag deposits can not be voted with after they expire
+    pub fn get_vote_weight(
+        &self,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> Result<u64, ProgramError> {
+        let expired_amount = self
+            .expiring_amounts
+            .iter()
+            .filter(|expiring| current_unix_timestamp >= expiring.expires_at)
+            .map(|expiring| expiring.amount)
+            .sum::<u64>();
+
+        let vote_weight = self.amount.checked_sub(expired_amount).unwrap();
+        if vote_weight == 0 && expired_amount > 0 {
+            return Err(GovernanceError::VotePowerExpired.into());
+        }
+        Ok(vote_weight)
+    }
+
+    /// Adds a delegated amount that expires at the given time
+    pub fn add_expiring_amount(&mut self, expires_at: Option<UnixTimestamp>, amount: u64) {
+        let expires_at = match expires_at {
+            Some(expires_at) if amount > 0 => expires_at,
+            _ => return,
+        };
+        match self
+            .expiring_amounts
+            .iter_mut()
+            .find(|expiring| expiring.expires_at == expires_at)
+        {
+            Some(expiring) => expiring.amount = expiring.amount.checked_add(amount).unwrap(),
+            None => self
+                .expiring_amounts
+                .push(ExpiringAmount { expires_at, amount }),
+        }
+    }
+
+    /// Removes a delegated amount that expires at the given time
+    pub fn remove_expiring_amount(&mut self, expires_at: Option<UnixTimestamp>, amount: u64) {
+        let expires_at = match expires_at {
+            Some(expires_at) if amount > 0 => expires_at,
+            _ => return,
+        };
+        let position = self
+            .expiring_amounts
+            .iter()
+            .position(|expiring| expiring.expires_at == expires_at)
+            .unwrap();
+        let expiring = &mut self.expiring_amounts[position];
+        expiring.amount = expiring.amount.checked_sub(amount).unwrap();
+        if expiring.amount == 0 {
+            self.expiring_amounts.remove(position);
+        }
+    }
+
+    /// Serializes the record and resizes the account, since every expiry takes space
+    pub fn resize_and_serialize<'a>(
+        &self,
+        payer_info: &AccountInfo<'a>,
+        vote_power_owner_record_info: &AccountInfo<'a>,
+        system_info: &AccountInfo<'a>,
+        rent: &Rent,
+    ) -> ProgramResult {
+        let padding = self.get_max_size().unwrap() - self.try_to_vec()?.len();
+        resize_and_serialize_account_with_padding(
+            payer_info,
+            vote_power_owner_record_info,
+            self,
+            padding,
+            system_info,
+            rent,
+        )
+    }
+
     pub fn create_empty_delegate<'a>(
         program_id: &Pubkey,
         delegated_by_scope: &Pubkey,
@@ -107,6 +212,7 @@ impl VotePowerOwnerRecord {
                 delegated_by_scope: *delegated_by_scope, // this is not a delegation
                 first_vote: None,
                 latest_vote: None,
+                expiring_amounts: vec![],
             };
 
             create_and_serialize_account_verify_with_bump(
@@ -431,9 +537,23 @@ pub fn get_vote_power_owner_record_data_for_owner(
         return Err(GovernanceError::GoverningTokenOwnerMustSign.into());
     }
 
+    get_vote_power_owner_record_data_for_verified_owner(
+        program_id,
+        vote_power_owner_record_info,
+        governing_owner_info.key,
+    )
+}
+
+/// Deserializes the record and asserts it belongs to the governing owner.
+/// The governing owner has to be verified by the caller
+pub fn get_vote_power_owner_record_data_for_verified_owner(
+    program_id: &Pubkey,
+    vote_power_owner_record_info: &AccountInfo,
+    governing_owner: &Pubkey,
+) -> Result<VotePowerOwnerRecord, ProgramError> {
     let token_owner_record_data =
         get_vote_power_owner_record_data(program_id, vote_power_owner_record_info)?;
-    if &token_owner_record_data.governing_owner != governing_owner_info.key {
+    if &token_owner_record_data.governing_owner != governing_owner {
         return Err(GovernanceError::InvalidTokenOwner.into());
     }
     Ok(token_owner_record_data)
@@ -472,6 +592,47 @@ pub fn get_vote_power_owner_record_data_for_proposal_owner(
 
 #[cfg(test)]
 mod test {
+    use super::*;
+
+    #[test]
+    fn test_expired_amounts_are_not_part_of_vote_weight() {
+        let mut token_owner_record = VotePowerOwnerRecord {
+            account_type: AccountType::VotePowerOwnerRecord,
+            source: VotePowerUnit::Tag {
+                record_factory: Pubkey::new_unique(),
+            },
+            amount: 6,
+            governing_owner: Pubkey::new_unique(),
+            unrelinquished_votes_count: 0,
+            total_votes_count: 0,
+            outstanding_proposal_count: 0,
+            delegated_by_scope: Pubkey::new_unique(),
+            first_vote: None,
+            latest_vote: None,
+            expiring_amounts: vec![],
+        };
+        token_owner_record.add_expiring_amount(Some(10), 1);
+        token_owner_record.add_expiring_amount(Some(20), 2);
+        token_owner_record.add_expiring_amount(Some(10), 3);
+        assert_eq!(token_owner_record.expiring_amounts.len(), 2);
+
+        assert_eq!(token_owner_record.get_vote_weight(9), Ok(6));
+        assert_eq!(token_owner_record.get_vote_weight(10), Ok(2));
+
+        // Only expired power left
+        token_owner_record.amount = 4;
+        assert_eq!(
+            token_owner_record.get_vote_weight(10),
+            Err(GovernanceError::VotePowerExpired.into())
+        );
+
+        token_owner_record.remove_expiring_amount(Some(10), 4);
+        assert_eq!(token_owner_record.expiring_amounts.len(), 1);
+        assert_eq!(
+            token_owner_record.get_max_size(),
+            Some(token_owner_record.try_to_vec().unwrap().len() + 32 + 32)
+        );
+    }
 
     /*
       #[test]
@@ -5,6 +5,7 @@ use borsh::maybestd::io::Write;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use shared::account::{get_account_data, MaxSize};
 use solana_program::account_info::AccountInfo;
+use solana_program::clock::UnixTimestamp;
 
 use solana_program::program_error::ProgramError;
 use solana_program::{program_pack::IsInitialized, pubkey::Pubkey};
@@ -71,6 +72,9 @@ pub struct VoteRecordV2 {
 
     /// Voter's weight
     pub vote_weight: u64,
+
+    /// When the vote was cast, delegations that expired before it are not part of the vote weight
+    pub cast_at: UnixTimestamp,
 }
 
 impl MaxSize for VoteRecordV2 {}
@@ -117,12 +121,12 @@ pub fn get_vote_record_data(
     get_account_data::<VoteRecordV2>(program_id, vote_record_info)
 }
 
-/// Deserializes VoteRecord and checks it belongs to the provided Proposal and Governing Token Owner
+/// Deserializes VoteRecord and checks it belongs to the provided Proposal and verified Governing Token Owner
 pub fn get_vote_record_data_for_proposal_and_token_owner(
     program_id: &Pubkey,
     vote_record_info: &AccountInfo,
     proposal: &Pubkey,
-    governing_owner: &AccountInfo,
+    governing_owner: &Pubkey,
 ) -> Result<VoteRecordV2, ProgramError> {
     let vote_record_data = get_vote_record_data(program_id, vote_record_info)?;
 
@@ -130,14 +134,10 @@ pub fn get_vote_record_data_for_proposal_and_token_owner(
         return Err(GovernanceError::InvalidProposalForVoterRecord.into());
     }
 
-    if &vote_record_data.governing_owner != governing_owner.key {
+    if &vote_record_data.governing_owner != governing_owner {
         return Err(GovernanceError::InvalidGoverningTokenOwnerForVoteRecord.into());
     }
 
-    if !governing_owner.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
-
     Ok(vote_record_data)
 }
 
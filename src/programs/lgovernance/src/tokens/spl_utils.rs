@@ -17,7 +17,9 @@ use spl_token::{
     state::Mint,
 };
 
-use crate::{error::GovernanceError, pack::check_data_len};
+pub use shared::token::{get_token_account_mint, get_token_account_owner, get_token_balance};
+
+use crate::error::GovernanceError;
 /*
 pub const MINT_SEED: &[u8] = b"mint";
 pub const UTILITY_MINT: &[u8] = b"utility";
@@ -520,33 +522,3 @@ pub fn get_spl_token_owner(token_account_info: &AccountInfo) -> Result<Pubkey, P
     let owner_data = array_ref![data, 32, 32];
     Ok(Pubkey::new_from_array(*owner_data))
 }
-
-/// Returns Tokens balance.
-/// Extrats balance field without unpacking entire struct.
-pub fn get_token_balance(token_account: &AccountInfo) -> Result<u64, ProgramError> {
-    let data = token_account.try_borrow_data()?;
-    check_data_len(&data, spl_token::state::Account::get_packed_len())?;
-    let amount = array_ref![data, 64, 8];
-
-    Ok(u64::from_le_bytes(*amount))
-}
-
-/// Returns Token account owner.
-/// Extrats owner field without unpacking entire struct.
-pub fn get_token_account_owner(token_account: &AccountInfo) -> Result<Pubkey, ProgramError> {
-    let data = token_account.try_borrow_data()?;
-    check_data_len(&data, spl_token::state::Account::get_packed_len())?;
-    let owner = array_ref![data, 32, 32];
-
-    Ok(Pubkey::new_from_array(*owner))
-}
-
-/// Returns Token account mint.
-/// Extrats mint field without unpacking entire struct.
-pub fn get_token_account_mint(token_account: &AccountInfo) -> Result<Pubkey, ProgramError> {
-    let data = token_account.try_borrow_data()?;
-    check_data_len(&data, spl_token::state::Account::get_packed_len())?;
-    let mint = array_ref![data, 0, 32];
-
-    Ok(Pubkey::new_from_array(*mint))
-}
@@ -395,3 +395,95 @@ async fn success_tag_delegate_undelegate_with_synchronization() {
         1
     );
 }
+
+#[tokio::test]
+async fn success_scope_budgets() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let user = TestUser::new();
+
+    let governance_token = TestToken::new(&mut bench).await;
+    governance_token
+        .create_token_holder_account(&mut bench)
+        .await;
+
+    user.create_associated_token_account(&mut bench, &governance_token)
+        .await;
+
+    governance_token
+        .mint_to(
+            &mut bench,
+            10,
+            &user.get_associated_token_account_address(&governance_token),
+        )
+        .await;
+
+    user.deposit_governance_tokens(&mut bench, 10, &governance_token)
+        .await;
+
+    let governance = TestGovernance::new(&mut bench).await;
+    let vote_power_unit = VotePowerUnit::Mint(governance_token.mint);
+
+    let scope_a = governance
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestToken(&governance_token),
+        )
+        .await;
+    let scope_b = governance
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestToken(&governance_token),
+        )
+        .await;
+
+    // Every scope gets a separate share of the deposit
+    let delegation_a =
+        TestDelegation::new(&mut bench, &user, &user, &vote_power_unit, &scope_a).await;
+    let delegation_b =
+        TestDelegation::new(&mut bench, &user, &user, &vote_power_unit, &scope_b).await;
+    user.update_budget(&mut bench, &vote_power_unit, &scope_a, 4)
+        .await
+        .unwrap();
+    user.update_budget(&mut bench, &vote_power_unit, &scope_b, 6)
+        .await
+        .unwrap();
+
+    // A budget can not exceed the deposit
+    assert!(user
+        .update_budget(&mut bench, &vote_power_unit, &scope_a, 11)
+        .await
+        .is_err());
+
+    assert!(delegation_a.try_delegate(&mut bench, &5).await.is_err());
+    delegation_a.delegate(&mut bench, &4).await;
+    delegation_b.delegate(&mut bench, &6).await;
+
+    // The delegated part of the budget can not be released without undelegating
+    assert!(user
+        .update_budget(&mut bench, &vote_power_unit, &scope_a, 3)
+        .await
+        .is_err());
+
+    user.update_budget(&mut bench, &vote_power_unit, &scope_a, 5)
+        .await
+        .unwrap();
+    let budget = user
+        .get_token_owner_budget_record(&mut bench, &vote_power_unit, &scope_a)
+        .await
+        .unwrap();
+    assert_eq!(budget.budget, 5);
+    assert_eq!(budget.amount, 1);
+
+    delegation_a.undelegate(&mut bench, &4).await;
+    user.update_budget(&mut bench, &vote_power_unit, &scope_a, 0)
+        .await
+        .unwrap();
+    assert_eq!(
+        user.get_token_owner_budget_record(&mut bench, &vote_power_unit, &scope_b)
+            .await
+            .unwrap()
+            .amount,
+        0
+    );
+}
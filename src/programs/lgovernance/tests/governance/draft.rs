@@ -0,0 +1,345 @@
+use crate::governance::utils::TestVotePowerSource;
+use crate::utils::program_test;
+use lgovernance::{
+    instruction::CreateProposalOptionType,
+    state::{
+        enums::{GovernanceAction, ProposalState, VoteType},
+        native_treasury::get_native_treasury_address,
+        proposal::{
+            proposal_option::{ProposalOption, ProposalOptionType},
+            proposal_transaction::ConditionedInstruction,
+        },
+        scopes::scope::{CreateProposalCriteria, ScopeConfig},
+    },
+};
+use lsignforme::{
+    get_sign_for_me_program_address,
+    instruction::{create_sign_for_me, SignForMe, SignerMaybeSignForMe},
+    state::{SignForMeAccount, SignForMeScope},
+};
+use ltag::condition::TagCondition;
+use shared::content::ContentSource;
+use solana_program::{borsh::try_from_slice_unchecked, pubkey::Pubkey, system_instruction};
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+use super::super::bench::ProgramTestBench;
+use super::utils::{TestGovernance, TestProposal, TestTagRecordFactory, TestToken, TestUser};
+
+async fn get_transactions_count(bench: &mut ProgramTestBench, option: &Pubkey) -> u16 {
+    let option_data =
+        try_from_slice_unchecked::<ProposalOption>(&bench.get_account(option).await.unwrap().data)
+            .unwrap();
+    match option_data.option_type {
+        ProposalOptionType::Instruction {
+            transactions_count, ..
+        } => transactions_count,
+        ProposalOptionType::Deny => panic!("Expected an instruction option"),
+    }
+}
+
+#[tokio::test]
+async fn success_edit_draft() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let user = TestUser::new();
+
+    let governance_token = TestToken::new(&mut bench).await;
+
+    let mut governance = TestGovernance::new(&mut bench).await;
+    governance.with_native_treasury(&mut bench).await;
+
+    let scope = governance
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestToken(&governance_token),
+        )
+        .await;
+
+    let mut proposal = TestProposal::new(
+        &mut bench,
+        0,
+        VoteType::SingleChoice,
+        vec![scope],
+        &governance,
+        &user.keypair,
+    )
+    .await;
+
+    proposal
+        .add_option(&mut bench, &CreateProposalOptionType::Deny, &user.keypair)
+        .await;
+    let instruction_option = proposal
+        .add_option(
+            &mut bench,
+            &CreateProposalOptionType::Instruction("Label".into()),
+            &user.keypair,
+        )
+        .await;
+
+    let recipent_wallet = bench.with_wallet().await;
+    let transfer = ConditionedInstruction {
+        instruction_data: system_instruction::transfer(
+            &get_native_treasury_address(&lgovernance::id(), &governance.governance),
+            &recipent_wallet.address,
+            1,
+        )
+        .into(),
+        scope,
+    };
+    proposal
+        .add_transaction(&mut bench, 1, 0, vec![transfer.clone()], &user.keypair)
+        .await;
+    assert_eq!(
+        get_transactions_count(&mut bench, &instruction_option).await,
+        1
+    );
+
+    // The option still has a transaction, and the deny option is not the last option
+    assert!(proposal
+        .remove_option(&mut bench, 1, &user.keypair)
+        .await
+        .is_err());
+    assert!(proposal
+        .remove_option(&mut bench, 0, &user.keypair)
+        .await
+        .is_err());
+
+    // Only the creator can edit the draft
+    let other = TestUser::new();
+    assert!(proposal
+        .remove_transaction(&mut bench, 1, 0, &other.keypair)
+        .await
+        .is_err());
+
+    proposal
+        .remove_transaction(&mut bench, 1, 0, &user.keypair)
+        .await
+        .unwrap();
+    assert!(bench
+        .get_account(&proposal.proposal_transactions[&1][0])
+        .await
+        .is_none());
+    assert_eq!(
+        get_transactions_count(&mut bench, &instruction_option).await,
+        0
+    );
+
+    proposal
+        .remove_option(&mut bench, 1, &user.keypair)
+        .await
+        .unwrap();
+    assert!(bench.get_account(&instruction_option).await.is_none());
+
+    let source = ContentSource::String("Fixed info".into());
+    proposal
+        .update_source(&mut bench, &source, &user.keypair)
+        .await
+        .unwrap();
+
+    let proposal_data = proposal.get_proposal_account(&mut bench).await;
+    assert_eq!(proposal_data.options_count, 1);
+    assert_eq!(proposal_data.source, source);
+    assert!(proposal_data.deny_option.is_some());
+}
+
+#[tokio::test]
+async fn success_update_source_grows_proposal() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let core_team = TestTagRecordFactory::new(&mut bench).await;
+    let governance = TestGovernance::new(&mut bench).await;
+    let scope = governance
+        .create_scope(
+            &mut bench,
+            ScopeConfig::get_single_tag_config(&core_team.factory, &None, &None, &None),
+        )
+        .await;
+
+    let member = TestUser::new();
+    core_team.new_record(&mut bench, &member).await;
+
+    let mut proposal = TestProposal::new(
+        &mut bench,
+        0,
+        VoteType::SingleChoice,
+        vec![scope],
+        &governance,
+        &member.keypair,
+    )
+    .await;
+    proposal
+        .add_option(&mut bench, &CreateProposalOptionType::Deny, &member.keypair)
+        .await;
+    let initial_size = bench
+        .get_account(&proposal.proposal)
+        .await
+        .unwrap()
+        .data
+        .len();
+
+    // A longer source grows the account, the space left for voting is kept
+    let source = ContentSource::String("a".repeat(200));
+    proposal
+        .update_source(&mut bench, &source, &member.keypair)
+        .await
+        .unwrap();
+    assert!(
+        bench
+            .get_account(&proposal.proposal)
+            .await
+            .unwrap()
+            .data
+            .len()
+            > initial_size
+    );
+    assert_eq!(
+        proposal.get_proposal_account(&mut bench).await.source,
+        source
+    );
+
+    // The proposal can still be updated in place
+    proposal
+        .finalize_draft(&mut bench, &governance, &member.keypair, None)
+        .await
+        .unwrap();
+    let proposal_data = proposal.get_proposal_account(&mut bench).await;
+    assert_eq!(proposal_data.state, ProposalState::Voting);
+    assert_eq!(proposal_data.source, source);
+}
+
+#[tokio::test]
+async fn success_finalize_draft_with_tag_condition() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let core_team = TestTagRecordFactory::new(&mut bench).await;
+    let suspended = TestTagRecordFactory::new(&mut bench).await;
+
+    let governance = TestGovernance::new(&mut bench).await;
+
+    // Proposals can only be created by "core team AND NOT suspended"
+    let mut config = ScopeConfig::get_single_tag_config(&core_team.factory, &None, &None, &None);
+    config.proposal_config.create_proposal_criteria = CreateProposalCriteria::Tags(TagCondition {
+        all_of: vec![core_team.factory],
+        any_of: vec![],
+        none_of: vec![suspended.factory],
+    });
+    let scope = governance.create_scope(&mut bench, config).await;
+
+    let member = TestUser::new();
+    core_team.new_record(&mut bench, &member).await;
+
+    let suspended_member = TestUser::new();
+    core_team.new_record(&mut bench, &suspended_member).await;
+    suspended.new_record(&mut bench, &suspended_member).await;
+
+    let outsider = TestUser::new();
+
+    for (user, can_create) in [
+        (&suspended_member, false),
+        (&outsider, false),
+        (&member, true),
+    ] {
+        let mut proposal = TestProposal::new(
+            &mut bench,
+            0,
+            VoteType::SingleChoice,
+            vec![scope],
+            &governance,
+            &user.keypair,
+        )
+        .await;
+        proposal
+            .add_option(&mut bench, &CreateProposalOptionType::Deny, &user.keypair)
+            .await;
+        assert_eq!(
+            proposal
+                .finalize_draft(&mut bench, &governance, &user.keypair, None)
+                .await
+                .is_ok(),
+            can_create
+        );
+    }
+}
+
+#[tokio::test]
+async fn success_finalize_draft_sign_for_me_once() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let core_team = TestTagRecordFactory::new(&mut bench).await;
+    let governance = TestGovernance::new(&mut bench).await;
+    let scope = governance
+        .create_scope(
+            &mut bench,
+            ScopeConfig::get_single_tag_config(&core_team.factory, &None, &None, &None),
+        )
+        .await;
+
+    let member = TestUser::new();
+    core_team.new_record(&mut bench, &member).await;
+
+    let mut proposal = TestProposal::new(
+        &mut bench,
+        0,
+        VoteType::SingleChoice,
+        vec![scope],
+        &governance,
+        &member.keypair,
+    )
+    .await;
+    proposal
+        .add_option(&mut bench, &CreateProposalOptionType::Deny, &member.keypair)
+        .await;
+
+    // The member lets a session key create proposals once
+    let session_key = Keypair::new();
+    let sign_for_me_scope = SignForMeScope {
+        program: lgovernance::id(),
+        resource: Some(governance.governance),
+        actions: GovernanceAction::CreateProposal.action(),
+    };
+    bench
+        .process_transaction(
+            &[create_sign_for_me(
+                &lsignforme::id(),
+                &member.keypair.pubkey(),
+                &[session_key.pubkey()],
+                1,
+                &sign_for_me_scope,
+                &bench.payer.pubkey(),
+                None,
+                Some(1),
+            )],
+            Some(&[&member.keypair]),
+        )
+        .await
+        .unwrap();
+
+    let sign_for_me = get_sign_for_me_program_address(
+        &lsignforme::id(),
+        &member.keypair.pubkey(),
+        &[session_key.pubkey()],
+        1,
+        &sign_for_me_scope,
+    )
+    .0;
+    let creator = SignerMaybeSignForMe {
+        original_signer: member.keypair.pubkey(),
+        sign_for_me: Some(SignForMe {
+            sign_for_me,
+            signers: vec![session_key.pubkey()],
+        }),
+    };
+
+    // The creator is also the owner of the tag record of the scope criteria, but is only verified once
+    proposal
+        .finalize_draft_as(&mut bench, &governance, &creator, &[&session_key], None)
+        .await
+        .unwrap();
+
+    let sign_for_me_account = try_from_slice_unchecked::<SignForMeAccount>(
+        &bench.get_account(&sign_for_me).await.unwrap().data,
+    )
+    .unwrap();
+    assert_eq!(sign_for_me_account.uses, 1);
+}
@@ -0,0 +1,312 @@
+use crate::governance::utils::{TestDelegation, TestVotePowerSource};
+use crate::utils::program_test;
+use lgovernance::{
+    error::GovernanceError,
+    instruction::create_proposal,
+    state::{
+        enums::ProposalState,
+        proposal::VoteType,
+        scopes::scope::{ScopeConfig, ScopeMatch, VotePowerUnit},
+    },
+};
+use lsignforme::instruction::SignerMaybeSignForMe;
+use shared::content::ContentSource;
+use solana_program_test::*;
+use solana_sdk::signer::Signer;
+
+use super::super::bench::ProgramTestBench;
+use super::utils::{TestGovernance, TestProposal, TestToken, TestUser};
+
+#[tokio::test]
+async fn success_register_freeze_detach_child() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let governance_token = TestToken::new(&mut bench).await;
+    let parent = TestGovernance::new(&mut bench).await;
+    let child = TestGovernance::new(&mut bench).await;
+
+    let parent_scope = parent
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestToken(&governance_token),
+        )
+        .await;
+
+    parent
+        .register_child(&mut bench, &child, vec![parent_scope])
+        .await
+        .unwrap();
+
+    let child_data = child.get_governance_account(&mut bench).await;
+    assert_eq!(child_data.parent, Some(parent.governance));
+    assert_eq!(child_data.delegated_scopes, vec![parent_scope]);
+    assert_eq!(
+        parent
+            .get_governance_account(&mut bench)
+            .await
+            .children_count,
+        1
+    );
+
+    // A child can not be registered twice
+    assert!(parent
+        .register_child(&mut bench, &child, vec![parent_scope])
+        .await
+        .is_err());
+
+    // Frozen governance can not create proposals
+    parent.freeze_child(&mut bench, &child, true).await;
+    let user = TestUser::new();
+    let create_proposal_instruction = create_proposal(
+        &lgovernance::id(),
+        &SignerMaybeSignForMe::from(&user.keypair.pubkey()),
+        &child.governance,
+        &bench.payer.pubkey(),
+        0,
+        VoteType::SingleChoice,
+        1,
+        &ContentSource::String("Info".into()),
+        None,
+    );
+    assert!(bench
+        .process_transaction(
+            &[create_proposal_instruction.clone()],
+            Some(&[&user.keypair])
+        )
+        .await
+        .is_err());
+
+    parent.freeze_child(&mut bench, &child, false).await;
+    bench
+        .process_transaction(&[create_proposal_instruction], Some(&[&user.keypair]))
+        .await
+        .unwrap();
+
+    parent.detach_child(&mut bench, &child).await;
+    let child_data = child.get_governance_account(&mut bench).await;
+    assert_eq!(child_data.parent, None);
+    assert!(child_data.delegated_scopes.is_empty());
+    assert_eq!(
+        parent
+            .get_governance_account(&mut bench)
+            .await
+            .children_count,
+        0
+    );
+}
+
+#[tokio::test]
+async fn success_child_executes_within_delegated_scopes() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let user = TestUser::new();
+    let governance_token = TestToken::new(&mut bench).await;
+
+    governance_token
+        .create_token_holder_account(&mut bench)
+        .await;
+
+    user.create_associated_token_account(&mut bench, &governance_token)
+        .await;
+
+    governance_token
+        .mint_to(
+            &mut bench,
+            1,
+            &user.get_associated_token_account_address(&governance_token),
+        )
+        .await;
+
+    user.deposit_governance_tokens(&mut bench, 1, &governance_token)
+        .await;
+
+    let vote_power_unit = VotePowerUnit::Mint(governance_token.mint);
+
+    let parent = TestGovernance::new(&mut bench).await;
+    let mut child = TestGovernance::new(&mut bench).await;
+    child.with_native_treasury(&mut bench).await;
+
+    let parent_scope = parent
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestToken(&governance_token),
+        )
+        .await;
+    let child_scope = child
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestToken(&governance_token),
+        )
+        .await;
+
+    parent
+        .register_child(&mut bench, &child, vec![parent_scope])
+        .await
+        .unwrap();
+
+    user.create_delegatee(&mut bench, &vote_power_unit, &child_scope)
+        .await;
+
+    let self_delegation =
+        TestDelegation::new(&mut bench, &user, &user, &vote_power_unit, &child_scope).await;
+    self_delegation.delegate(&mut bench, &1).await;
+
+    let (proposal, recipent_wallet) = TestProposal::new_child_transfer_proposal(
+        &mut bench,
+        &user,
+        &child_scope,
+        &child,
+        1,
+        None,
+        vec![parent_scope],
+    )
+    .await;
+
+    proposal
+        .vote_with_delegate(&mut bench, &vec![1], &user, &vote_power_unit, &child_scope)
+        .await;
+
+    proposal.count_votes(&mut bench).await;
+
+    assert_eq!(
+        proposal.get_state(&mut bench).await,
+        ProposalState::Succeeded
+    );
+
+    proposal
+        .advance_clock_past_max_hold_up_time(&mut bench, 1)
+        .await;
+
+    // The parent revokes the scope before the transaction is executed
+    parent.update_child(&mut bench, &child, vec![]).await;
+    assert_eq!(
+        proposal.try_execute_transactions(&mut bench, 1).await,
+        Err(GovernanceError::ScopeNotDelegatedByParent.into())
+    );
+
+    parent
+        .update_child(&mut bench, &child, vec![parent_scope])
+        .await;
+    bench.advance_clock().await;
+
+    let transfer_destination_balance = bench
+        .get_account(&recipent_wallet.address)
+        .await
+        .unwrap()
+        .lamports;
+
+    proposal.execute_transactions(&mut bench, 1).await;
+
+    assert_eq!(
+        bench
+            .get_account(&recipent_wallet.address)
+            .await
+            .unwrap()
+            .lamports
+            - transfer_destination_balance,
+        1
+    );
+}
+
+#[tokio::test]
+async fn success_dissolve_frozen_child() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let parent = TestGovernance::new(&mut bench).await;
+    let child = TestGovernance::new(&mut bench).await;
+
+    parent
+        .register_child(&mut bench, &child, vec![])
+        .await
+        .unwrap();
+
+    // The child has to be frozen first, so its proposals can not be executed anymore
+    assert_eq!(
+        parent.dissolve_child(&mut bench, &child).await,
+        Err(GovernanceError::ChildGovernanceNotFrozen.into())
+    );
+
+    parent.freeze_child(&mut bench, &child, true).await;
+    parent.dissolve_child(&mut bench, &child).await.unwrap();
+
+    assert!(bench.get_account(&child.governance).await.is_none());
+    assert_eq!(
+        parent
+            .get_governance_account(&mut bench)
+            .await
+            .children_count,
+        0
+    );
+}
+
+#[tokio::test]
+async fn fail_child_authority_outside_delegated_scopes() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let governance_token = TestToken::new(&mut bench).await;
+    let parent = TestGovernance::new(&mut bench).await;
+    let child = TestGovernance::new(&mut bench).await;
+
+    let parent_system_scope = parent
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestToken(&governance_token),
+        )
+        .await;
+    let governance_scope_config = ScopeConfig::get_single_mint_config(
+        &governance_token.mint,
+        &Some(ScopeMatch::ProgramId(lgovernance::id())),
+        &None,
+        &None,
+    );
+    let parent_governance_scope = parent
+        .create_scope(&mut bench, governance_scope_config.clone())
+        .await;
+
+    parent
+        .register_child(&mut bench, &child, vec![parent_system_scope])
+        .await
+        .unwrap();
+
+    // The authority of the child has to act within a delegated parent scope, like its proposals
+    assert!(child
+        .try_create_scope(&mut bench, governance_scope_config.clone(), None)
+        .await
+        .is_err());
+    assert_eq!(
+        child
+            .try_create_scope(
+                &mut bench,
+                governance_scope_config.clone(),
+                Some(&parent_system_scope)
+            )
+            .await,
+        Err(GovernanceError::ScopeNotApplicableForInstruction.into())
+    );
+    assert_eq!(
+        child
+            .try_create_scope(
+                &mut bench,
+                governance_scope_config.clone(),
+                Some(&parent_governance_scope)
+            )
+            .await,
+        Err(GovernanceError::ScopeNotDelegatedByParent.into())
+    );
+
+    parent
+        .update_child(
+            &mut bench,
+            &child,
+            vec![parent_system_scope, parent_governance_scope],
+        )
+        .await;
+    child
+        .try_create_scope(
+            &mut bench,
+            governance_scope_config,
+            Some(&parent_governance_scope),
+        )
+        .await
+        .unwrap();
+}
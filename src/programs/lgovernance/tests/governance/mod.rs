@@ -1,3 +1,7 @@
 pub mod delegation;
+pub mod draft;
+pub mod hierarchy;
+pub mod pause;
+pub mod realm;
 pub mod utils;
 pub mod voting;
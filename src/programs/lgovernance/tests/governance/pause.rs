@@ -0,0 +1,155 @@
+use crate::governance::utils::{TestDelegation, TestVotePowerSource};
+use crate::utils::program_test;
+use lgovernance::{
+    error::GovernanceError,
+    instruction::create_proposal,
+    state::{
+        enums::ProposalState, governance::PauseConfig, proposal::VoteType,
+        scopes::scope::VotePowerUnit,
+    },
+};
+use lsignforme::instruction::SignerMaybeSignForMe;
+use shared::content::ContentSource;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+use super::super::bench::ProgramTestBench;
+use super::utils::{TestGovernance, TestProposal, TestToken, TestUser};
+
+#[tokio::test]
+async fn success_guardian_pause_expires() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let user = TestUser::new();
+
+    let governance_token = TestToken::new(&mut bench).await;
+
+    governance_token
+        .create_token_holder_account(&mut bench)
+        .await;
+
+    user.create_associated_token_account(&mut bench, &governance_token)
+        .await;
+
+    governance_token
+        .mint_to(
+            &mut bench,
+            1,
+            &user.get_associated_token_account_address(&governance_token),
+        )
+        .await;
+
+    user.deposit_governance_tokens(&mut bench, 1, &governance_token)
+        .await;
+
+    let vote_power_unit = VotePowerUnit::Mint(governance_token.mint);
+
+    let mut governance = TestGovernance::new(&mut bench).await;
+    governance.with_native_treasury(&mut bench).await;
+
+    let scope = governance
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestToken(&governance_token),
+        )
+        .await;
+
+    let guardian = Keypair::new();
+    governance
+        .update_pause_config(
+            &mut bench,
+            PauseConfig {
+                guardian: Some(guardian.pubkey()),
+                pause_scope: None,
+                unpause_scope: None,
+                max_pause_duration: 1000,
+            },
+        )
+        .await;
+
+    user.create_delegatee(&mut bench, &vote_power_unit, &scope)
+        .await;
+
+    let self_delegation =
+        TestDelegation::new(&mut bench, &user, &user, &vote_power_unit, &scope).await;
+    self_delegation.delegate(&mut bench, &1).await;
+
+    let (proposal, _recipent_wallet) =
+        TestProposal::new_transfer_proposal(&mut bench, &user, &scope, &governance, 1).await;
+
+    // Only the guardian can pause, and only for a bounded duration
+    assert!(governance
+        .pause(&mut bench, &Keypair::new(), 100)
+        .await
+        .is_err());
+    assert!(governance.pause(&mut bench, &guardian, 1001).await.is_err());
+    governance.pause(&mut bench, &guardian, 100).await.unwrap();
+
+    assert!(proposal
+        .vote_signed(&mut bench, &vec![1], &user, &vote_power_unit, &scope, 1)
+        .await
+        .is_err());
+
+    // No new proposals while paused
+    let proposals_count = governance
+        .get_governance_account(&mut bench)
+        .await
+        .proposals_count;
+    assert_eq!(
+        bench
+            .process_transaction(
+                &[create_proposal(
+                    &lgovernance::id(),
+                    &SignerMaybeSignForMe::from(&user.keypair.pubkey()),
+                    &governance.governance,
+                    &bench.payer.pubkey(),
+                    proposals_count,
+                    VoteType::SingleChoice,
+                    1,
+                    &ContentSource::String("Info".into()),
+                    None,
+                )],
+                Some(&[&user.keypair]),
+            )
+            .await,
+        Err(GovernanceError::GovernancePaused.into())
+    );
+
+    // The pause expires by itself
+    let paused_until = governance
+        .get_governance_account(&mut bench)
+        .await
+        .paused_until
+        .unwrap();
+    bench.advance_clock_past_timestamp(paused_until).await;
+
+    proposal
+        .vote_signed(&mut bench, &vec![1], &user, &vote_power_unit, &scope, 1)
+        .await
+        .unwrap();
+
+    proposal.count_votes(&mut bench).await;
+
+    assert_eq!(
+        proposal.get_state(&mut bench).await,
+        ProposalState::Succeeded
+    );
+
+    // The guardian can not pause again until a cooldown as long as the max pause duration has passed
+    assert_eq!(
+        governance.pause(&mut bench, &guardian, 100).await,
+        Err(GovernanceError::GuardianPauseCooldown.into())
+    );
+
+    let guardian_cooldown_until = governance
+        .get_governance_account(&mut bench)
+        .await
+        .guardian_cooldown_until
+        .unwrap();
+    assert_eq!(guardian_cooldown_until, paused_until + 1000);
+    bench
+        .advance_clock_past_timestamp(guardian_cooldown_until)
+        .await;
+
+    governance.pause(&mut bench, &guardian, 100).await.unwrap();
+}
@@ -0,0 +1,131 @@
+use crate::utils::program_test;
+use lgovernance::{
+    error::GovernanceError, instruction::create_realm_registry, state::scopes::scope::VotePowerUnit,
+};
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+use super::super::bench::ProgramTestBench;
+use super::utils::{TestGovernance, TestRealm, TestToken, TestUser};
+
+#[tokio::test]
+async fn success_realm_governances() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let user = TestUser::new();
+    let governance_token = TestToken::new(&mut bench).await;
+
+    governance_token
+        .create_token_holder_account(&mut bench)
+        .await;
+
+    user.create_associated_token_account(&mut bench, &governance_token)
+        .await;
+
+    governance_token
+        .mint_to(
+            &mut bench,
+            10,
+            &user.get_associated_token_account_address(&governance_token),
+        )
+        .await;
+
+    user.deposit_governance_tokens(&mut bench, 10, &governance_token)
+        .await;
+
+    let vote_power_origin_record =
+        user.get_vote_power_origin_record_address(&VotePowerUnit::Mint(governance_token.mint));
+
+    let realm = TestRealm::new(&mut bench, "My Org", vec![governance_token.mint], 10).await;
+    let governance = TestGovernance::new(&mut bench).await;
+
+    // The realm authority has to agree
+    assert_eq!(
+        realm
+            .add_governance_as(
+                &mut bench,
+                &governance,
+                &user,
+                Some(&vote_power_origin_record),
+                &Keypair::new(),
+            )
+            .await,
+        Err(GovernanceError::InvalidAuthorityForRealm.into())
+    );
+
+    // Weight is required to add a governance
+    assert!(realm
+        .add_governance(&mut bench, &governance, &user, None)
+        .await
+        .is_err());
+
+    realm
+        .add_governance(
+            &mut bench,
+            &governance,
+            &user,
+            Some(&vote_power_origin_record),
+        )
+        .await
+        .unwrap();
+
+    let realm_data = realm.get_realm_account(&mut bench).await;
+    assert_eq!(realm_data.name, "My Org");
+    assert_eq!(realm_data.governances, vec![governance.governance]);
+
+    // A governance can only be added once
+    assert!(realm
+        .add_governance(
+            &mut bench,
+            &governance,
+            &user,
+            Some(&vote_power_origin_record),
+        )
+        .await
+        .is_err());
+
+    // Raising the min weight stops the user from adding more governances
+    realm
+        .update(&mut bench, vec![governance_token.mint], Vec::new(), 11)
+        .await;
+    let other_governance = TestGovernance::new(&mut bench).await;
+    assert!(realm
+        .add_governance(
+            &mut bench,
+            &other_governance,
+            &user,
+            Some(&vote_power_origin_record),
+        )
+        .await
+        .is_err());
+
+    realm.remove_governance(&mut bench, &governance).await;
+    assert!(realm
+        .get_realm_account(&mut bench)
+        .await
+        .governances
+        .is_empty());
+}
+
+#[tokio::test]
+async fn fail_create_realm_without_authority_signature() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    // Nobody can be made the authority of a realm without agreeing
+    let authority = Keypair::new();
+    let mut create_realm_ix = create_realm_registry(
+        &lgovernance::id(),
+        &bench.payer.pubkey(),
+        "My Org",
+        Some(authority.pubkey()),
+        Vec::new(),
+        Vec::new(),
+        10,
+    );
+    create_realm_ix.accounts[3].is_signer = false;
+
+    assert_eq!(
+        bench.process_transaction(&[create_realm_ix], None).await,
+        Err(GovernanceError::RealmAuthorityMustSign.into())
+    );
+}
@@ -4,29 +4,41 @@ use super::super::bench::ProgramTestBench;
 use crate::bench::WalletCookie;
 
 use ltag::{
-    get_tag_program_address, get_tag_record_factory_program_address, get_tag_record_program_address,
+    get_indexed_tag_record_program_address, get_tag_program_address,
+    get_tag_record_factory_program_address, get_tag_record_program_address, state::TagRecordStatus,
 };
 use shared::content::ContentSource;
 use solana_program::{
-    borsh::try_from_slice_unchecked, instruction::AccountMeta, program_pack::Pack,
+    borsh::try_from_slice_unchecked,
+    clock::UnixTimestamp,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    program_pack::Pack,
     system_instruction, system_program,
 };
 
 use lgovernance::{
     instruction::{
-        cast_vote, count_vote_max_weights, count_votes, create_delegatee, create_governance,
-        create_native_treasury, create_proposal, create_proposal_option, create_realm,
-        create_scope, create_token_owner_budget_record, delegate, delegate_history,
-        deposit_governing_tag, deposit_governing_tokens, execute_transaction, finalize_draft,
-        insert_scope, insert_transaction, uncast_vote, undelegate, undelegate_history,
-        update_governance_authority, CreateProposalOptionType, SignedCreateProposal,
+        add_realm_governance, cast_signed_votes, cast_vote, count_vote_max_weights, count_votes,
+        count_votes_all, create_delegatee, create_governance, create_native_treasury,
+        create_proposal, create_proposal_option, create_realm, create_realm_registry, create_scope,
+        create_token_owner_budget_record, delegate, delegate_history,
+        deposit_governing_indexed_tag, deposit_governing_tag, deposit_governing_tokens,
+        detach_child_governance, dissolve_child_governance, execute_transaction,
+        expire_tag_delegation, finalize_draft, freeze_child_governance, insert_scope,
+        insert_transaction, pause_governance, register_child_governance, remove_proposal_option,
+        remove_realm_governance, remove_transaction, start_voting, uncast_vote, undelegate,
+        undelegate_history, update_child_governance, update_governance_authority,
+        update_pause_config, update_proposal_source, update_realm, update_token_owner_budget,
+        CastSignedVote, CreateProposalOptionType, SignedCreateProposal,
     },
+    shared::ed25519::new_ed25519_verify_instruction,
     state::{
         delegation::scope_delegation_record_account::{
             get_scope_delegation_account_program_address, ScopeDelegationRecordAccount,
         },
         enums::ProposalState,
-        governance::{get_governance_address, GovernanceV2},
+        governance::{get_governance_address, GovernanceV2, PauseConfig},
         native_treasury::get_native_treasury_address,
         proposal::{
             get_proposal_address,
@@ -36,14 +48,20 @@ use lgovernance::{
             },
             ProposalV2, VoteType,
         },
-        realm::get_realm_mint_program_address,
+        realm::{get_realm_address, get_realm_mint_program_address, Realm},
         scopes::scope::{get_scope_program_address, Scope, ScopeConfig, ScopeMatch, VotePowerUnit},
-        token_owner_budget_record::get_token_owner_budget_record_address,
-        vote_power_origin_record::get_vote_power_origin_record_address,
+        signed_vote::SignedVote,
+        token_owner_budget_record::{
+            get_token_owner_budget_record_address, TokenOwnerBudgetRecord,
+        },
+        vote_power_origin_record::{
+            get_indexed_vote_power_origin_record_address, get_vote_power_origin_record_address,
+        },
         vote_power_owner_record::{get_vote_power_owner_record_address, VotePowerOwnerRecord},
         vote_record::{get_vote_record_address, Vote, VoteRecordV2},
     },
 };
+use lsignforme::instruction::SignerMaybeSignForMe;
 
 use solana_program_test::*;
 use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
@@ -307,6 +325,14 @@ pub struct TestTagRecordFactory {
 
 impl TestTagRecordFactory {
     pub async fn new(bench: &mut ProgramTestBench) -> Self {
+        Self::new_with_config(bench, None, false).await
+    }
+
+    pub async fn new_with_config(
+        bench: &mut ProgramTestBench,
+        record_validity: Option<u64>,
+        indexed_records: bool,
+    ) -> Self {
         let authority = Keypair::new();
         let tag = Pubkey::new_unique().to_string();
         bench
@@ -329,8 +355,14 @@ impl TestTagRecordFactory {
                 &[ltag::instruction::create_tag_record_factory(
                     &ltag::id(),
                     &tag_address,
+                    None,
                     &authority.pubkey(),
                     &bench.payer.pubkey(),
+                    false,
+                    false,
+                    false,
+                    record_validity,
+                    indexed_records,
                 )],
                 Some(&[&authority]),
             )
@@ -348,6 +380,15 @@ impl TestTagRecordFactory {
     }
 
     pub async fn new_record(&self, bench: &mut ProgramTestBench, owner: &TestUser) {
+        self.new_record_with_quantity(bench, owner, 1).await;
+    }
+
+    pub async fn new_record_with_quantity(
+        &self,
+        bench: &mut ProgramTestBench,
+        owner: &TestUser,
+        quantity: u64,
+    ) {
         bench
             .process_transaction(
                 &[ltag::instruction::create_tag_record(
@@ -356,6 +397,65 @@ impl TestTagRecordFactory {
                     &self.factory,
                     &self.authority.pubkey(),
                     &bench.payer.pubkey(),
+                    quantity,
+                )],
+                Some(&[&self.authority]),
+            )
+            .await
+            .unwrap();
+    }
+
+    pub async fn renew_record(&self, bench: &mut ProgramTestBench, owner: &TestUser) {
+        bench
+            .process_transaction(
+                &[ltag::instruction::renew_tag_record(
+                    &ltag::id(),
+                    &owner.get_tag_record_address(self),
+                    &self.factory,
+                    &self.authority.pubkey(),
+                )],
+                Some(&[&self.authority]),
+            )
+            .await
+            .unwrap();
+    }
+
+    pub async fn suspend_record(&self, bench: &mut ProgramTestBench, owner: &TestUser) {
+        bench
+            .process_transaction(
+                &[ltag::instruction::update_tag_record_attributes(
+                    &ltag::id(),
+                    &owner.get_tag_record_address(self),
+                    &self.factory,
+                    &self.authority.pubkey(),
+                    &bench.payer.pubkey(),
+                    None,
+                    vec![],
+                    TagRecordStatus::Suspended,
+                )],
+                Some(&[&self.authority]),
+            )
+            .await
+            .unwrap();
+    }
+
+    pub async fn new_indexed_record_with_quantity(
+        &self,
+        bench: &mut ProgramTestBench,
+        owner: &TestUser,
+        index: u64,
+        quantity: u64,
+    ) {
+        bench
+            .process_transaction(
+                &[ltag::instruction::create_indexed_tag_record(
+                    &ltag::id(),
+                    &owner.keypair.pubkey(),
+                    &self.factory,
+                    &self.authority.pubkey(),
+                    &bench.payer.pubkey(),
+                    index,
+                    quantity,
                 )],
                 Some(&[&self.authority]),
             )
@@ -469,10 +569,51 @@ impl TestUser {
             .unwrap();
     }
 
+    pub async fn deposit_governance_indexed_tag(
+        &self,
+        bench: &mut ProgramTestBench,
+        tag_record_factory: &TestTagRecordFactory,
+        index: u64,
+    ) -> Result<(), ProgramError> {
+        bench
+            .process_transaction(
+                &[deposit_governing_indexed_tag(
+                    &lgovernance::id(),
+                    &get_indexed_tag_record_program_address(
+                        &ltag::id(),
+                        &tag_record_factory.factory,
+                        &self.keypair.pubkey(),
+                        index,
+                    )
+                    .0,
+                    &self.keypair.pubkey(),
+                    &tag_record_factory.factory,
+                    &bench.payer.pubkey(),
+                    index,
+                )],
+                Some(&[&self.keypair]),
+            )
+            .await
+    }
+
     pub fn get_vote_power_origin_record_address(&self, source: &VotePowerUnit) -> Pubkey {
         get_vote_power_origin_record_address(&lgovernance::id(), source, &self.keypair.pubkey()).0
     }
 
+    pub fn get_indexed_vote_power_origin_record_address(
+        &self,
+        source: &VotePowerUnit,
+        index: u64,
+    ) -> Pubkey {
+        get_indexed_vote_power_origin_record_address(
+            &lgovernance::id(),
+            source,
+            &self.keypair.pubkey(),
+            index,
+        )
+        .0
+    }
+
     pub async fn get_token_owner_record(
         &self,
         bench: &mut ProgramTestBench,
@@ -528,10 +669,10 @@ impl TestUser {
         bench: &mut ProgramTestBench,
         source: &VotePowerUnit,
         scope: &Pubkey,
-    ) -> Option<VotePowerOwnerRecord> {
+    ) -> Option<TokenOwnerBudgetRecord> {
         let address = self.get_token_owner_budget_record_address(source, scope);
         if let Some(account) = bench.get_account(&address).await {
-            Some(try_from_slice_unchecked::<VotePowerOwnerRecord>(&account.data).unwrap())
+            Some(try_from_slice_unchecked::<TokenOwnerBudgetRecord>(&account.data).unwrap())
         } else {
             None
         }
@@ -602,6 +743,27 @@ impl TestUser {
             .unwrap();
     }
 
+    pub async fn update_budget(
+        &self,
+        bench: &mut ProgramTestBench,
+        source: &VotePowerUnit,
+        scope: &Pubkey,
+        budget: u64,
+    ) -> Result<(), ProgramError> {
+        bench
+            .process_transaction(
+                &[update_token_owner_budget(
+                    &lgovernance::id(),
+                    &self.get_vote_power_origin_record_address(source),
+                    &self.keypair.pubkey(),
+                    scope,
+                    budget,
+                )],
+                Some(&[&self.keypair]),
+            )
+            .await
+    }
+
     pub async fn get_token_account(
         &self,
         banks_client: &mut BanksClient,
@@ -676,6 +838,14 @@ impl<'a> TestDelegation<'a> {
         }
     }
     pub async fn delegate(&self, bench: &mut ProgramTestBench, amount: &u64) {
+        self.try_delegate(bench, amount).await.unwrap();
+    }
+
+    pub async fn try_delegate(
+        &self,
+        bench: &mut ProgramTestBench,
+        amount: &u64,
+    ) -> Result<(), ProgramError> {
         let from_token_record = self.get_delegator_token_origin_record_address();
         let to_token_record = self.get_delegatee_vote_power_owner_record_address();
         bench
@@ -686,17 +856,17 @@ impl<'a> TestDelegation<'a> {
                     &self
                         .from
                         .get_token_owner_budget_record_address(self.source, &self.scope),
-                    &self.from.keypair.pubkey(),
+                    &SignerMaybeSignForMe::from(&self.from.keypair.pubkey()),
                     &to_token_record,
                     &self.to.keypair.pubkey(),
                     &bench.payer.pubkey(),
+                    self.get_delegator_tag_record_address().as_ref(),
                     amount,
                     &self.scope,
                 )],
                 Some(&[&self.from.keypair]),
             )
             .await
-            .unwrap();
     }
 
     pub async fn undelegate(&self, bench: &mut ProgramTestBench, amount: &u64) {
@@ -718,7 +888,7 @@ impl<'a> TestDelegation<'a> {
                     &self
                         .from
                         .get_token_owner_budget_record_address(self.source, &self.scope),
-                    &self.from.keypair.pubkey(),
+                    &SignerMaybeSignForMe::from(&self.from.keypair.pubkey()),
                     &to_token_record,
                     &self.to.keypair.pubkey(),
                     &bench.payer.pubkey(),
@@ -852,6 +1022,37 @@ impl<'a> TestDelegation<'a> {
     pub fn get_delegator_token_origin_record_address(&self) -> Pubkey {
         self.from.get_vote_power_origin_record_address(self.source)
     }
+
+    pub fn get_delegator_tag_record_address(&self) -> Option<Pubkey> {
+        match self.source {
+            VotePowerUnit::Tag { record_factory } => Some(
+                get_tag_record_program_address(
+                    &ltag::id(),
+                    record_factory,
+                    &self.from.keypair.pubkey(),
+                )
+                .0,
+            ),
+            VotePowerUnit::Mint(_) => None,
+        }
+    }
+
+    pub async fn expire(&self, bench: &mut ProgramTestBench) -> Result<(), ProgramError> {
+        bench
+            .process_transaction(
+                &[expire_tag_delegation(
+                    &lgovernance::id(),
+                    &self.delegation,
+                    &self.get_delegator_token_origin_record_address(),
+                    &self.get_delegator_tag_record_address().unwrap(),
+                    &self.get_delegatee_vote_power_owner_record_address(),
+                    &bench.payer.pubkey(),
+                    None,
+                )],
+                None,
+            )
+            .await
+    }
     pub async fn get_delegator_token_origin_record(
         &self,
         bench: &mut ProgramTestBench,
@@ -938,6 +1139,7 @@ impl TestGovernance {
                     &lgovernance::id(),
                     &self.governance,
                     account.optional_authority.as_ref(),
+                    None,
                     new_authority,
                 )],
                 Some(&[&self.temporary_authority]),
@@ -947,6 +1149,16 @@ impl TestGovernance {
     }
 
     pub async fn create_scope(&self, bench: &mut ProgramTestBench, scope: ScopeConfig) -> Pubkey {
+        self.try_create_scope(bench, scope, None).await.unwrap()
+    }
+
+    /// Creates the scope signed by the authority, a child passes the delegated parent scope the instruction falls within
+    pub async fn try_create_scope(
+        &self,
+        bench: &mut ProgramTestBench,
+        scope: ScopeConfig,
+        authority_scope: Option<&Pubkey>,
+    ) -> Result<Pubkey, ProgramError> {
         let id = Pubkey::new_unique();
         let (scope_address, _create_scope_address_bump_seed) =
             get_scope_program_address(&lgovernance::id(), &id);
@@ -959,13 +1171,13 @@ impl TestGovernance {
                     &self.governance,
                     &bench.payer.pubkey(),
                     &Some(self.temporary_authority.pubkey()),
+                    authority_scope,
                     &scope,
                 )],
                 Some(&[&self.temporary_authority]),
             )
-            .await
-            .unwrap();
-        scope_address
+            .await?;
+        Ok(scope_address)
     }
 
     pub async fn create_scope_system<'b>(
@@ -999,6 +1211,149 @@ impl TestGovernance {
         scope
     }
 
+    pub async fn register_child(
+        &self,
+        bench: &mut ProgramTestBench,
+        child: &TestGovernance,
+        delegated_scopes: Vec<Pubkey>,
+    ) -> Result<(), ProgramError> {
+        bench
+            .process_transaction(
+                &[register_child_governance(
+                    &lgovernance::id(),
+                    &child.governance,
+                    Some(&child.temporary_authority.pubkey()),
+                    &self.governance,
+                    Some(&self.temporary_authority.pubkey()),
+                    None,
+                    &[],
+                    delegated_scopes,
+                )],
+                Some(&[&child.temporary_authority, &self.temporary_authority]),
+            )
+            .await
+    }
+
+    pub async fn freeze_child(
+        &self,
+        bench: &mut ProgramTestBench,
+        child: &TestGovernance,
+        frozen: bool,
+    ) {
+        bench
+            .process_transaction(
+                &[freeze_child_governance(
+                    &lgovernance::id(),
+                    &child.governance,
+                    &self.governance,
+                    Some(&self.temporary_authority.pubkey()),
+                    None,
+                    frozen,
+                )],
+                Some(&[&self.temporary_authority]),
+            )
+            .await
+            .unwrap();
+    }
+
+    pub async fn update_child(
+        &self,
+        bench: &mut ProgramTestBench,
+        child: &TestGovernance,
+        delegated_scopes: Vec<Pubkey>,
+    ) {
+        bench
+            .process_transaction(
+                &[update_child_governance(
+                    &lgovernance::id(),
+                    &child.governance,
+                    &self.governance,
+                    Some(&self.temporary_authority.pubkey()),
+                    None,
+                    delegated_scopes,
+                    Some(child.temporary_authority.pubkey()),
+                )],
+                Some(&[&self.temporary_authority]),
+            )
+            .await
+            .unwrap();
+    }
+
+    pub async fn dissolve_child(
+        &self,
+        bench: &mut ProgramTestBench,
+        child: &TestGovernance,
+    ) -> Result<(), ProgramError> {
+        bench
+            .process_transaction(
+                &[dissolve_child_governance(
+                    &lgovernance::id(),
+                    &child.governance,
+                    &self.governance,
+                    Some(&self.temporary_authority.pubkey()),
+                    None,
+                    &bench.payer.pubkey(),
+                )],
+                Some(&[&self.temporary_authority]),
+            )
+            .await
+    }
+
+    pub async fn detach_child(&self, bench: &mut ProgramTestBench, child: &TestGovernance) {
+        bench
+            .process_transaction(
+                &[detach_child_governance(
+                    &lgovernance::id(),
+                    &child.governance,
+                    &self.governance,
+                    Some(&self.temporary_authority.pubkey()),
+                    None,
+                )],
+                Some(&[&self.temporary_authority]),
+            )
+            .await
+            .unwrap();
+    }
+
+    pub async fn update_pause_config(
+        &self,
+        bench: &mut ProgramTestBench,
+        pause_config: PauseConfig,
+    ) {
+        bench
+            .process_transaction(
+                &[update_pause_config(
+                    &lgovernance::id(),
+                    &self.governance,
+                    Some(&self.temporary_authority.pubkey()),
+                    None,
+                    pause_config,
+                )],
+                Some(&[&self.temporary_authority]),
+            )
+            .await
+            .unwrap();
+    }
+
+    pub async fn pause(
+        &self,
+        bench: &mut ProgramTestBench,
+        guardian: &Keypair,
+        duration: u64,
+    ) -> Result<(), ProgramError> {
+        bench
+            .process_transaction(
+                &[pause_governance(
+                    &lgovernance::id(),
+                    &self.governance,
+                    Some(&guardian.pubkey()),
+                    duration,
+                )],
+                Some(&[guardian]),
+            )
+            .await
+    }
+
     pub async fn get_governance_account(&self, bench: &mut ProgramTestBench) -> GovernanceV2 {
         let account = bench.get_account(&self.governance).await.unwrap();
 
@@ -1021,12 +1376,147 @@ impl TestGovernance {
             .unwrap();
     }
 }
+pub struct TestRealm {
+    pub realm: Pubkey,
+    pub authority: Keypair,
+}
+
+impl TestRealm {
+    pub async fn new(
+        bench: &mut ProgramTestBench,
+        name: &str,
+        governing_mints: Vec<Pubkey>,
+        min_weight_to_create_governance: u64,
+    ) -> Self {
+        let authority = Keypair::new();
+        bench
+            .process_transaction(
+                &[create_realm_registry(
+                    &lgovernance::id(),
+                    &bench.payer.pubkey(),
+                    name,
+                    Some(authority.pubkey()),
+                    governing_mints,
+                    Vec::new(),
+                    min_weight_to_create_governance,
+                )],
+                Some(&[&authority]),
+            )
+            .await
+            .unwrap();
+        Self {
+            realm: get_realm_address(&lgovernance::id(), name).unwrap().0,
+            authority,
+        }
+    }
+
+    pub async fn add_governance(
+        &self,
+        bench: &mut ProgramTestBench,
+        governance: &TestGovernance,
+        creator: &TestUser,
+        vote_power_origin_record: Option<&Pubkey>,
+    ) -> Result<(), ProgramError> {
+        self.add_governance_as(
+            bench,
+            governance,
+            creator,
+            vote_power_origin_record,
+            &self.authority,
+        )
+        .await
+    }
+
+    /// Adds the governance, signed by the given realm authority
+    pub async fn add_governance_as(
+        &self,
+        bench: &mut ProgramTestBench,
+        governance: &TestGovernance,
+        creator: &TestUser,
+        vote_power_origin_record: Option<&Pubkey>,
+        realm_authority: &Keypair,
+    ) -> Result<(), ProgramError> {
+        bench
+            .process_transaction(
+                &[add_realm_governance(
+                    &lgovernance::id(),
+                    &self.realm,
+                    &governance.governance,
+                    Some(&governance.temporary_authority.pubkey()),
+                    None,
+                    &realm_authority.pubkey(),
+                    &creator.keypair.pubkey(),
+                    vote_power_origin_record,
+                    &bench.payer.pubkey(),
+                )],
+                Some(&[
+                    &governance.temporary_authority,
+                    realm_authority,
+                    &creator.keypair,
+                ]),
+            )
+            .await
+    }
+
+    pub async fn remove_governance(
+        &self,
+        bench: &mut ProgramTestBench,
+        governance: &TestGovernance,
+    ) {
+        bench
+            .process_transaction(
+                &[remove_realm_governance(
+                    &lgovernance::id(),
+                    &self.realm,
+                    &governance.governance,
+                    &self.authority.pubkey(),
+                )],
+                Some(&[&self.authority]),
+            )
+            .await
+            .unwrap();
+    }
+
+    pub async fn update(
+        &self,
+        bench: &mut ProgramTestBench,
+        governing_mints: Vec<Pubkey>,
+        tag_record_factories: Vec<Pubkey>,
+        min_weight_to_create_governance: u64,
+    ) {
+        bench
+            .process_transaction(
+                &[update_realm(
+                    &lgovernance::id(),
+                    &self.realm,
+                    &self.authority.pubkey(),
+                    &bench.payer.pubkey(),
+                    Some(self.authority.pubkey()),
+                    governing_mints,
+                    tag_record_factories,
+                    min_weight_to_create_governance,
+                )],
+                Some(&[&self.authority]),
+            )
+            .await
+            .unwrap();
+    }
+
+    pub async fn get_realm_account(&self, bench: &mut ProgramTestBench) -> Realm {
+        let account = bench.get_account(&self.realm).await.unwrap();
+
+        try_from_slice_unchecked::<Realm>(&account.data).unwrap()
+    }
+}
+
 pub struct TestProposal {
     pub proposal: Pubkey,
+    pub governance: Pubkey,
     pub proposal_transactions: HashMap<u16, Vec<Pubkey>>,
     pub scopes: Vec<Pubkey>,
     pub options: Vec<Pubkey>,
     pub instruction_index: u16,
+    pub parent_scopes: Vec<Pubkey>,
 }
 
 impl TestProposal {
@@ -1046,13 +1536,14 @@ impl TestProposal {
         );
         let mut instructions = vec![create_proposal(
             &lgovernance::id(),
-            &owner.pubkey(),
+            &SignerMaybeSignForMe::from(&owner.pubkey()),
             &governance.governance,
             &bench.payer.pubkey(),
             proposal_index,
             vote_type,
             scopes.len() as u8,
             &ContentSource::String("Info".into()),
+            None,
         )];
         for scope in &scopes {
             instructions.push(insert_scope(
@@ -1071,10 +1562,12 @@ impl TestProposal {
 
         Self {
             proposal: proposal_address,
+            governance: governance.governance,
             proposal_transactions: HashMap::new(),
             scopes,
             instruction_index: 0,
             options: Vec::new(),
+            parent_scopes: Vec::new(),
         }
     }
 
@@ -1084,6 +1577,47 @@ impl TestProposal {
         scope: &Pubkey,
         governance: &TestGovernance,
         transfer_amount: u64,
+    ) -> (TestProposal, WalletCookie) {
+        Self::new_scheduled_transfer_proposal(
+            bench,
+            owner,
+            scope,
+            governance,
+            transfer_amount,
+            None,
+        )
+        .await
+    }
+
+    pub async fn new_scheduled_transfer_proposal(
+        bench: &mut ProgramTestBench,
+        owner: &TestUser,
+        scope: &Pubkey,
+        governance: &TestGovernance,
+        transfer_amount: u64,
+        start_voting_at: Option<UnixTimestamp>,
+    ) -> (TestProposal, WalletCookie) {
+        Self::new_child_transfer_proposal(
+            bench,
+            owner,
+            scope,
+            governance,
+            transfer_amount,
+            start_voting_at,
+            Vec::new(),
+        )
+        .await
+    }
+
+    /// Transfer proposal of a child governance, acting within the delegated parent scopes
+    pub async fn new_child_transfer_proposal(
+        bench: &mut ProgramTestBench,
+        owner: &TestUser,
+        scope: &Pubkey,
+        governance: &TestGovernance,
+        transfer_amount: u64,
+        start_voting_at: Option<UnixTimestamp>,
+        parent_scopes: Vec<Pubkey>,
     ) -> (TestProposal, WalletCookie) {
         let mut proposal = TestProposal::new(
             bench,
@@ -1094,6 +1628,7 @@ impl TestProposal {
             &owner.keypair,
         )
         .await;
+        proposal.parent_scopes = parent_scopes;
 
         proposal
             .add_option(bench, &CreateProposalOptionType::Deny, &owner.keypair)
@@ -1135,8 +1670,9 @@ impl TestProposal {
             .await;
 
         proposal
-            .finalize_draft(bench, governance, &owner.keypair)
-            .await;
+            .finalize_draft(bench, governance, &owner.keypair, start_voting_at)
+            .await
+            .unwrap();
         (proposal, recipent_wallet)
     }
 
@@ -1188,10 +1724,12 @@ impl TestProposal {
             &bench.payer.pubkey(),
             &owner.pubkey(),
             &self.proposal,
+            &self.governance,
             option_index,
             option_instructions.len() as u16,
             hold_up_time,
             instructions,
+            &self.parent_scopes,
         )];
 
         let instruction_key = get_proposal_transaction_address(
@@ -1208,12 +1746,96 @@ impl TestProposal {
             .unwrap();
     }
 
+    pub async fn remove_transaction(
+        &self,
+        bench: &mut ProgramTestBench,
+        option_index: u16,
+        transaction_index: u16,
+        owner: &Keypair,
+    ) -> Result<(), ProgramError> {
+        bench
+            .process_transaction(
+                &[remove_transaction(
+                    &lgovernance::id(),
+                    &owner.pubkey(),
+                    &self.proposal,
+                    &bench.payer.pubkey(),
+                    option_index,
+                    transaction_index,
+                )],
+                Some(&[owner]),
+            )
+            .await
+    }
+
+    pub async fn remove_option(
+        &mut self,
+        bench: &mut ProgramTestBench,
+        option_index: u16,
+        owner: &Keypair,
+    ) -> Result<(), ProgramError> {
+        bench
+            .process_transaction(
+                &[remove_proposal_option(
+                    &lgovernance::id(),
+                    &owner.pubkey(),
+                    &self.proposal,
+                    &bench.payer.pubkey(),
+                    option_index,
+                )],
+                Some(&[owner]),
+            )
+            .await?;
+        self.options.pop();
+        Ok(())
+    }
+
+    pub async fn update_source(
+        &self,
+        bench: &mut ProgramTestBench,
+        source: &ContentSource,
+        owner: &Keypair,
+    ) -> Result<(), ProgramError> {
+        bench
+            .process_transaction(
+                &[update_proposal_source(
+                    &lgovernance::id(),
+                    &owner.pubkey(),
+                    &self.proposal,
+                    &bench.payer.pubkey(),
+                    source,
+                )],
+                Some(&[owner]),
+            )
+            .await
+    }
+
     pub async fn finalize_draft(
         &self,
         bench: &mut ProgramTestBench,
         governance: &TestGovernance,
         owner: &Keypair,
-    ) {
+        start_voting_at: Option<UnixTimestamp>,
+    ) -> Result<(), ProgramError> {
+        self.finalize_draft_as(
+            bench,
+            governance,
+            &SignerMaybeSignForMe::from(&owner.pubkey()),
+            &[owner],
+            start_voting_at,
+        )
+        .await
+    }
+
+    /// Finalizes the draft for the owner, signed by the owner or signers of a SignForMe record of the owner
+    pub async fn finalize_draft_as(
+        &self,
+        bench: &mut ProgramTestBench,
+        governance: &TestGovernance,
+        owner: &SignerMaybeSignForMe,
+        signers: &[&Keypair],
+        start_voting_at: Option<UnixTimestamp>,
+    ) -> Result<(), ProgramError> {
         let mut scope_accounts = Vec::new();
         for scope in &self.scopes {
             let account = bench.get_account(scope).await.unwrap();
@@ -1231,12 +1853,12 @@ impl TestProposal {
                         let tag_record = get_tag_record_program_address(
                             &ltag::id(),
                             &record_factory,
-                            &owner.pubkey(),
+                            &owner.original_signer,
                         )
                         .0;
 
                         SignedCreateProposal::Tag {
-                            owner: owner.pubkey(),
+                            owner: owner.clone(),
                             record: tag_record,
                         }
                     }
@@ -1247,31 +1869,37 @@ impl TestProposal {
                         let record = get_vote_power_owner_record_address(
                             &lgovernance::id(),
                             &VotePowerUnit::Mint(mint),
-                            &owner.pubkey(),
+                            &owner.original_signer,
                             scope,
                         )
                         .0;
                         SignedCreateProposal::Token {
-                            governing_owner: owner.pubkey(),
+                            governing_owner: owner.clone(),
                             owner_record: record,
                         }
                     }
+                    lgovernance::state::scopes::scope::CreateProposalCriteria::Tags(condition) => {
+                        SignedCreateProposal::Tags {
+                            owner: owner.clone(),
+                            condition,
+                        }
+                    }
                 },
             ))
         }
 
         let instructions = [finalize_draft(
             &lgovernance::id(),
-            &owner.pubkey(),
+            owner,
             &self.proposal,
             &governance.governance,
             &signed_scopes,
+            start_voting_at,
         )];
 
         bench
-            .process_transaction(&instructions, Some(&[owner]))
+            .process_transaction(&instructions, Some(signers))
             .await
-            .unwrap();
     }
 
     /* pub async fn get_proposal_transactions(
@@ -1383,6 +2011,31 @@ impl TestProposal {
         source: &VotePowerUnit,
         scope: &Pubkey,
     ) {
+        self.vote_with_delegate_as(
+            bench,
+            vote,
+            owner,
+            source,
+            scope,
+            &SignerMaybeSignForMe::from(&owner.keypair.pubkey()),
+            &[&owner.keypair],
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Votes for the owner, signed by the owner or signers of a SignForMe record of the owner
+    #[allow(clippy::too_many_arguments)]
+    pub async fn vote_with_delegate_as(
+        &self,
+        bench: &mut ProgramTestBench,
+        vote: &Vote,
+        owner: &TestUser,
+        source: &VotePowerUnit,
+        scope: &Pubkey,
+        governing_owner: &SignerMaybeSignForMe,
+        signers: &[&Keypair],
+    ) -> Result<(), ProgramError> {
         let vote_options = self.get_vote_option(bench, vote).await;
         let latest_vote = owner
             .get_latest_vote_delegate_address(bench, source, scope)
@@ -1395,17 +2048,87 @@ impl TestProposal {
                     &bench.payer.pubkey(),
                     &self.proposal,
                     &owner.get_token_owner_delegate_record_address(scope, source),
-                    &owner.keypair.pubkey(),
+                    governing_owner,
                     scope,
+                    &self.governance,
                     &vote_options,
                     latest_vote.as_ref(),
-                    true,
                 )],
-                Some(&[&owner.keypair]),
+                Some(signers),
             )
             .await
-            .unwrap();
     }
+    pub async fn vote_signed(
+        &self,
+        bench: &mut ProgramTestBench,
+        vote: &Vote,
+        owner: &TestUser,
+        source: &VotePowerUnit,
+        scope: &Pubkey,
+        nonce: u64,
+    ) -> Result<(), ProgramError> {
+        self.vote_signed_with_messages(
+            bench,
+            &[(vote, owner, nonce)],
+            source,
+            scope,
+            |signed_vote| signed_vote.to_message(&lgovernance::id()),
+        )
+        .await
+    }
+
+    /// Casts the votes with one instruction each in a single transaction, the votes are signed over the messages given by to_message
+    pub async fn vote_signed_with_messages(
+        &self,
+        bench: &mut ProgramTestBench,
+        votes: &[(&Vote, &TestUser, u64)],
+        source: &VotePowerUnit,
+        scope: &Pubkey,
+        to_message: fn(&SignedVote) -> Vec<u8>,
+    ) -> Result<(), ProgramError> {
+        let mut instructions = Vec::new();
+        let mut cast_instructions = Vec::new();
+        for (index, (vote, owner, nonce)) in votes.iter().enumerate() {
+            let vote_options = self.get_vote_option(bench, vote).await;
+            let latest_vote = owner
+                .get_latest_vote_delegate_address(bench, source, scope)
+                .await;
+            let signed_vote = SignedVote {
+                proposal: self.proposal,
+                options: (*vote).clone(),
+                scope: *scope,
+                nonce: *nonce,
+            };
+            let message = to_message(&signed_vote);
+            let signature = owner.keypair.sign_message(&message);
+            let mut signature_bytes = [0u8; 64];
+            signature_bytes.copy_from_slice(signature.as_ref());
+
+            instructions.push(new_ed25519_verify_instruction(
+                &owner.keypair.pubkey(),
+                &signature_bytes,
+                &message,
+            ));
+            cast_instructions.push(cast_signed_votes(
+                &lgovernance::id(),
+                &bench.payer.pubkey(),
+                &[CastSignedVote {
+                    vote: signed_vote,
+                    signature_instruction_index: index as u16,
+                    governance: self.governance,
+                    governing_owner: owner.keypair.pubkey(),
+                    token_record: owner.get_token_owner_delegate_record_address(scope, source),
+                    options: vote_options,
+                    last_vote_record: latest_vote,
+                }],
+            ));
+        }
+        instructions.append(&mut cast_instructions);
+
+        // The relayer (bench payer) submits and pays for the votes
+        bench.process_transaction(&instructions, None).await
+    }
+
     pub async fn unvote_with_delegate(
         &self,
         bench: &mut ProgramTestBench,
@@ -1415,6 +2138,33 @@ impl TestProposal {
         scope: &Pubkey,
         beneficiary: &Pubkey,
     ) {
+        self.unvote_with_delegate_as(
+            bench,
+            vote,
+            owner,
+            source,
+            scope,
+            beneficiary,
+            &SignerMaybeSignForMe::from(&owner.keypair.pubkey()),
+            &[&owner.keypair],
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Unvotes for the owner, signed by the owner or signers of a SignForMe record of the owner
+    #[allow(clippy::too_many_arguments)]
+    pub async fn unvote_with_delegate_as(
+        &self,
+        bench: &mut ProgramTestBench,
+        vote: Vote,
+        owner: &TestUser,
+        source: &VotePowerUnit,
+        scope: &Pubkey,
+        beneficiary: &Pubkey,
+        governing_owner: &SignerMaybeSignForMe,
+        signers: &[&Keypair],
+    ) -> Result<(), ProgramError> {
         let mut vote_options = Vec::new();
         for index in &vote {
             let option = get_proposal_option_program_address(
@@ -1432,15 +2182,14 @@ impl TestProposal {
                     &lgovernance::id(),
                     &self.proposal,
                     &owner.get_token_owner_delegate_record_address(scope, source),
-                    &owner.keypair.pubkey(),
+                    governing_owner,
                     beneficiary,
                     scope,
                     &vote_options,
                 )],
-                Some(&[&owner.keypair]),
+                Some(signers),
             )
             .await
-            .unwrap();
     }
     /*
     pub async fn unvote(
@@ -1487,6 +2236,16 @@ impl TestProposal {
         option_index: u16,
         /*  instruction_accounts: &[AccountMeta], */
     ) {
+        self.try_execute_transactions(bench, option_index)
+            .await
+            .unwrap();
+    }
+
+    pub async fn try_execute_transactions(
+        &self,
+        bench: &mut ProgramTestBench,
+        option_index: u16,
+    ) -> Result<(), ProgramError> {
         let governance = self.get_proposal_account(bench).await.governance;
         let governance_account = try_from_slice_unchecked::<GovernanceV2>(
             &bench.get_account(&governance).await.unwrap().data,
@@ -1543,18 +2302,22 @@ impl TestProposal {
                         )
                         .0,
                         governance_account.seed,
+                        &self.parent_scopes,
                         &account_metas, // ?????
                     )],
                     None,
                 )
-                .await
-                .unwrap();
+                .await?;
         }
+        Ok(())
     }
 
-    pub async fn count_votes(&self, bench: &mut ProgramTestBench) {
+    async fn count_vote_max_weights_instruction(
+        &self,
+        bench: &mut ProgramTestBench,
+        proposal: &ProposalV2,
+    ) -> Instruction {
         let mut scope_sources = Vec::new();
-        let proposal = self.get_proposal_account(bench).await;
         for scope_weight in &proposal.scopes_max_vote_weight {
             let scope = try_from_slice_unchecked::<Scope>(
                 &bench.get_account(&scope_weight.scope).await.unwrap().data,
@@ -1571,13 +2334,17 @@ impl TestProposal {
                     .collect::<Vec<VotePowerUnit>>(),
             ))
         }
+        count_vote_max_weights(&lgovernance::id(), &self.proposal, &scope_sources)
+    }
+
+    pub async fn count_votes(&self, bench: &mut ProgramTestBench) {
+        let proposal = self.get_proposal_account(bench).await;
 
         // Count votes for all options
-        let mut instructions = vec![count_vote_max_weights(
-            &lgovernance::id(),
-            &self.proposal,
-            &scope_sources,
-        )];
+        let mut instructions = vec![
+            self.count_vote_max_weights_instruction(bench, &proposal)
+                .await,
+        ];
 
         for option in &self.options {
             instructions.push(count_votes(
@@ -1595,6 +2362,27 @@ impl TestProposal {
             .unwrap();
     }
 
+    /// Counts the votes of the provided options in one instruction
+    pub async fn count_votes_all(
+        &self,
+        bench: &mut ProgramTestBench,
+        options: &[Pubkey],
+    ) -> Result<(), ProgramError> {
+        let proposal = self.get_proposal_account(bench).await;
+        let instructions = [
+            self.count_vote_max_weights_instruction(bench, &proposal)
+                .await,
+            count_votes_all(
+                &lgovernance::id(),
+                &self.proposal,
+                proposal.deny_option.as_ref(),
+                &self.scopes,
+                options,
+            ),
+        ];
+        bench.process_transaction(&instructions, None).await
+    }
+
     pub async fn advance_clock_past_max_hold_up_time(
         &self,
         bench: &mut ProgramTestBench,
@@ -1616,6 +2404,12 @@ impl TestProposal {
             .await;
     }
 
+    pub async fn start_voting(&self, bench: &mut ProgramTestBench) -> Result<(), ProgramError> {
+        bench
+            .process_transaction(&[start_voting(&lgovernance::id(), &self.proposal)], None)
+            .await
+    }
+
     pub async fn get_state(&self, bench: &mut ProgramTestBench) -> ProposalState {
         let proposal = self.get_proposal_account(bench).await;
         proposal.state
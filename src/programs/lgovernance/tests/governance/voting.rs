@@ -1,11 +1,21 @@
 use crate::governance::utils::{TestDelegation, TestTagRecordFactory, TestVotePowerSource};
 use crate::utils::program_test;
-use lgovernance::state::enums::ProposalState;
+use borsh::BorshSerialize;
+use lgovernance::error::GovernanceError;
+use lgovernance::state::enums::{GovernanceAction, ProposalState};
+use lsignforme::{
+    get_sign_for_me_program_address,
+    instruction::{create_sign_for_me, SignForMe, SignerMaybeSignForMe},
+    state::{SignForMeAccount, SignForMeScope},
+};
+use ltag::error::TagError;
+use solana_program::{borsh::try_from_slice_unchecked, pubkey::Pubkey};
+use solana_sdk::{signature::Keypair, signer::Signer};
 
 use lgovernance::state::scopes::scope::VotePowerUnit;
+use lgovernance::state::vote_power_origin_record::VotePowerOriginRecord;
 use solana_program_test::*;
 
-
 use super::super::bench::ProgramTestBench;
 use super::utils::{TestGovernance, TestProposal, TestToken, TestUser};
 
@@ -189,6 +199,129 @@ async fn success_token_vote_unvote() {
     )
 }
 
+#[tokio::test]
+async fn success_vote_unvote_sign_for_me() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let user = TestUser::new();
+
+    let governance_token = TestToken::new(&mut bench).await;
+
+    governance_token
+        .create_token_holder_account(&mut bench)
+        .await;
+
+    user.create_associated_token_account(&mut bench, &governance_token)
+        .await;
+
+    governance_token
+        .mint_to(
+            &mut bench,
+            1,
+            &user.get_associated_token_account_address(&governance_token),
+        )
+        .await;
+
+    user.deposit_governance_tokens(&mut bench, 1, &governance_token)
+        .await;
+
+    let vote_power_unit = VotePowerUnit::Mint(governance_token.mint);
+
+    let mut governance = TestGovernance::new(&mut bench).await;
+    governance.with_native_treasury(&mut bench).await;
+
+    let scope = governance
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestToken(&governance_token),
+        )
+        .await;
+
+    user.create_delegatee(&mut bench, &vote_power_unit, &scope)
+        .await;
+    let self_delegation =
+        TestDelegation::new(&mut bench, &user, &user, &vote_power_unit, &scope).await;
+    self_delegation.delegate(&mut bench, &1).await;
+
+    let (proposal, _recipent_wallet) =
+        TestProposal::new_transfer_proposal(&mut bench, &user, &scope, &governance, 1).await;
+
+    // The user lets a session key vote in the governance
+    let session_key = Keypair::new();
+    let sign_for_me_scope = SignForMeScope {
+        program: lgovernance::id(),
+        resource: Some(governance.governance),
+        actions: GovernanceAction::Vote.action(),
+    };
+    bench
+        .process_transaction(
+            &[create_sign_for_me(
+                &lsignforme::id(),
+                &user.keypair.pubkey(),
+                &[session_key.pubkey()],
+                1,
+                &sign_for_me_scope,
+                &bench.payer.pubkey(),
+                None,
+                None,
+            )],
+            Some(&[&user.keypair]),
+        )
+        .await
+        .unwrap();
+
+    let sign_for_me = get_sign_for_me_program_address(
+        &lsignforme::id(),
+        &user.keypair.pubkey(),
+        &[session_key.pubkey()],
+        1,
+        &sign_for_me_scope,
+    )
+    .0;
+    let governing_owner = SignerMaybeSignForMe {
+        original_signer: user.keypair.pubkey(),
+        sign_for_me: Some(SignForMe {
+            sign_for_me,
+            signers: vec![session_key.pubkey()],
+        }),
+    };
+
+    proposal
+        .vote_with_delegate_as(
+            &mut bench,
+            &vec![0],
+            &user,
+            &vote_power_unit,
+            &scope,
+            &governing_owner,
+            &[&session_key],
+        )
+        .await
+        .unwrap();
+
+    let beneficiary = bench.with_wallet().await;
+    proposal
+        .unvote_with_delegate_as(
+            &mut bench,
+            vec![0],
+            &user,
+            &vote_power_unit,
+            &scope,
+            &beneficiary.address,
+            &governing_owner,
+            &[&session_key],
+        )
+        .await
+        .unwrap();
+
+    // Both uses are counted
+    let sign_for_me_account = try_from_slice_unchecked::<SignForMeAccount>(
+        &bench.get_account(&sign_for_me).await.unwrap().data,
+    )
+    .unwrap();
+    assert_eq!(sign_for_me_account.uses, 2);
+}
+
 #[tokio::test]
 async fn success_tag_vote_unvote() {
     let mut bench = ProgramTestBench::start_new(program_test()).await;
@@ -258,3 +391,764 @@ async fn success_tag_vote_unvote() {
             > beneficiary_balance
     )
 }
+
+#[tokio::test]
+async fn success_signed_vote() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let user = TestUser::new();
+
+    let governance_token = TestToken::new(&mut bench).await;
+
+    governance_token
+        .create_token_holder_account(&mut bench)
+        .await;
+
+    user.create_associated_token_account(&mut bench, &governance_token)
+        .await;
+
+    governance_token
+        .mint_to(
+            &mut bench,
+            1,
+            &user.get_associated_token_account_address(&governance_token),
+        )
+        .await;
+
+    user.deposit_governance_tokens(&mut bench, 1, &governance_token)
+        .await;
+
+    let vote_power_unit = VotePowerUnit::Mint(governance_token.mint);
+
+    let mut governance = TestGovernance::new(&mut bench).await;
+    governance.with_native_treasury(&mut bench).await;
+
+    let scope = governance
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestToken(&governance_token),
+        )
+        .await;
+
+    user.create_delegatee(&mut bench, &vote_power_unit, &scope)
+        .await;
+
+    let self_delegation =
+        TestDelegation::new(&mut bench, &user, &user, &vote_power_unit, &scope).await;
+    self_delegation.delegate(&mut bench, &1).await;
+
+    let (proposal, _recipent_wallet) =
+        TestProposal::new_transfer_proposal(&mut bench, &user, &scope, &governance, 1).await;
+
+    // vote off-chain, submitted by a relayer
+    proposal
+        .vote_signed(&mut bench, &vec![1], &user, &vote_power_unit, &scope, 1)
+        .await
+        .unwrap();
+
+    // replaying the same signed vote fails
+    assert_eq!(
+        proposal
+            .vote_signed(&mut bench, &vec![1], &user, &vote_power_unit, &scope, 1)
+            .await,
+        Err(GovernanceError::SignedVoteNonceAlreadyUsed.into())
+    );
+
+    // the nonce is still used after unvoting, so the old signed vote can not be replayed
+    let beneficiary = bench.with_wallet().await;
+    proposal
+        .unvote_with_delegate(
+            &mut bench,
+            vec![1],
+            &user,
+            &vote_power_unit,
+            &scope,
+            &beneficiary.address,
+        )
+        .await;
+    bench.advance_clock().await;
+    assert_eq!(
+        proposal
+            .vote_signed(&mut bench, &vec![1], &user, &vote_power_unit, &scope, 1)
+            .await,
+        Err(GovernanceError::SignedVoteNonceAlreadyUsed.into())
+    );
+
+    // a newly signed vote with a higher nonce is accepted
+    proposal
+        .vote_signed(&mut bench, &vec![1], &user, &vote_power_unit, &scope, 2)
+        .await
+        .unwrap();
+
+    proposal.count_votes(&mut bench).await;
+
+    assert_eq!(
+        proposal.get_state(&mut bench).await,
+        ProposalState::Succeeded
+    );
+}
+
+#[tokio::test]
+async fn success_signed_votes_in_one_transaction() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let users = [TestUser::new(), TestUser::new()];
+
+    let governance_token = TestToken::new(&mut bench).await;
+
+    governance_token
+        .create_token_holder_account(&mut bench)
+        .await;
+
+    for user in &users {
+        user.create_associated_token_account(&mut bench, &governance_token)
+            .await;
+
+        governance_token
+            .mint_to(
+                &mut bench,
+                1,
+                &user.get_associated_token_account_address(&governance_token),
+            )
+            .await;
+
+        user.deposit_governance_tokens(&mut bench, 1, &governance_token)
+            .await;
+    }
+
+    let vote_power_unit = VotePowerUnit::Mint(governance_token.mint);
+
+    let mut governance = TestGovernance::new(&mut bench).await;
+    governance.with_native_treasury(&mut bench).await;
+
+    let scope = governance
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestToken(&governance_token),
+        )
+        .await;
+
+    for user in &users {
+        user.create_delegatee(&mut bench, &vote_power_unit, &scope)
+            .await;
+
+        let self_delegation =
+            TestDelegation::new(&mut bench, user, user, &vote_power_unit, &scope).await;
+        self_delegation.delegate(&mut bench, &1).await;
+    }
+
+    let (proposal, _recipent_wallet) =
+        TestProposal::new_transfer_proposal(&mut bench, &users[0], &scope, &governance, 1).await;
+
+    // votes signed without the domain tag and program id, or for another program, are rejected
+    assert_eq!(
+        proposal
+            .vote_signed_with_messages(
+                &mut bench,
+                &[(&vec![1], &users[0], 1)],
+                &vote_power_unit,
+                &scope,
+                |signed_vote| signed_vote.try_to_vec().unwrap(),
+            )
+            .await,
+        Err(GovernanceError::InvalidSignedVote.into())
+    );
+    assert_eq!(
+        proposal
+            .vote_signed_with_messages(
+                &mut bench,
+                &[(&vec![1], &users[0], 1)],
+                &vote_power_unit,
+                &scope,
+                |signed_vote| signed_vote.to_message(&Pubkey::new_unique()),
+            )
+            .await,
+        Err(GovernanceError::InvalidSignedVote.into())
+    );
+
+    // every instruction finds the signature of its vote by index
+    proposal
+        .vote_signed_with_messages(
+            &mut bench,
+            &[(&vec![1], &users[0], 1), (&vec![1], &users[1], 1)],
+            &vote_power_unit,
+            &scope,
+            |signed_vote| signed_vote.to_message(&lgovernance::id()),
+        )
+        .await
+        .unwrap();
+
+    proposal.count_votes(&mut bench).await;
+
+    assert_eq!(
+        proposal.get_state(&mut bench).await,
+        ProposalState::Succeeded
+    );
+}
+
+#[tokio::test]
+async fn success_scheduled_vote() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let user = TestUser::new();
+
+    let governance_token = TestToken::new(&mut bench).await;
+
+    governance_token
+        .create_token_holder_account(&mut bench)
+        .await;
+
+    user.create_associated_token_account(&mut bench, &governance_token)
+        .await;
+
+    governance_token
+        .mint_to(
+            &mut bench,
+            1,
+            &user.get_associated_token_account_address(&governance_token),
+        )
+        .await;
+
+    user.deposit_governance_tokens(&mut bench, 1, &governance_token)
+        .await;
+
+    let vote_power_unit = VotePowerUnit::Mint(governance_token.mint);
+
+    let mut governance = TestGovernance::new(&mut bench).await;
+    governance.with_native_treasury(&mut bench).await;
+
+    let scope = governance
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestToken(&governance_token),
+        )
+        .await;
+
+    user.create_delegatee(&mut bench, &vote_power_unit, &scope)
+        .await;
+
+    let self_delegation =
+        TestDelegation::new(&mut bench, &user, &user, &vote_power_unit, &scope).await;
+    self_delegation.delegate(&mut bench, &1).await;
+
+    let start_voting_at = bench.get_clock().await.unix_timestamp + 1000;
+    let (proposal, _recipent_wallet) = TestProposal::new_scheduled_transfer_proposal(
+        &mut bench,
+        &user,
+        &scope,
+        &governance,
+        1,
+        Some(start_voting_at),
+    )
+    .await;
+
+    assert_eq!(
+        proposal.get_state(&mut bench).await,
+        ProposalState::Scheduled
+    );
+
+    // voting has not started
+    assert!(proposal
+        .vote_signed(&mut bench, &vec![1], &user, &vote_power_unit, &scope, 1)
+        .await
+        .is_err());
+    assert!(proposal.start_voting(&mut bench).await.is_err());
+
+    bench.advance_clock_past_timestamp(start_voting_at).await;
+
+    // anyone can open voting once the start time has passed
+    proposal.start_voting(&mut bench).await.unwrap();
+    let proposal_data = proposal.get_proposal_account(&mut bench).await;
+    assert_eq!(proposal_data.state, ProposalState::Voting);
+    assert_eq!(proposal_data.voting_at, Some(start_voting_at));
+
+    proposal
+        .vote_with_delegate(&mut bench, &vec![1], &user, &vote_power_unit, &scope)
+        .await;
+
+    proposal.count_votes(&mut bench).await;
+
+    assert_eq!(
+        proposal.get_state(&mut bench).await,
+        ProposalState::Succeeded
+    );
+}
+
+#[tokio::test]
+async fn success_count_votes_all_in_batches() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let user = TestUser::new();
+
+    let governance_token = TestToken::new(&mut bench).await;
+
+    governance_token
+        .create_token_holder_account(&mut bench)
+        .await;
+
+    user.create_associated_token_account(&mut bench, &governance_token)
+        .await;
+
+    governance_token
+        .mint_to(
+            &mut bench,
+            1,
+            &user.get_associated_token_account_address(&governance_token),
+        )
+        .await;
+
+    user.deposit_governance_tokens(&mut bench, 1, &governance_token)
+        .await;
+
+    let vote_power_unit = VotePowerUnit::Mint(governance_token.mint);
+
+    let mut governance = TestGovernance::new(&mut bench).await;
+    governance.with_native_treasury(&mut bench).await;
+
+    let scope = governance
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestToken(&governance_token),
+        )
+        .await;
+
+    user.create_delegatee(&mut bench, &vote_power_unit, &scope)
+        .await;
+
+    let self_delegation =
+        TestDelegation::new(&mut bench, &user, &user, &vote_power_unit, &scope).await;
+    self_delegation.delegate(&mut bench, &1).await;
+
+    let (proposal, _recipent_wallet) =
+        TestProposal::new_transfer_proposal(&mut bench, &user, &scope, &governance, 1).await;
+
+    proposal
+        .vote_with_delegate(&mut bench, &vec![1], &user, &vote_power_unit, &scope)
+        .await;
+
+    // A batch without the instruction option only counts the deny option, which is not resolved yet
+    proposal.count_votes_all(&mut bench, &[]).await.unwrap();
+    let proposal_data = proposal.get_proposal_account(&mut bench).await;
+    assert_eq!(proposal_data.state, ProposalState::Voting);
+    assert_eq!(proposal_data.options_counted_count, 0);
+
+    proposal
+        .count_votes_all(&mut bench, &proposal.options)
+        .await
+        .unwrap();
+    let proposal_data = proposal.get_proposal_account(&mut bench).await;
+    assert_eq!(proposal_data.state, ProposalState::Succeeded);
+    assert_eq!(proposal_data.options_counted_count, 2);
+    assert_eq!(proposal_data.winning_options, vec![1]);
+    assert_eq!(proposal_data.defeated_options, vec![0]);
+
+    // A decided proposal can not be counted again, so the hold up time is not restarted
+    bench.advance_clock().await;
+    assert_eq!(
+        proposal
+            .count_votes_all(&mut bench, &proposal.options)
+            .await,
+        Err(GovernanceError::InvalidProposalState.into())
+    );
+    let recounted_proposal_data = proposal.get_proposal_account(&mut bench).await;
+    assert_eq!(recounted_proposal_data.state, ProposalState::Succeeded);
+    assert_eq!(
+        recounted_proposal_data.voting_completed_at,
+        proposal_data.voting_completed_at
+    );
+
+    // Neither after execution
+    proposal
+        .advance_clock_past_max_hold_up_time(&mut bench, 1)
+        .await;
+    proposal.execute_transactions(&mut bench, 1).await;
+    let executed_state = proposal.get_state(&mut bench).await;
+    assert_eq!(
+        proposal
+            .count_votes_all(&mut bench, &proposal.options)
+            .await,
+        Err(GovernanceError::InvalidProposalState.into())
+    );
+    assert_eq!(proposal.get_state(&mut bench).await, executed_state);
+}
+
+#[tokio::test]
+async fn success_tag_quantity_vote() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let user = TestUser::new();
+
+    let tag_record_factory = TestTagRecordFactory::new(&mut bench).await;
+    let vote_power_unit = VotePowerUnit::Tag {
+        record_factory: tag_record_factory.factory,
+    };
+
+    // e.g. a contributor of tier 3
+    tag_record_factory
+        .new_record_with_quantity(&mut bench, &user, 3)
+        .await;
+
+    user.deposit_governance_tag(&mut bench, &tag_record_factory)
+        .await;
+
+    let origin_record = bench
+        .get_borsh_account::<VotePowerOriginRecord>(
+            &user.get_vote_power_origin_record_address(&vote_power_unit),
+        )
+        .await;
+    assert_eq!(origin_record.amount, 3);
+
+    let mut governance = TestGovernance::new(&mut bench).await;
+    governance.with_native_treasury(&mut bench).await;
+
+    let scope = governance
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestTagRecordFactory(&tag_record_factory),
+        )
+        .await;
+
+    user.create_delegatee(&mut bench, &vote_power_unit, &scope)
+        .await;
+
+    let delegation = TestDelegation::new(&mut bench, &user, &user, &vote_power_unit, &scope).await;
+    delegation.delegate(&mut bench, &3).await;
+
+    let (proposal, _recipent_wallet) =
+        TestProposal::new_transfer_proposal(&mut bench, &user, &scope, &governance, 1).await;
+
+    proposal
+        .vote_with_delegate(&mut bench, &vec![1], &user, &vote_power_unit, &scope)
+        .await;
+
+    proposal.count_votes(&mut bench).await;
+
+    // The max vote weight is the total outstanding quantity
+    let proposal_data = proposal.get_proposal_account(&mut bench).await;
+    assert_eq!(proposal_data.scopes_max_vote_weight[0].weight, 3);
+    assert_eq!(
+        proposal.get_state(&mut bench).await,
+        ProposalState::Succeeded
+    );
+}
+
+#[tokio::test]
+async fn success_deposit_indexed_tag_records() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let user = TestUser::new();
+
+    let tag_record_factory = TestTagRecordFactory::new_with_config(&mut bench, None, true).await;
+    let vote_power_unit = VotePowerUnit::Tag {
+        record_factory: tag_record_factory.factory,
+    };
+
+    tag_record_factory
+        .new_indexed_record_with_quantity(&mut bench, &user, 0, 2)
+        .await;
+    tag_record_factory
+        .new_indexed_record_with_quantity(&mut bench, &user, 1, 3)
+        .await;
+
+    // Every record is deposited, so the deposits add up to the outstanding quantity
+    for (index, quantity) in [(0, 2), (1, 3)] {
+        user.deposit_governance_indexed_tag(&mut bench, &tag_record_factory, index)
+            .await
+            .unwrap();
+
+        let origin_record = bench
+            .get_borsh_account::<VotePowerOriginRecord>(
+                &user.get_indexed_vote_power_origin_record_address(&vote_power_unit, index),
+            )
+            .await;
+        assert_eq!(origin_record.amount, quantity);
+    }
+
+    // Depositing a record again does not add to the deposit
+    bench.advance_clock().await;
+    user.deposit_governance_indexed_tag(&mut bench, &tag_record_factory, 0)
+        .await
+        .unwrap();
+    let origin_record = bench
+        .get_borsh_account::<VotePowerOriginRecord>(
+            &user.get_indexed_vote_power_origin_record_address(&vote_power_unit, 0),
+        )
+        .await;
+    assert_eq!(origin_record.amount, 2);
+}
+
+#[tokio::test]
+async fn fail_vote_with_expired_tag() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let user = TestUser::new();
+
+    let tag_record_factory =
+        TestTagRecordFactory::new_with_config(&mut bench, Some(1000), false).await;
+    let vote_power_unit = VotePowerUnit::Tag {
+        record_factory: tag_record_factory.factory,
+    };
+
+    tag_record_factory.new_record(&mut bench, &user).await;
+    user.deposit_governance_tag(&mut bench, &tag_record_factory)
+        .await;
+
+    let origin_record = bench
+        .get_borsh_account::<VotePowerOriginRecord>(
+            &user.get_vote_power_origin_record_address(&vote_power_unit),
+        )
+        .await;
+    let expires_at = origin_record.expires_at.unwrap();
+
+    let mut governance = TestGovernance::new(&mut bench).await;
+    governance.with_native_treasury(&mut bench).await;
+
+    let scope = governance
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestTagRecordFactory(&tag_record_factory),
+        )
+        .await;
+
+    user.create_delegatee(&mut bench, &vote_power_unit, &scope)
+        .await;
+
+    let delegation = TestDelegation::new(&mut bench, &user, &user, &vote_power_unit, &scope).await;
+    delegation.delegate(&mut bench, &1).await;
+
+    let (proposal, _recipent_wallet) =
+        TestProposal::new_transfer_proposal(&mut bench, &user, &scope, &governance, 1).await;
+
+    // The deposit expires together with the tag record
+    bench.advance_clock_past_timestamp(expires_at).await;
+
+    assert_eq!(
+        proposal
+            .vote_with_delegate_as(
+                &mut bench,
+                &vec![1],
+                &user,
+                &vote_power_unit,
+                &scope,
+                &SignerMaybeSignForMe::from(&user.keypair.pubkey()),
+                &[&user.keypair],
+            )
+            .await,
+        Err(GovernanceError::VotePowerExpired.into())
+    );
+}
+
+#[tokio::test]
+async fn success_vote_with_unexpired_delegations() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let user = TestUser::new();
+    let other_user = TestUser::new();
+
+    let tag_record_factory =
+        TestTagRecordFactory::new_with_config(&mut bench, Some(10000), false).await;
+    let vote_power_unit = VotePowerUnit::Tag {
+        record_factory: tag_record_factory.factory,
+    };
+
+    tag_record_factory.new_record(&mut bench, &user).await;
+    user.deposit_governance_tag(&mut bench, &tag_record_factory)
+        .await;
+    let expires_at = bench
+        .get_borsh_account::<VotePowerOriginRecord>(
+            &user.get_vote_power_origin_record_address(&vote_power_unit),
+        )
+        .await
+        .expires_at
+        .unwrap();
+
+    // The record of the other user is issued later, so it expires later
+    bench.advance_clock_past_timestamp(expires_at - 5000).await;
+    tag_record_factory
+        .new_record_with_quantity(&mut bench, &other_user, 2)
+        .await;
+    other_user
+        .deposit_governance_tag(&mut bench, &tag_record_factory)
+        .await;
+
+    let mut governance = TestGovernance::new(&mut bench).await;
+    governance.with_native_treasury(&mut bench).await;
+
+    let scope = governance
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestTagRecordFactory(&tag_record_factory),
+        )
+        .await;
+
+    user.create_delegatee(&mut bench, &vote_power_unit, &scope)
+        .await;
+
+    TestDelegation::new(&mut bench, &user, &user, &vote_power_unit, &scope)
+        .await
+        .delegate(&mut bench, &1)
+        .await;
+    TestDelegation::new(&mut bench, &other_user, &user, &vote_power_unit, &scope)
+        .await
+        .delegate(&mut bench, &2)
+        .await;
+
+    let (proposal, _recipent_wallet) =
+        TestProposal::new_transfer_proposal(&mut bench, &user, &scope, &governance, 1).await;
+
+    // The expired delegation does not block the delegatee, it is only left out of the vote weight
+    bench.advance_clock_past_timestamp(expires_at).await;
+    proposal
+        .vote_with_delegate(&mut bench, &vec![1], &user, &vote_power_unit, &scope)
+        .await;
+
+    let (_, vote_record) = user
+        .get_vote_record_delegate(&mut bench, &proposal, &vote_power_unit, &scope)
+        .await
+        .unwrap();
+    assert_eq!(vote_record.vote_weight, 2);
+}
+
+#[tokio::test]
+async fn success_delegate_renewed_tag_refreshes_expiry() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let user = TestUser::new();
+
+    let tag_record_factory =
+        TestTagRecordFactory::new_with_config(&mut bench, Some(10000), false).await;
+    let vote_power_unit = VotePowerUnit::Tag {
+        record_factory: tag_record_factory.factory,
+    };
+
+    tag_record_factory.new_record(&mut bench, &user).await;
+    user.deposit_governance_tag(&mut bench, &tag_record_factory)
+        .await;
+    let expires_at = bench
+        .get_borsh_account::<VotePowerOriginRecord>(
+            &user.get_vote_power_origin_record_address(&vote_power_unit),
+        )
+        .await
+        .expires_at
+        .unwrap();
+
+    let mut governance = TestGovernance::new(&mut bench).await;
+    governance.with_native_treasury(&mut bench).await;
+
+    let scope = governance
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestTagRecordFactory(&tag_record_factory),
+        )
+        .await;
+
+    user.create_delegatee(&mut bench, &vote_power_unit, &scope)
+        .await;
+
+    let delegation = TestDelegation::new(&mut bench, &user, &user, &vote_power_unit, &scope).await;
+    delegation.delegate(&mut bench, &1).await;
+
+    // Renew the record and deposit it again, then delegate again to move the delegation to the new expiry
+    bench.advance_clock_past_timestamp(expires_at - 5000).await;
+    tag_record_factory.renew_record(&mut bench, &user).await;
+    user.deposit_governance_tag(&mut bench, &tag_record_factory)
+        .await;
+    delegation.delegate(&mut bench, &0).await;
+
+    let delegation_record = delegation.get_delegation_record(&mut bench).await.unwrap();
+    assert!(delegation_record.expires_at.unwrap() > expires_at);
+
+    let (proposal, _recipent_wallet) =
+        TestProposal::new_transfer_proposal(&mut bench, &user, &scope, &governance, 1).await;
+
+    bench.advance_clock_past_timestamp(expires_at).await;
+    proposal
+        .vote_with_delegate(&mut bench, &vec![1], &user, &vote_power_unit, &scope)
+        .await;
+
+    let (_, vote_record) = user
+        .get_vote_record_delegate(&mut bench, &proposal, &vote_power_unit, &scope)
+        .await
+        .unwrap();
+    assert_eq!(vote_record.vote_weight, 1);
+}
+
+#[tokio::test]
+async fn success_expire_delegation_of_suspended_tag() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+
+    let user = TestUser::new();
+    let other_user = TestUser::new();
+
+    let tag_record_factory = TestTagRecordFactory::new(&mut bench).await;
+    let vote_power_unit = VotePowerUnit::Tag {
+        record_factory: tag_record_factory.factory,
+    };
+
+    tag_record_factory.new_record(&mut bench, &user).await;
+    user.deposit_governance_tag(&mut bench, &tag_record_factory)
+        .await;
+    tag_record_factory
+        .new_record_with_quantity(&mut bench, &other_user, 2)
+        .await;
+    other_user
+        .deposit_governance_tag(&mut bench, &tag_record_factory)
+        .await;
+
+    let mut governance = TestGovernance::new(&mut bench).await;
+    governance.with_native_treasury(&mut bench).await;
+
+    let scope = governance
+        .create_scope_system(
+            &mut bench,
+            TestVotePowerSource::TestTagRecordFactory(&tag_record_factory),
+        )
+        .await;
+
+    user.create_delegatee(&mut bench, &vote_power_unit, &scope)
+        .await;
+
+    let delegation = TestDelegation::new(&mut bench, &user, &user, &vote_power_unit, &scope).await;
+    delegation.delegate(&mut bench, &1).await;
+    TestDelegation::new(&mut bench, &other_user, &user, &vote_power_unit, &scope)
+        .await
+        .delegate(&mut bench, &2)
+        .await;
+
+    // The delegation can not be expired while the tag record backs the deposit
+    assert_eq!(
+        delegation.expire(&mut bench).await,
+        Err(GovernanceError::TagDepositStillBacked.into())
+    );
+
+    tag_record_factory.suspend_record(&mut bench, &user).await;
+
+    // The deposit of the suspended record can not be delegated anymore
+    assert_eq!(
+        delegation.try_delegate(&mut bench, &0).await,
+        Err(TagError::TagRecordNotActive.into())
+    );
+
+    // Anyone can leave the delegation of the suspended record out of the vote weight
+    delegation.expire(&mut bench).await.unwrap();
+    let expires_at = delegation
+        .get_delegation_record(&mut bench)
+        .await
+        .unwrap()
+        .expires_at
+        .unwrap();
+
+    let (proposal, _recipent_wallet) =
+        TestProposal::new_transfer_proposal(&mut bench, &user, &scope, &governance, 1).await;
+
+    bench.advance_clock_past_timestamp(expires_at).await;
+    proposal
+        .vote_with_delegate(&mut bench, &vec![1], &user, &vote_power_unit, &scope)
+        .await;
+
+    let (_, vote_record) = user
+        .get_vote_record_delegate(&mut bench, &proposal, &vote_power_unit, &scope)
+        .await
+        .unwrap();
+    assert_eq!(vote_record.vote_weight, 2);
+}
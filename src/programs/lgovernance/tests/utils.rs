@@ -11,6 +11,11 @@ pub fn program_test() -> ProgramTest {
         lchannel::id(),
         processor!(ltag::processor::Processor::process),
     ); */
+    program.add_program(
+        "lsignforme",
+        lsignforme::id(),
+        processor!(lsignforme::processor::Processor::process),
+    );
     program.add_program(
         "ltag",
         ltag::id(),
@@ -18,6 +18,27 @@ pub enum SignForMeError {
 
     #[error("SignForMe record already exist")]
     SignForMeRecordAlreadyExist,
+
+    #[error("Action is not in the scope of the SignForMe record")]
+    InvalidScope,
+
+    #[error("SignForMe record has expired")]
+    SignForMeExpired,
+
+    #[error("SignForMe record has no uses left")]
+    SignForMeExhausted,
+
+    #[error("SignForMe record can still be used")]
+    SignForMeStillUsable,
+
+    #[error("Signers have to be unique and at most MAX_SIGN_FOR_ME_SIGNERS")]
+    InvalidSigners,
+
+    #[error("Threshold has to be at least one and at most the number of signers")]
+    InvalidThreshold,
+
+    #[error("Not enough signers of the SignForMe record signed")]
+    NotEnoughSigners,
 }
 
 impl PrintProgramError for SignForMeError {
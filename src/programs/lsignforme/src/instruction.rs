@@ -1,45 +1,128 @@
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
+    account_info::AccountInfo,
+    clock::UnixTimestamp,
+    entrypoint::ProgramResult,
     instruction::{AccountMeta, Instruction},
+    program::invoke,
     pubkey::Pubkey,
     system_program,
 };
 
-use crate::get_sign_for_me_program_address;
+use crate::{get_sign_for_me_program_address, state::SignForMeScope};
+
+/// Signers acting for an owner through a SignForMe record, for the instructions of consuming programs
+#[derive(Clone, Debug)]
+pub struct SignForMe {
+    pub sign_for_me: Pubkey,
+
+    /// Signers of the record that co-sign, as many as the threshold of the record
+    pub signers: Vec<Pubkey>,
+}
+
+impl SignForMe {
+    /// Accounts the consuming program verifies and passes on to UseSignForMe
+    pub fn add_account_infos(&self, accounts: &mut Vec<AccountMeta>) {
+        accounts.push(AccountMeta::new(self.sign_for_me, false));
+        for signer in &self.signers {
+            accounts.push(AccountMeta::new_readonly(*signer, true));
+        }
+        accounts.push(AccountMeta::new_readonly(crate::id(), false));
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SignerMaybeSignForMe {
+    pub original_signer: Pubkey,
+    pub sign_for_me: Option<SignForMe>,
+}
+
+impl SignerMaybeSignForMe {
+    /// The owner account, a signer unless a SignForMe record is used
+    pub fn get_account_meta(&self) -> AccountMeta {
+        AccountMeta::new_readonly(self.original_signer, self.sign_for_me.is_none())
+    }
+
+    pub fn add_sign_for_me_account_infos(&self, accounts: &mut Vec<AccountMeta>) {
+        if let Some(sign_for_me) = &self.sign_for_me {
+            sign_for_me.add_account_infos(accounts);
+        }
+    }
+
+    pub fn add_account_infos(&self, accounts: &mut Vec<AccountMeta>) {
+        accounts.push(self.get_account_meta());
+        self.add_sign_for_me_account_infos(accounts);
+    }
+}
+
+impl From<&Pubkey> for SignerMaybeSignForMe {
+    fn from(original_signer: &Pubkey) -> Self {
+        Self {
+            original_signer: *original_signer,
+            sign_for_me: None,
+        }
+    }
+}
 
 #[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
 pub enum SignForMeInstruction {
     CreateSignForMe {
         #[allow(dead_code)] // but it's not
-        signer: Pubkey,
+        signers: Vec<Pubkey>,
 
         #[allow(dead_code)] // but it's not
-        scope: Pubkey,
+        threshold: u8,
+
+        #[allow(dead_code)] // but it's not
+        scope: SignForMeScope,
+
+        #[allow(dead_code)] // but it's not
+        expires_at: Option<UnixTimestamp>,
+
+        #[allow(dead_code)] // but it's not
+        max_uses: Option<u64>,
 
         #[allow(dead_code)] // but it's not
         bump_seed: u8,
     },
     DeleteSignForMe,
+
+    /// Counts a use of the record, invoked by the consuming programs when the signer acts for the owner
+    UseSignForMe,
+
+    /// Anyone can close an expired or exhausted record, the rent goes back to the owner
+    CloseSignForMe,
+
+    /// A signer gives up signing for the owner, the threshold is lowered if the remaining signers can not reach it.
+    /// The record is closed and the rent goes back to the owner when the last signer renounces
+    RenounceSignForMe,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_sign_for_me(
     program_id: &Pubkey,
 
     // Accounts
     owner: &Pubkey,
-    signer: &Pubkey,
-    scope: &Pubkey,
+    signers: &[Pubkey],
+    threshold: u8,
+    scope: &SignForMeScope,
     payer: &Pubkey,
     // Args
+    expires_at: Option<UnixTimestamp>,
+    max_uses: Option<u64>,
 ) -> Instruction {
     let (sign_for_me_address, bump_seed) =
-        get_sign_for_me_program_address(program_id, owner, signer, scope);
+        get_sign_for_me_program_address(program_id, owner, signers, threshold, scope);
 
     Instruction {
         program_id: *program_id,
         data: (SignForMeInstruction::CreateSignForMe {
-            scope: *scope,
-            signer: *signer,
+            scope: scope.clone(),
+            signers: signers.to_vec(),
+            threshold,
+            expires_at,
+            max_uses,
             bump_seed,
         })
         .try_to_vec()
@@ -73,3 +156,75 @@ pub fn delete_sign_for_me_as_owner(
         ],
     }
 }
+
+pub fn use_sign_for_me(
+    program_id: &Pubkey,
+    // Accounts
+    sign_for_me: &Pubkey,
+    signers: &[Pubkey],
+    // Args
+) -> Instruction {
+    let mut accounts = vec![AccountMeta::new(*sign_for_me, false)];
+    for signer in signers {
+        accounts.push(AccountMeta::new_readonly(*signer, true));
+    }
+    Instruction {
+        program_id: *program_id,
+        data: (SignForMeInstruction::UseSignForMe).try_to_vec().unwrap(),
+        accounts,
+    }
+}
+
+/// Invokes UseSignForMe from a consuming program, the signatures of the signers are passed on
+pub fn invoke_use_sign_for_me<'a>(
+    sign_for_me_info: &AccountInfo<'a>,
+    signer_infos: &[AccountInfo<'a>],
+    sign_for_me_program_info: &AccountInfo<'a>,
+) -> ProgramResult {
+    let signers: Vec<Pubkey> = signer_infos.iter().map(|info| *info.key).collect();
+    let mut account_infos = vec![sign_for_me_info.clone()];
+    account_infos.extend_from_slice(signer_infos);
+    account_infos.push(sign_for_me_program_info.clone());
+    invoke(
+        &use_sign_for_me(sign_for_me_program_info.key, sign_for_me_info.key, &signers),
+        &account_infos,
+    )
+}
+
+pub fn close_sign_for_me(
+    program_id: &Pubkey,
+    // Accounts
+    sign_for_me: &Pubkey,
+    owner: &Pubkey,
+    // Args
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        data: (SignForMeInstruction::CloseSignForMe).try_to_vec().unwrap(),
+        accounts: vec![
+            AccountMeta::new(*sign_for_me, false),
+            AccountMeta::new(*owner, false),
+        ],
+    }
+}
+
+pub fn renounce_sign_for_me(
+    program_id: &Pubkey,
+    // Accounts
+    sign_for_me: &Pubkey,
+    signer: &Pubkey,
+    owner: &Pubkey,
+    // Args
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        data: (SignForMeInstruction::RenounceSignForMe)
+            .try_to_vec()
+            .unwrap(),
+        accounts: vec![
+            AccountMeta::new(*sign_for_me, false),
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(*owner, false),
+        ],
+    }
+}
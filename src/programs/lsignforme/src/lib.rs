@@ -8,20 +8,22 @@ pub mod state;
 
 solana_program::declare_id!("HGXKsXGRx9qSyrNS6YAEd5FqjGMXLf41syf9jhNkbvwn");
 use solana_program::pubkey::Pubkey;
+use state::{get_signers_seed, SignForMeScope};
 
 const SIGN_FOR_ME_RECORD_SEED: &[u8] = b"s4m";
 pub fn get_sign_for_me_program_address(
     program_id: &Pubkey,
     owner: &Pubkey,
-    signer: &Pubkey,
-    scope: &Pubkey,
+    signers: &[Pubkey],
+    threshold: u8,
+    scope: &SignForMeScope,
 ) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[
             SIGN_FOR_ME_RECORD_SEED,
             owner.as_ref(),
-            signer.as_ref(),
-            scope.as_ref(),
+            &get_signers_seed(signers, threshold),
+            &scope.get_seed(),
         ],
         program_id,
     )
@@ -29,15 +31,15 @@ pub fn get_sign_for_me_program_address(
 
 pub fn get_sign_for_me_program_address_seeds<'a>(
     owner: &'a Pubkey,
-    signer: &'a Pubkey,
-    scope: &'a Pubkey,
+    signers_seed: &'a [u8; 32],
+    scope_seed: &'a [u8; 32],
     bump_seed: &'a [u8; 1],
 ) -> [&'a [u8]; 5] {
     [
         SIGN_FOR_ME_RECORD_SEED,
         owner.as_ref(),
-        signer.as_ref(),
-        scope.as_ref(),
+        signers_seed,
+        scope_seed,
         bump_seed,
     ]
 }
@@ -6,6 +6,7 @@ use shared::{
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     borsh::try_from_slice_unchecked,
+    clock::{Clock, UnixTimestamp},
     entrypoint::ProgramResult,
     msg,
     program_error::ProgramError,
@@ -20,16 +21,23 @@ use crate::{
     error::SignForMeError,
     get_sign_for_me_program_address_seeds,
     instruction::SignForMeInstruction,
-    state::{get_sign_for_me_data_for_signed_owner, AccountType, SignForMeAccount},
+    state::{
+        assert_valid_signers, get_sign_for_me_data_for_signed_owner, get_signers_seed,
+        next_sign_for_me_signer_infos, AccountType, SignForMeAccount, SignForMeScope,
+    },
 };
 
 pub struct Processor {}
 impl Processor {
+    #[allow(clippy::too_many_arguments)]
     pub fn process_create_sign_for_me(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        signer: Pubkey,
-        scope: Pubkey,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+        scope: SignForMeScope,
+        expires_at: Option<UnixTimestamp>,
+        max_uses: Option<u64>,
         bump_seed: u8,
     ) -> ProgramResult {
         let accounts_iter = &mut accounts.iter();
@@ -47,10 +55,18 @@ impl Processor {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
+        assert_valid_signers(&signers, threshold)?;
+
         let rent = Rent::get()?;
         let bump_seeds = [bump_seed];
-        let seeds =
-            get_sign_for_me_program_address_seeds(owner_info.key, &signer, &scope, &bump_seeds);
+        let signers_seed = get_signers_seed(&signers, threshold);
+        let scope_seed = scope.get_seed();
+        let seeds = get_sign_for_me_program_address_seeds(
+            owner_info.key,
+            &signers_seed,
+            &scope_seed,
+            &bump_seeds,
+        );
 
         create_and_serialize_account_verify_with_bump(
             payer_info,
@@ -59,7 +75,11 @@ impl Processor {
                 account_type: AccountType::SignerRecord,
                 owner: *owner_info.key,
                 scope,
-                signer,
+                signers,
+                threshold,
+                expires_at,
+                max_uses,
+                uses: 0,
             },
             &seeds,
             program_id,
@@ -84,21 +104,121 @@ impl Processor {
         Ok(())
     }
 
+    pub fn process_use_sign_for_me(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let sign_for_me_info = next_account_info(accounts_iter)?;
+        let signer_infos =
+            next_sign_for_me_signer_infos(program_id, sign_for_me_info, accounts_iter)?;
+
+        let mut sign_for_me_data =
+            get_account_data::<SignForMeAccount>(program_id, sign_for_me_info)?;
+        sign_for_me_data.assert_signed(&signer_infos)?;
+        sign_for_me_data.assert_usable(Clock::get()?.unix_timestamp)?;
+        sign_for_me_data.uses = sign_for_me_data.uses.checked_add(1).unwrap();
+        sign_for_me_data.serialize(&mut *sign_for_me_info.data.borrow_mut())?;
+        Ok(())
+    }
+
+    pub fn process_close_sign_for_me(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let sign_for_me_info = next_account_info(accounts_iter)?;
+        let owner_info = next_account_info(accounts_iter)?;
+
+        let sign_for_me_data = get_account_data::<SignForMeAccount>(program_id, sign_for_me_info)?;
+        if &sign_for_me_data.owner != owner_info.key {
+            return Err(SignForMeError::InvalidOwner.into());
+        }
+
+        if sign_for_me_data
+            .assert_usable(Clock::get()?.unix_timestamp)
+            .is_ok()
+        {
+            return Err(SignForMeError::SignForMeStillUsable.into());
+        }
+
+        dispose_account(sign_for_me_info, owner_info);
+        Ok(())
+    }
+
+    pub fn process_renounce_sign_for_me(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let sign_for_me_info = next_account_info(accounts_iter)?;
+        let signer_info = next_account_info(accounts_iter)?;
+        let owner_info = next_account_info(accounts_iter)?;
+
+        if !signer_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut sign_for_me_data =
+            get_account_data::<SignForMeAccount>(program_id, sign_for_me_info)?;
+        if !sign_for_me_data.signers.contains(signer_info.key) {
+            return Err(SignForMeError::InvalidSigner.into());
+        }
+        if &sign_for_me_data.owner != owner_info.key {
+            return Err(SignForMeError::InvalidOwner.into());
+        }
+
+        sign_for_me_data
+            .signers
+            .retain(|signer| signer != signer_info.key);
+
+        // The last signer leaves nothing to sign with
+        if sign_for_me_data.signers.is_empty() {
+            dispose_account(sign_for_me_info, owner_info);
+            return Ok(());
+        }
+
+        // The threshold can not exceed the remaining signers.
+        // The address stays the one derived from the signers the record was created with
+        sign_for_me_data.threshold = sign_for_me_data
+            .threshold
+            .min(sign_for_me_data.signers.len() as u8);
+        assert_valid_signers(&sign_for_me_data.signers, sign_for_me_data.threshold)?;
+
+        sign_for_me_data.serialize(&mut *sign_for_me_info.data.borrow_mut())?;
+        Ok(())
+    }
+
     pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
         let instruction = try_from_slice_unchecked::<SignForMeInstruction>(data)?;
         match instruction {
             SignForMeInstruction::CreateSignForMe {
                 bump_seed,
                 scope,
-                signer,
+                signers,
+                threshold,
+                expires_at,
+                max_uses,
             } => {
                 msg!("Instruction: CreateSignForMe");
-                Self::process_create_sign_for_me(program_id, accounts, signer, scope, bump_seed)
+                Self::process_create_sign_for_me(
+                    program_id, accounts, signers, threshold, scope, expires_at, max_uses,
+                    bump_seed,
+                )
             }
             SignForMeInstruction::DeleteSignForMe => {
                 msg!("Instruction: DeleteSignForMe");
                 Self::process_delete_sign_for_me_as_owner(program_id, accounts)
             }
+            SignForMeInstruction::UseSignForMe => {
+                msg!("Instruction: UseSignForMe");
+                Self::process_use_sign_for_me(program_id, accounts)
+            }
+            SignForMeInstruction::CloseSignForMe => {
+                msg!("Instruction: CloseSignForMe");
+                Self::process_close_sign_for_me(program_id, accounts)
+            }
+            SignForMeInstruction::RenounceSignForMe => {
+                msg!("Instruction: RenounceSignForMe");
+                Self::process_renounce_sign_for_me(program_id, accounts)
+            }
         }
     }
 }
@@ -3,12 +3,20 @@ use shared::{
     account::{get_account_data, MaxSize},
     content::ContentSource,
 };
+use std::slice::Iter;
+
 use solana_program::{
-    account_info::AccountInfo, program_error::ProgramError, program_pack::IsInitialized,
+    account_info::{next_account_info, AccountInfo},
+    clock::{Clock, UnixTimestamp},
+    entrypoint::ProgramResult,
+    hash::hashv,
+    program_error::ProgramError,
+    program_pack::IsInitialized,
     pubkey::Pubkey,
+    sysvar::Sysvar,
 };
 
-use crate::error::SignForMeError;
+use crate::{error::SignForMeError, instruction::invoke_use_sign_for_me};
 #[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
 pub enum AccountType {
     SignerRecord,
@@ -17,8 +25,115 @@ pub enum AccountType {
 pub struct SignForMeAccount {
     pub account_type: AccountType,
     pub owner: Pubkey,
-    pub signer: Pubkey,
-    pub scope: Pubkey,
+
+    /// Keys that can sign for the owner
+    pub signers: Vec<Pubkey>,
+
+    /// Number of the signers that have to co-sign, 1 for a single signer
+    pub threshold: u8,
+    pub scope: SignForMeScope,
+
+    /// The signer can not act for the owner from this time, e.g. for session keys
+    pub expires_at: Option<UnixTimestamp>,
+
+    /// Times the signer can act for the owner, counted by the consuming programs with UseSignForMe
+    pub max_uses: Option<u64>,
+    pub uses: u64,
+}
+
+impl SignForMeAccount {
+    pub fn assert_usable(&self, current_unix_timestamp: UnixTimestamp) -> ProgramResult {
+        if let Some(expires_at) = self.expires_at {
+            if current_unix_timestamp >= expires_at {
+                return Err(SignForMeError::SignForMeExpired.into());
+            }
+        }
+        if let Some(max_uses) = self.max_uses {
+            if self.uses >= max_uses {
+                return Err(SignForMeError::SignForMeExhausted.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Asserts enough of the listed signers signed, a signer counts once
+    pub fn assert_signed(&self, signer_infos: &[AccountInfo]) -> ProgramResult {
+        let mut signed: Vec<&Pubkey> = Vec::new();
+        for signer_info in signer_infos {
+            if !signer_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if !self.signers.contains(signer_info.key) {
+                return Err(SignForMeError::InvalidSigner.into());
+            }
+            if !signed.contains(&signer_info.key) {
+                signed.push(signer_info.key);
+            }
+        }
+        if signed.len() < self.threshold as usize {
+            return Err(SignForMeError::NotEnoughSigners.into());
+        }
+        Ok(())
+    }
+}
+
+/// Max number of signers of a record, e.g. for a 2-of-3 shared team account
+pub const MAX_SIGN_FOR_ME_SIGNERS: usize = 10;
+
+pub fn assert_valid_signers(signers: &[Pubkey], threshold: u8) -> ProgramResult {
+    if signers.len() > MAX_SIGN_FOR_ME_SIGNERS
+        || signers
+            .iter()
+            .enumerate()
+            .any(|(i, signer)| signers[..i].contains(signer))
+    {
+        return Err(SignForMeError::InvalidSigners.into());
+    }
+    if threshold == 0 || threshold as usize > signers.len() {
+        return Err(SignForMeError::InvalidThreshold.into());
+    }
+    Ok(())
+}
+
+/// Seed of the SignForMe address for the signers and threshold
+pub fn get_signers_seed(signers: &[Pubkey], threshold: u8) -> [u8; 32] {
+    let mut seeds: Vec<&[u8]> = signers.iter().map(|signer| signer.as_ref()).collect();
+    let threshold = [threshold];
+    seeds.push(&threshold);
+    hashv(&seeds).to_bytes()
+}
+
+/// All actions of the program
+pub const ALL_ACTIONS: u64 = u64::MAX;
+
+/// What the signer can do for the owner
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
+pub struct SignForMeScope {
+    /// Program the signer can act in
+    pub program: Pubkey,
+
+    /// Resource of the program, e.g. a channel, any resource if None
+    pub resource: Option<Pubkey>,
+
+    /// Mask of the actions the signer can do, the bits are defined by the program
+    pub actions: u64,
+}
+
+impl SignForMeScope {
+    /// Seed of the SignForMe address, so the owner can delegate different scopes to the same signer
+    pub fn get_seed(&self) -> [u8; 32] {
+        hashv(&[&self.try_to_vec().unwrap()]).to_bytes()
+    }
+
+    pub fn allows(&self, program: &Pubkey, resource: &Pubkey, action: u64) -> bool {
+        &self.program == program
+            && self
+                .resource
+                .as_ref()
+                .map(|scope_resource| scope_resource == resource)
+                .unwrap_or(true)
+            && self.actions & action == action
+    }
 }
 
 impl MaxSize for SignForMeAccount {
@@ -33,27 +148,34 @@ impl IsInitialized for SignForMeAccount {
     }
 }
 
-pub fn get_sign_for_me_data_for_owner_and_signer<'a>(
+pub fn get_sign_for_me_data_for_owner_and_signers<'a>(
     program_id: &Pubkey,
     signer_for_me_info: &AccountInfo<'a>,
     owner: &Pubkey,
-    signer: &AccountInfo<'a>,
+    signers: &[AccountInfo<'a>],
 ) -> Result<SignForMeAccount, ProgramError> {
-    if !signer.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
     let data = get_account_data::<SignForMeAccount>(program_id, signer_for_me_info)?;
 
     if &data.owner != owner {
         return Err(SignForMeError::InvalidOwner.into());
     }
 
-    if &data.signer != signer.key {
-        return Err(SignForMeError::InvalidSigner.into());
-    }
+    data.assert_signed(signers)?;
     Ok(data)
 }
 
+/// Takes as many signer accounts as the threshold of the record from the iterator
+pub fn next_sign_for_me_signer_infos<'a>(
+    program_id: &Pubkey,
+    signer_for_me_info: &AccountInfo<'a>,
+    accounts_iter: &mut Iter<AccountInfo<'a>>,
+) -> Result<Vec<AccountInfo<'a>>, ProgramError> {
+    let data = get_account_data::<SignForMeAccount>(program_id, signer_for_me_info)?;
+    (0..data.threshold)
+        .map(|_| next_account_info(accounts_iter).map(Clone::clone))
+        .collect()
+}
+
 pub fn get_sign_for_me_data_for_signed_owner<'a>(
     program_id: &Pubkey,
     signer_for_me_info: &AccountInfo<'a>,
@@ -69,3 +191,56 @@ pub fn get_sign_for_me_data_for_signed_owner<'a>(
 
     Ok(data)
 }
+
+/// Verifies the signers can do the action on the resource of the program for the owner
+pub fn get_sign_for_me_data_for_scope<'a>(
+    program_id: &Pubkey,
+    signer_for_me_info: &AccountInfo<'a>,
+    owner: &Pubkey,
+    signers: &[AccountInfo<'a>],
+    program: &Pubkey,
+    resource: &Pubkey,
+    action: u64,
+) -> Result<SignForMeAccount, ProgramError> {
+    let data =
+        get_sign_for_me_data_for_owner_and_signers(program_id, signer_for_me_info, owner, signers)?;
+    if !data.scope.allows(program, resource, action) {
+        return Err(SignForMeError::InvalidScope.into());
+    }
+    data.assert_usable(Clock::get()?.unix_timestamp)?;
+    Ok(data)
+}
+
+/// Verifies the owner signed, or signers of a SignForMe record of the owner can do the action on the resource of the program.
+/// For the latter the record, its signers and this program are consumed from the iterator and the use is counted
+pub fn verify_signed_owner_maybe_sign_for_me<'a>(
+    owner_info: &AccountInfo<'a>,
+    program: &Pubkey,
+    resource: &Pubkey,
+    action: u64,
+    accounts_iter: &mut Iter<AccountInfo<'a>>,
+) -> ProgramResult {
+    if owner_info.is_signer {
+        return Ok(());
+    }
+
+    let sign_for_me_info = next_account_info(accounts_iter)?;
+    let signer_infos =
+        next_sign_for_me_signer_infos(&crate::id(), sign_for_me_info, accounts_iter)?;
+    get_sign_for_me_data_for_scope(
+        &crate::id(),
+        sign_for_me_info,
+        owner_info.key,
+        &signer_infos,
+        program,
+        resource,
+        action,
+    )?;
+
+    // Count the use, for records with limited uses
+    let sign_for_me_program_info = next_account_info(accounts_iter)?;
+    if sign_for_me_program_info.key != &crate::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    invoke_use_sign_for_me(sign_for_me_info, &signer_infos, sign_for_me_program_info)
+}
@@ -1,20 +1,33 @@
+use lsignforme::error::SignForMeError;
 use lsignforme::get_sign_for_me_program_address;
-use lsignforme::state::SignForMeAccount;
+use lsignforme::state::{SignForMeAccount, SignForMeScope, ALL_ACTIONS};
 use solana_program::borsh::try_from_slice_unchecked;
+use solana_program::clock::UnixTimestamp;
 use solana_program::hash::Hash;
+use solana_program::instruction::InstructionError;
+use solana_program::program_error::ProgramError;
 use solana_program_test::*;
 use solana_sdk::signature::Keypair;
-use solana_sdk::{pubkey::Pubkey, signer::Signer, transaction::Transaction};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
 
 use crate::utils::program_test;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn create_sign_for_me(
     banks_client: &mut BanksClient,
     payer: &Keypair,
     recent_blockhash: &Hash,
     owner: &Keypair,
-    signer: &Pubkey,
-    scope: &Pubkey,
+    signers: &[Pubkey],
+    threshold: u8,
+    scope: &SignForMeScope,
+    expires_at: Option<UnixTimestamp>,
+    max_uses: Option<u64>,
 ) -> Pubkey {
     // Create tag record
     banks_client
@@ -22,9 +35,12 @@ pub async fn create_sign_for_me(
             &[lsignforme::instruction::create_sign_for_me(
                 &lsignforme::id(),
                 &owner.pubkey(),
-                signer,
+                signers,
+                threshold,
                 scope,
                 &payer.pubkey(),
+                expires_at,
+                max_uses,
             )],
             Some(&payer.pubkey()),
             &[payer, owner],
@@ -33,8 +49,14 @@ pub async fn create_sign_for_me(
         .await
         .unwrap();
 
-    let sign_for_me_address =
-        get_sign_for_me_program_address(&lsignforme::id(), &owner.pubkey(), signer, scope).0;
+    let sign_for_me_address = get_sign_for_me_program_address(
+        &lsignforme::id(),
+        &owner.pubkey(),
+        signers,
+        threshold,
+        scope,
+    )
+    .0;
     let sign_for_me_address_account_info = banks_client
         .get_account(sign_for_me_address)
         .await
@@ -44,8 +66,12 @@ pub async fn create_sign_for_me(
         try_from_slice_unchecked::<SignForMeAccount>(&sign_for_me_address_account_info.data)
             .unwrap();
     assert_eq!(&sign_for_me_account.owner, &owner.pubkey());
-    assert_eq!(&sign_for_me_account.signer, signer);
+    assert_eq!(&sign_for_me_account.signers, signers);
+    assert_eq!(sign_for_me_account.threshold, threshold);
     assert_eq!(&sign_for_me_account.scope, scope);
+    assert_eq!(sign_for_me_account.expires_at, expires_at);
+    assert_eq!(sign_for_me_account.max_uses, max_uses);
+    assert_eq!(sign_for_me_account.uses, 0);
     sign_for_me_address
 }
 
@@ -99,15 +125,22 @@ async fn success() {
 
     let owner = Keypair::new();
     let signer = Pubkey::new_unique();
-    let scope = Pubkey::new_unique();
+    let scope = SignForMeScope {
+        program: Pubkey::new_unique(),
+        resource: None,
+        actions: ALL_ACTIONS,
+    };
 
     let sign_for_me = create_sign_for_me(
         &mut banks_client,
         &payer,
         &recent_blockhash,
         &owner,
-        &signer,
+        &[signer],
+        1,
         &scope,
+        None,
+        None,
     )
     .await;
 
@@ -121,4 +154,410 @@ async fn success() {
     )
     .await;
 }
-// TODO add negative tests
+
+pub async fn use_sign_for_me(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    sign_for_me: &Pubkey,
+    signers: &[&Keypair],
+) -> Result<(), TransportError> {
+    let signer_keys: Vec<Pubkey> = signers.iter().map(|signer| signer.pubkey()).collect();
+    let mut transaction = Transaction::new_with_payer(
+        &[lsignforme::instruction::use_sign_for_me(
+            &lsignforme::id(),
+            sign_for_me,
+            &signer_keys,
+        )],
+        Some(&payer.pubkey()),
+    );
+    transaction.partial_sign(&[payer], *recent_blockhash);
+    transaction.partial_sign(signers, *recent_blockhash);
+    banks_client.process_transaction(transaction).await
+}
+
+pub async fn close_sign_for_me(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    sign_for_me: &Pubkey,
+    owner: &Pubkey,
+) -> Result<(), TransportError> {
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[lsignforme::instruction::close_sign_for_me(
+                &lsignforme::id(),
+                sign_for_me,
+                owner,
+            )],
+            Some(&payer.pubkey()),
+            &[payer],
+            *recent_blockhash,
+        ))
+        .await
+}
+
+fn assert_sign_for_me_error(result: Result<(), TransportError>, error: SignForMeError) {
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::from(u64::from(ProgramError::Custom(error as u32)))
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_close_exhausted() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+
+    let owner = Keypair::new();
+    let signer = Keypair::new();
+    let scope = SignForMeScope {
+        program: Pubkey::new_unique(),
+        resource: None,
+        actions: ALL_ACTIONS,
+    };
+
+    let sign_for_me = create_sign_for_me(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &owner,
+        &[signer.pubkey()],
+        1,
+        &scope,
+        None,
+        Some(1),
+    )
+    .await;
+
+    // Can not close while usable
+    assert_sign_for_me_error(
+        close_sign_for_me(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &sign_for_me,
+            &owner.pubkey(),
+        )
+        .await,
+        SignForMeError::SignForMeStillUsable,
+    );
+
+    use_sign_for_me(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &sign_for_me,
+        &[&signer],
+    )
+    .await
+    .unwrap();
+
+    let sign_for_me_account = try_from_slice_unchecked::<SignForMeAccount>(
+        &banks_client
+            .get_account(sign_for_me)
+            .await
+            .unwrap()
+            .unwrap()
+            .data,
+    )
+    .unwrap();
+    assert_eq!(sign_for_me_account.uses, 1);
+
+    // No uses left, the payer signs too so the transaction differs from the first use
+    let result = banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[
+                lsignforme::instruction::use_sign_for_me(
+                    &lsignforme::id(),
+                    &sign_for_me,
+                    &[signer.pubkey()],
+                ),
+                solana_sdk::system_instruction::transfer(&payer.pubkey(), &owner.pubkey(), 1),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &signer],
+            recent_blockhash,
+        ))
+        .await;
+    assert_sign_for_me_error(result, SignForMeError::SignForMeExhausted);
+
+    // Anyone can close it, the rent goes to the owner
+    let balance_pre = banks_client.get_balance(owner.pubkey()).await.unwrap();
+    close_sign_for_me(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &sign_for_me,
+        &owner.pubkey(),
+    )
+    .await
+    .unwrap();
+    assert!(banks_client
+        .get_account(sign_for_me)
+        .await
+        .unwrap()
+        .is_none());
+    let balance_post = banks_client.get_balance(owner.pubkey()).await.unwrap();
+    assert!(balance_pre < balance_post);
+}
+
+#[tokio::test]
+async fn success_close_expired() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+
+    let owner = Keypair::new();
+    let signer = Keypair::new();
+    let scope = SignForMeScope {
+        program: Pubkey::new_unique(),
+        resource: None,
+        actions: ALL_ACTIONS,
+    };
+
+    let sign_for_me = create_sign_for_me(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &owner,
+        &[signer.pubkey()],
+        1,
+        &scope,
+        Some(0),
+        None,
+    )
+    .await;
+
+    assert_sign_for_me_error(
+        use_sign_for_me(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &sign_for_me,
+            &[&signer],
+        )
+        .await,
+        SignForMeError::SignForMeExpired,
+    );
+
+    close_sign_for_me(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &sign_for_me,
+        &owner.pubkey(),
+    )
+    .await
+    .unwrap();
+    assert!(banks_client
+        .get_account(sign_for_me)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+pub async fn renounce_sign_for_me(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    sign_for_me: &Pubkey,
+    signer: &Keypair,
+    owner: &Pubkey,
+) -> Result<(), TransportError> {
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[lsignforme::instruction::renounce_sign_for_me(
+                &lsignforme::id(),
+                sign_for_me,
+                &signer.pubkey(),
+                owner,
+            )],
+            Some(&payer.pubkey()),
+            &[payer, signer],
+            *recent_blockhash,
+        ))
+        .await
+}
+
+#[tokio::test]
+async fn success_renounce() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+
+    let owner = Keypair::new();
+    let signer = Keypair::new();
+    let scope = SignForMeScope {
+        program: Pubkey::new_unique(),
+        resource: None,
+        actions: ALL_ACTIONS,
+    };
+
+    let sign_for_me = create_sign_for_me(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &owner,
+        &[signer.pubkey()],
+        1,
+        &scope,
+        None,
+        None,
+    )
+    .await;
+
+    // The signer gives up the delegation, the rent goes to the owner
+    let balance_pre = banks_client.get_balance(owner.pubkey()).await.unwrap();
+    renounce_sign_for_me(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &sign_for_me,
+        &signer,
+        &owner.pubkey(),
+    )
+    .await
+    .unwrap();
+    assert!(banks_client
+        .get_account(sign_for_me)
+        .await
+        .unwrap()
+        .is_none());
+    let balance_post = banks_client.get_balance(owner.pubkey()).await.unwrap();
+    assert!(balance_pre < balance_post);
+}
+
+#[tokio::test]
+async fn success_threshold() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+
+    let owner = Keypair::new();
+    let signers = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let signer_keys: Vec<Pubkey> = signers.iter().map(|signer| signer.pubkey()).collect();
+    let scope = SignForMeScope {
+        program: Pubkey::new_unique(),
+        resource: None,
+        actions: ALL_ACTIONS,
+    };
+
+    let sign_for_me = create_sign_for_me(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &owner,
+        &signer_keys,
+        2,
+        &scope,
+        None,
+        None,
+    )
+    .await;
+
+    // One signer is not enough for 2-of-3, signing twice does not count
+    assert_sign_for_me_error(
+        use_sign_for_me(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &sign_for_me,
+            &[&signers[0], &signers[0]],
+        )
+        .await,
+        SignForMeError::NotEnoughSigners,
+    );
+
+    use_sign_for_me(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &sign_for_me,
+        &[&signers[0], &signers[2]],
+    )
+    .await
+    .unwrap();
+
+    // Two signers are left, enough for the threshold
+    renounce_sign_for_me(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &sign_for_me,
+        &signers[0],
+        &owner.pubkey(),
+    )
+    .await
+    .unwrap();
+    let sign_for_me_account = try_from_slice_unchecked::<SignForMeAccount>(
+        &banks_client
+            .get_account(sign_for_me)
+            .await
+            .unwrap()
+            .unwrap()
+            .data,
+    )
+    .unwrap();
+    assert_eq!(sign_for_me_account.signers, signer_keys[1..].to_vec());
+    assert_eq!(sign_for_me_account.threshold, 2);
+
+    // The renounced signer can not co-sign anymore
+    assert_sign_for_me_error(
+        use_sign_for_me(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &sign_for_me,
+            &[&signers[0], &signers[1]],
+        )
+        .await,
+        SignForMeError::InvalidSigner,
+    );
+
+    // One signer is left, the threshold is lowered
+    renounce_sign_for_me(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &sign_for_me,
+        &signers[1],
+        &owner.pubkey(),
+    )
+    .await
+    .unwrap();
+    let sign_for_me_account = try_from_slice_unchecked::<SignForMeAccount>(
+        &banks_client
+            .get_account(sign_for_me)
+            .await
+            .unwrap()
+            .unwrap()
+            .data,
+    )
+    .unwrap();
+    assert_eq!(sign_for_me_account.signers, signer_keys[2..].to_vec());
+    assert_eq!(sign_for_me_account.threshold, 1);
+
+    use_sign_for_me(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &sign_for_me,
+        &[&signers[2]],
+    )
+    .await
+    .unwrap();
+
+    // The last signer closes the record
+    renounce_sign_for_me(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &sign_for_me,
+        &signers[2],
+        &owner.pubkey(),
+    )
+    .await
+    .unwrap();
+    assert!(banks_client
+        .get_account(sign_for_me)
+        .await
+        .unwrap()
+        .is_none());
+}
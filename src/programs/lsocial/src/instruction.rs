@@ -1,4 +1,6 @@
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+pub use lsignforme::instruction::{SignForMe, SignerMaybeSignForMe};
+use ltag::condition::TagCondition;
 use shared::content::ContentSource;
 use solana_program::{
     instruction::{AccountMeta, Instruction},
@@ -18,29 +20,6 @@ pub enum CreateVoteConfig {
     Simple,
 }
 
-#[derive(Clone, Debug)]
-pub struct SignForMe {
-    pub sign_for_me: Pubkey,
-    pub signer: Pubkey,
-}
-
-#[derive(Clone, Debug)]
-pub struct SignerMaybeSignForMe {
-    pub original_signer: Pubkey,
-    pub sign_for_me: Option<SignForMe>,
-}
-impl SignerMaybeSignForMe {
-    pub fn add_account_infos(&self, accounts: &mut Vec<AccountMeta>) {
-        accounts.push(AccountMeta::new_readonly(
-            self.original_signer,
-            self.sign_for_me.is_none(),
-        ));
-        if let Some(sign_for_me) = &self.sign_for_me {
-            accounts.push(AccountMeta::new_readonly(sign_for_me.sign_for_me, false));
-            accounts.push(AccountMeta::new_readonly(sign_for_me.signer, true));
-        }
-    }
-}
 pub enum SignedAuthorityCondition {
     Pubkey(SignerMaybeSignForMe),
     Tag {
@@ -49,6 +28,10 @@ pub enum SignedAuthorityCondition {
         record_factory: Pubkey,
     },
     None,
+    Tags {
+        owner: SignerMaybeSignForMe,
+        condition: TagCondition,
+    },
 }
 
 pub struct SignedAuthority {
@@ -76,6 +59,13 @@ impl SignedAuthority {
                 key.add_account_infos(accounts);
             }
             SignedAuthorityCondition::None => {}
+            SignedAuthorityCondition::Tags { owner, condition } => {
+                owner.add_account_infos(accounts);
+                for address in condition.get_account_addresses(&ltag::id(), &owner.original_signer)
+                {
+                    accounts.push(AccountMeta::new_readonly(address, false));
+                }
+            }
         }
     }
 }
@@ -90,6 +80,7 @@ impl From<SignedAuthority> for AuthorityCondition {
             SignedAuthorityCondition::Tag { record_factory, .. } => {
                 AuthorityCondition::Tag { record_factory }
             }
+            SignedAuthorityCondition::Tags { condition, .. } => AuthorityCondition::Tags(condition),
         }
     }
 }
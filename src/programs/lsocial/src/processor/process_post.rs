@@ -41,7 +41,12 @@ pub fn process_create_post(
     let channel_info = next_account_info(accounts_iter)?;
     let owner_info = next_account_info(accounts_iter)?;
 
-    match verify_signed_owner_maybe_sign_for_me(owner_info, accounts_iter) {
+    match verify_signed_owner_maybe_sign_for_me(
+        owner_info,
+        channel_info.key,
+        &AuthorityType::CreatePost,
+        accounts_iter,
+    ) {
         Ok(()) => {}
         Err(_err) => {
             return Err(SocialError::TestError.into());
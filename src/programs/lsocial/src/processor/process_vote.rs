@@ -43,7 +43,12 @@ pub fn process_post_vote(
     let channel_info = next_account_info(accounts_iter)?;
     let vote_record_info = next_account_info(accounts_iter)?;
     let vote_record_owner_info = next_account_info(accounts_iter)?;
-    verify_signed_owner_maybe_sign_for_me(vote_record_owner_info, accounts_iter)?;
+    verify_signed_owner_maybe_sign_for_me(
+        vote_record_owner_info,
+        channel_info.key,
+        &AuthorityType::Vote,
+        accounts_iter,
+    )?;
     let payer_info = next_account_info(accounts_iter)?;
     let system_info = next_account_info(accounts_iter)?;
 
@@ -111,7 +116,12 @@ pub fn process_post_unvote(program_id: &Pubkey, accounts: &[AccountInfo]) -> Pro
     let channel_info = next_account_info(accounts_iter)?;
     let vote_record_info = next_account_info(accounts_iter)?;
     let vote_record_owner_info = next_account_info(accounts_iter)?;
-    verify_signed_owner_maybe_sign_for_me(vote_record_owner_info, accounts_iter)?;
+    verify_signed_owner_maybe_sign_for_me(
+        vote_record_owner_info,
+        channel_info.key,
+        &AuthorityType::Vote,
+        accounts_iter,
+    )?;
     let destination_info = next_account_info(accounts_iter)?;
 
     let authority_info = next_account_info(accounts_iter)?;
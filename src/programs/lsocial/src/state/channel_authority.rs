@@ -1,14 +1,19 @@
 use std::slice::Iter;
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
-use ltag::state::{get_tag_record_data_with_factory_and_signed_owner, TagRecordAccount};
+use ltag::{
+    condition::{assert_tag_condition, TagCondition},
+    state::{get_tag_record_data_with_factory_and_signed_owner, TagRecordAccount},
+};
 use shared::account::{get_account_data, MaxSize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     msg,
     program_error::ProgramError,
     program_pack::IsInitialized,
     pubkey::Pubkey,
+    sysvar::Sysvar,
 };
 
 use crate::{
@@ -32,6 +37,13 @@ pub enum AuthorityType {
     RemoveChannel,
 }
 
+impl AuthorityType {
+    /// Action bit of the authority type in SignForMe scopes
+    pub fn action(&self) -> u64 {
+        1 << (self.clone() as u64)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub enum AuthorityCondition {
     Pubkey(Pubkey),
@@ -40,6 +52,9 @@ pub enum AuthorityCondition {
         record_factory: Pubkey,
     },
     None,
+
+    /// Owner satisfying a condition over tag records, e.g. "core team AND NOT suspended"
+    Tags(TagCondition),
 }
 
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
@@ -75,29 +90,47 @@ pub fn check_activity_authority<'a>(
         get_channel_authority_data_for_channel(program_id, authority_info, channel_info.key)?;
 
     // Check if user can use authority
-
-    /// TODO limit capability of sign for me with scope
-    ///
     match &authority_data.condition {
         AuthorityCondition::Tag { record_factory } => {
             let tag_record_info = next_account_info(accounts_iter)?;
             let tag_owner_info = next_account_info(accounts_iter)?;
             let data = get_account_data::<TagRecordAccount>(&ltag::id(), tag_record_info)?;
 
-            if &data.factory != record_factory && &data.owner != tag_owner_info.key {
+            if &data.factory != record_factory || &data.owner != tag_owner_info.key {
                 return Err(SocialError::InvalidAuthority.into());
             }
-
-            verify_signed_owner_maybe_sign_for_me(tag_owner_info, accounts_iter)?;
+            data.assert_valid(Clock::get()?.unix_timestamp)?;
+
+            verify_signed_owner_maybe_sign_for_me(
+                tag_owner_info,
+                channel_info.key,
+                required_authority_type,
+                accounts_iter,
+            )?;
         }
         AuthorityCondition::Pubkey(pubkey) => {
             let signer = next_account_info(accounts_iter)?;
             if signer.key != pubkey {
                 return Err(SocialError::InvalidAuthority.into());
             }
-            verify_signed_owner_maybe_sign_for_me(signer, accounts_iter)?;
+            verify_signed_owner_maybe_sign_for_me(
+                signer,
+                channel_info.key,
+                required_authority_type,
+                accounts_iter,
+            )?;
         }
         AuthorityCondition::None => {}
+        AuthorityCondition::Tags(condition) => {
+            let tag_owner_info = next_account_info(accounts_iter)?;
+            verify_signed_owner_maybe_sign_for_me(
+                tag_owner_info,
+                channel_info.key,
+                required_authority_type,
+                accounts_iter,
+            )?;
+            assert_tag_condition(&ltag::id(), condition, tag_owner_info.key, accounts_iter)?;
+        }
     }
 
     // Check if required authority type exist in the set
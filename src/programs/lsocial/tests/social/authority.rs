@@ -1,3 +1,5 @@
+use lsocial::error::SocialError;
+use lsocial::instruction::SignedAuthorityCondition;
 use lsocial::state::{
     channel::ChannelType,
     channel_authority::{AuthorityCondition, AuthorityType},
@@ -5,12 +7,17 @@ use lsocial::state::{
     vote_record::Vote,
 };
 
+use ltag::{condition::TagCondition, error::TagError};
+use solana_program::program_error::ProgramError;
 use solana_program_test::*;
+use solana_sdk::signer::Signer;
 
 use crate::bench::ProgramTestBench;
 use crate::utils::program_test;
 
-use super::utils::{TestAuthority, TestChannel, TestPost, TestTagRecordFactory, TestUser};
+use super::utils::{
+    get_tag_record_address, TestAuthority, TestChannel, TestPost, TestTagRecordFactory, TestUser,
+};
 /*
 #[tokio::test]
 async fn success_update_authority() {
@@ -408,4 +415,279 @@ pub async fn success_authority_by_tag() {
     new_authourity.delete(&mut bench, &admin_signer).await;
 }
 
+#[tokio::test]
+pub async fn fail_authority_by_tag_wrong_record() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+    let user = TestUser::new();
+
+    let tag_record_factory = TestTagRecordFactory::new(&mut bench).await;
+    let other_tag_record_factory = TestTagRecordFactory::new(&mut bench).await;
+
+    let (test_collection, collection_authority) = TestChannel::new(
+        &mut bench,
+        &user,
+        None,
+        &ChannelType::Collection,
+        None,
+        None,
+    )
+    .await;
+
+    let signed_owner = (&user).into();
+    let create_channel_authority = collection_authority
+        .get_signing_authority(&mut bench, &signed_owner)
+        .await;
+
+    let (test_channel, authority) = TestChannel::new(
+        &mut bench,
+        &user,
+        None,
+        &ChannelType::PostStream,
+        Some(&test_collection),
+        Some(&create_channel_authority),
+    )
+    .await;
+
+    let admin_signer = authority
+        .get_signing_authority(&mut bench, &signed_owner)
+        .await;
+
+    let tag_authority = TestAuthority::new(
+        &mut bench,
+        &test_channel,
+        &vec![AuthorityType::CreatePost, AuthorityType::Vote],
+        &AuthorityCondition::Tag {
+            record_factory: tag_record_factory.factory,
+        },
+        &admin_signer,
+    )
+    .await;
+
+    let member = TestUser::new();
+    tag_record_factory.new_record(&mut bench, &member).await;
+    let member_signer = (&member).into();
+    let member_authority_signer = tag_authority
+        .get_signing_authority(&mut bench, &member_signer)
+        .await;
+
+    let post = TestPost::new(
+        &mut bench,
+        &test_channel,
+        &member_signer,
+        &PostContent::String("a".into()),
+        None,
+        &member_authority_signer,
+    )
+    .await;
+
+    // The record of the factory is owned by someone else
+    let outsider = TestUser::new();
+    let outsider_signer = (&outsider).into();
+    let mut outsider_authority_signer = tag_authority
+        .get_signing_authority(&mut bench, &outsider_signer)
+        .await;
+    if let SignedAuthorityCondition::Tag { record, .. } =
+        &mut outsider_authority_signer.authority.condition
+    {
+        *record = get_tag_record_address(&member.keypair.pubkey(), &tag_record_factory.factory);
+    }
+    assert_eq!(
+        post.vote(
+            &mut bench,
+            Vote::Up,
+            &outsider_signer,
+            &outsider_authority_signer,
+        )
+        .await,
+        Err(ProgramError::Custom(SocialError::InvalidAuthority as u32))
+    );
+
+    // The record is owned by the signer, but of another factory
+    other_tag_record_factory
+        .new_record(&mut bench, &outsider)
+        .await;
+    let mut other_record_authority_signer = tag_authority
+        .get_signing_authority(&mut bench, &outsider_signer)
+        .await;
+    if let SignedAuthorityCondition::Tag { record, .. } =
+        &mut other_record_authority_signer.authority.condition
+    {
+        *record = get_tag_record_address(
+            &outsider.keypair.pubkey(),
+            &other_tag_record_factory.factory,
+        );
+    }
+    assert_eq!(
+        post.vote(
+            &mut bench,
+            Vote::Up,
+            &outsider_signer,
+            &other_record_authority_signer,
+        )
+        .await,
+        Err(ProgramError::Custom(SocialError::InvalidAuthority as u32))
+    );
+}
+
+#[tokio::test]
+pub async fn success_authority_by_tag_condition() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+    let user = TestUser::new();
+
+    let core_team = TestTagRecordFactory::new(&mut bench).await;
+    let suspended = TestTagRecordFactory::new(&mut bench).await;
+
+    let (test_channel, collection_authority) = TestChannel::new(
+        &mut bench,
+        &user,
+        None,
+        &ChannelType::Collection,
+        None,
+        None,
+    )
+    .await;
+
+    let signed_owner = (&user).into();
+    let create_channel_authority = collection_authority
+        .get_signing_authority(&mut bench, &signed_owner)
+        .await;
+
+    let (test_channel, authority) = TestChannel::new(
+        &mut bench,
+        &user,
+        None,
+        &ChannelType::PostStream,
+        Some(&test_channel),
+        Some(&create_channel_authority),
+    )
+    .await;
+
+    let admin_signer = authority
+        .get_signing_authority(&mut bench, &signed_owner)
+        .await;
+
+    let post = TestPost::new(
+        &mut bench,
+        &test_channel,
+        &signed_owner,
+        &PostContent::String("a".into()),
+        None,
+        &admin_signer,
+    )
+    .await;
+
+    // Moderators are "core team AND NOT suspended"
+    let moderator_authority = TestAuthority::new(
+        &mut bench,
+        &test_channel,
+        &vec![AuthorityType::Vote],
+        &AuthorityCondition::Tags(TagCondition {
+            all_of: vec![core_team.factory],
+            any_of: vec![],
+            none_of: vec![suspended.factory],
+        }),
+        &admin_signer,
+    )
+    .await;
+
+    let moderator = TestUser::new();
+    core_team.new_record(&mut bench, &moderator).await;
+
+    let suspended_moderator = TestUser::new();
+    core_team.new_record(&mut bench, &suspended_moderator).await;
+    suspended.new_record(&mut bench, &suspended_moderator).await;
+
+    let outsider = TestUser::new();
+
+    let moderator_signer = (&moderator).into();
+    let moderator_authority_signer = moderator_authority
+        .get_signing_authority(&mut bench, &moderator_signer)
+        .await;
+    post.vote(
+        &mut bench,
+        Vote::Up,
+        &moderator_signer,
+        &moderator_authority_signer,
+    )
+    .await
+    .unwrap();
+
+    let suspended_moderator_signer = (&suspended_moderator).into();
+    let suspended_moderator_authority_signer = moderator_authority
+        .get_signing_authority(&mut bench, &suspended_moderator_signer)
+        .await;
+    assert!(post
+        .vote(
+            &mut bench,
+            Vote::Up,
+            &suspended_moderator_signer,
+            &suspended_moderator_authority_signer,
+        )
+        .await
+        .is_err());
+
+    let outsider_signer = (&outsider).into();
+    let outsider_authority_signer = moderator_authority
+        .get_signing_authority(&mut bench, &outsider_signer)
+        .await;
+    assert!(post
+        .vote(
+            &mut bench,
+            Vote::Up,
+            &outsider_signer,
+            &outsider_authority_signer,
+        )
+        .await
+        .is_err());
+
+    // The suspended moderator can not delete the record to escape the condition
+    assert_eq!(
+        suspended
+            .delete_record(&mut bench, &suspended_moderator)
+            .await,
+        Err(TagError::OwnerCannotDelete.into())
+    );
+    assert!(post
+        .vote(
+            &mut bench,
+            Vote::Down,
+            &suspended_moderator_signer,
+            &suspended_moderator_authority_signer,
+        )
+        .await
+        .is_err());
+
+    // Factories with records the owner can delete can not be used in none_of conditions
+    let deletable = TestTagRecordFactory::new_with_owner_can_delete(&mut bench, true).await;
+    let lenient_authority = TestAuthority::new(
+        &mut bench,
+        &test_channel,
+        &vec![AuthorityType::Vote],
+        &AuthorityCondition::Tags(TagCondition {
+            all_of: vec![core_team.factory],
+            any_of: vec![],
+            none_of: vec![deletable.factory],
+        }),
+        &admin_signer,
+    )
+    .await;
+
+    let member = TestUser::new();
+    core_team.new_record(&mut bench, &member).await;
+    let member_signer = (&member).into();
+    let member_authority_signer = lenient_authority
+        .get_signing_authority(&mut bench, &member_signer)
+        .await;
+    assert_eq!(
+        post.vote(
+            &mut bench,
+            Vote::Up,
+            &member_signer,
+            &member_authority_signer,
+        )
+        .await,
+        Err(TagError::InvalidTagConditionFactory.into())
+    );
+}
+
 // TODO add negative tests
@@ -6,10 +6,14 @@ use crate::{
 };
 
 use borsh::BorshSerialize;
+use lsignforme::{error::SignForMeError, state::SignForMeScope};
 use lsocial::{
     error::SocialError,
     instruction::{CreateVoteConfig, PostInstruction},
-    state::{channel::ChannelType, post::PostContent, vote_record::Vote},
+    state::{
+        channel::ChannelType, channel_authority::AuthorityType, post::PostContent,
+        vote_record::Vote,
+    },
 };
 
 use solana_program::{program_error::ProgramError, pubkey::Pubkey};
@@ -196,7 +200,11 @@ async fn success_vote_unvote_sign_for_me() {
 
     let sign_for_me_signer = Keypair::new();
 
-    let scope = test_channel.channel;
+    let scope = SignForMeScope {
+        program: lsocial::id(),
+        resource: Some(test_channel.channel),
+        actions: AuthorityType::CreatePost.action() | AuthorityType::Vote.action(),
+    };
 
     let sign_for_me =
         TestSignForMe::new(&mut bench, &admin.keypair, &sign_for_me_signer, &scope).await;
@@ -257,3 +265,79 @@ async fn success_vote_unvote_sign_for_me() {
         ProgramError::Custom(SocialError::VoteDoesNotExist as u32)
     );
 }
+
+#[tokio::test]
+async fn fail_sign_for_me_out_of_scope() {
+    let mut bench = ProgramTestBench::start_new(program_test()).await;
+    let admin = TestUser::new();
+    let (test_collection, collection_authority) = TestChannel::new(
+        &mut bench,
+        &admin,
+        None,
+        &ChannelType::Collection,
+        None,
+        None,
+    )
+    .await;
+
+    let signed_owner = (&admin).into();
+    let create_channel_authority = collection_authority
+        .get_signing_authority(&mut bench, &signed_owner)
+        .await;
+
+    let (test_channel, authority) = TestChannel::new(
+        &mut bench,
+        &admin,
+        None,
+        &ChannelType::PostStream,
+        Some(&test_collection),
+        Some(&create_channel_authority),
+    )
+    .await;
+
+    let admin_signing_authority = authority
+        .get_signing_authority(&mut bench, &signed_owner)
+        .await;
+    let post = TestPost::new(
+        &mut bench,
+        &test_channel,
+        &signed_owner,
+        &PostContent::String("a".into()),
+        None,
+        &admin_signing_authority,
+    )
+    .await;
+
+    // Delegations of another action, and of the action in another channel
+    let sign_for_me_signer = Keypair::new();
+    let scopes = [
+        SignForMeScope {
+            program: lsocial::id(),
+            resource: Some(test_channel.channel),
+            actions: AuthorityType::CreatePost.action(),
+        },
+        SignForMeScope {
+            program: lsocial::id(),
+            resource: Some(Pubkey::new_unique()),
+            actions: AuthorityType::Vote.action(),
+        },
+    ];
+    for scope in &scopes {
+        let sign_for_me =
+            TestSignForMe::new(&mut bench, &admin.keypair, &sign_for_me_signer, scope).await;
+        let signing_owner = TestSignerMaybeForMe {
+            original_signer: &admin.keypair,
+            sign_for_me: Some(&sign_for_me),
+        };
+        let signing_authority = authority
+            .get_signing_authority(&mut bench, &signing_owner)
+            .await;
+
+        assert_eq!(
+            post.vote(&mut bench, Vote::Up, &signing_owner, &signing_authority)
+                .await
+                .unwrap_err(),
+            ProgramError::Custom(SignForMeError::InvalidScope as u32)
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use lsignforme::get_sign_for_me_program_address;
+use lsignforme::{get_sign_for_me_program_address, state::SignForMeScope};
 use ltag::{
     get_tag_program_address, get_tag_record_factory_program_address, get_tag_record_program_address,
 };
@@ -132,6 +132,13 @@ pub struct TestTagRecordFactory {
 
 impl TestTagRecordFactory {
     pub async fn new(bench: &mut ProgramTestBench) -> Self {
+        Self::new_with_owner_can_delete(bench, false).await
+    }
+
+    pub async fn new_with_owner_can_delete(
+        bench: &mut ProgramTestBench,
+        owner_can_delete: bool,
+    ) -> Self {
         let authority = Keypair::new();
         let tag = Pubkey::new_unique().to_string();
         bench
@@ -154,8 +161,14 @@ impl TestTagRecordFactory {
                 &[ltag::instruction::create_tag_record_factory(
                     &ltag::id(),
                     &tag_address,
+                    None,
                     &authority.pubkey(),
                     &bench.payer.pubkey(),
+                    false,
+                    false,
+                    !owner_can_delete,
+                    None,
+                    false,
                 )],
                 Some(&[&authority]),
             )
@@ -181,12 +194,37 @@ impl TestTagRecordFactory {
                     &self.factory,
                     &self.authority.pubkey(),
                     &bench.payer.pubkey(),
+                    1,
                 )],
                 Some(&[&self.authority]),
             )
             .await
             .unwrap();
     }
+
+    pub async fn delete_record(
+        &self,
+        bench: &mut ProgramTestBench,
+        owner: &TestUser,
+    ) -> Result<(), ProgramError> {
+        bench
+            .process_transaction(
+                &[ltag::instruction::delete_tag_record_as_owner(
+                    &ltag::id(),
+                    &get_tag_record_program_address(
+                        &ltag::id(),
+                        &self.factory,
+                        &owner.keypair.pubkey(),
+                    )
+                    .0,
+                    &owner.keypair.pubkey(),
+                    &self.factory,
+                    &owner.keypair.pubkey(),
+                )],
+                Some(&[&owner.keypair]),
+            )
+            .await
+    }
 }
 
 pub struct TestAuthority {
@@ -228,7 +266,7 @@ impl<'a> TestSignerMaybeForMe<'a> {
 pub struct TestSignForMe<'a> {
     pub signer: &'a Keypair,
     pub sign_for_me: SignForMe,
-    pub scope: Pubkey,
+    pub scope: SignForMeScope,
 }
 
 impl<'a> TestSignForMe<'a> {
@@ -236,16 +274,19 @@ impl<'a> TestSignForMe<'a> {
         bench: &mut ProgramTestBench,
         owner: &'a Keypair,
         signer: &'a Keypair,
-        scope: &Pubkey,
+        scope: &SignForMeScope,
     ) -> TestSignForMe<'a> {
         bench
             .process_transaction(
                 &[lsignforme::instruction::create_sign_for_me(
                     &lsignforme::id(),
                     &owner.pubkey(),
-                    &signer.pubkey(),
+                    &[signer.pubkey()],
+                    1,
                     scope,
                     &bench.payer.pubkey(),
+                    None,
+                    None,
                 )],
                 Some(&[owner]),
             )
@@ -257,14 +298,15 @@ impl<'a> TestSignForMe<'a> {
                 sign_for_me: get_sign_for_me_program_address(
                     &lsignforme::id(),
                     &owner.pubkey(),
-                    &signer.pubkey(),
+                    &[signer.pubkey()],
+                    1,
                     scope,
                 )
                 .0,
-                signer: signer.pubkey(),
+                signers: vec![signer.pubkey()],
             },
             signer,
-            scope: *scope,
+            scope: scope.clone(),
         }
     }
 }
@@ -369,6 +411,10 @@ impl TestAuthority {
                     owner: signer_maybe_sign_for_me.into(),
                 }
             }
+            AuthorityCondition::Tags(condition) => SignedAuthorityCondition::Tags {
+                owner: signer_maybe_sign_for_me.into(),
+                condition,
+            },
         };
         TestSignedAuthority {
             signer: &signer_maybe_sign_for_me,
@@ -0,0 +1,141 @@
+//! Conditions over tag records, for programs gating actions on the tags of an owner
+
+use std::slice::Iter;
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use shared::account::get_account_data;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::{Clock, UnixTimestamp},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    error::TagError,
+    get_tag_record_program_address,
+    state::{TagRecordAccount, TagRecordFactoryAccount},
+};
+
+/// Condition on the records an owner holds, given as record factories.
+/// E.g. moderators can be "core team AND NOT suspended" with `all_of: [core_team], none_of: [suspended]`
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq, Default)]
+pub struct TagCondition {
+    /// The owner has to hold a record from every factory
+    pub all_of: Vec<Pubkey>,
+
+    /// The owner has to hold a record from at least one factory, if any
+    pub any_of: Vec<Pubkey>,
+
+    /// The owner can not hold a record from any factory.
+    /// Only the absence of a record the owner can not remove can be verified,
+    /// see `TagRecordFactoryAccount::assert_can_be_excluded`
+    pub none_of: Vec<Pubkey>,
+}
+
+impl TagCondition {
+    /// The record factories of the condition
+    pub fn factories(&self) -> impl Iterator<Item = &Pubkey> {
+        self.all_of
+            .iter()
+            .chain(self.any_of.iter())
+            .chain(self.none_of.iter())
+    }
+
+    /// Addresses to pass to the verifier, in order. The record of the owner for every factory,
+    /// preceded by the factory itself for none_of factories
+    pub fn get_account_addresses(&self, program_id: &Pubkey, owner: &Pubkey) -> Vec<Pubkey> {
+        let mut addresses = Vec::new();
+        for factory in self.all_of.iter().chain(self.any_of.iter()) {
+            addresses.push(get_tag_record_program_address(program_id, factory, owner).0);
+        }
+        for factory in &self.none_of {
+            addresses.push(*factory);
+            addresses.push(get_tag_record_program_address(program_id, factory, owner).0);
+        }
+        addresses
+    }
+}
+
+/// Whether the owner holds a valid record from the factory, the record can have any index.
+/// An empty account counts as not held, if it is the (unique) record address of the owner
+pub fn holds_tag_record(
+    program_id: &Pubkey,
+    tag_record_info: &AccountInfo,
+    factory: &Pubkey,
+    owner: &Pubkey,
+    current_unix_timestamp: UnixTimestamp,
+) -> Result<bool, ProgramError> {
+    if tag_record_info.data_is_empty() {
+        if tag_record_info.key != &get_tag_record_program_address(program_id, factory, owner).0 {
+            return Err(TagError::InvalidTagRecordAddress.into());
+        }
+        return Ok(false);
+    }
+
+    let data = get_account_data::<TagRecordAccount>(program_id, tag_record_info)?;
+    if &data.factory != factory {
+        return Err(TagError::InvalidTagRecordFactory.into());
+    }
+    if &data.owner != owner {
+        return Err(TagError::InvalidOwner.into());
+    }
+    Ok(data.assert_valid(current_unix_timestamp).is_ok())
+}
+
+/// Verifies the owner satisfies the condition.
+/// Consumes the accounts of `TagCondition::get_account_addresses`, in order.
+/// The owner is expected to be verified by the caller
+pub fn verify_tag_condition(
+    program_id: &Pubkey,
+    condition: &TagCondition,
+    owner: &Pubkey,
+    accounts_iter: &mut Iter<AccountInfo>,
+) -> Result<bool, ProgramError> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut holds = |factory: &Pubkey, excluded: bool| -> Result<bool, ProgramError> {
+        if excluded {
+            // The factory is passed to verify the record can not be removed by the owner
+            let tag_record_factory_info = next_account_info(accounts_iter)?;
+            if tag_record_factory_info.key != factory {
+                return Err(TagError::InvalidTagRecordFactory.into());
+            }
+            get_account_data::<TagRecordFactoryAccount>(program_id, tag_record_factory_info)?
+                .assert_can_be_excluded()?;
+        }
+        let tag_record_info = next_account_info(accounts_iter)?;
+        holds_tag_record(program_id, tag_record_info, factory, owner, now)
+    };
+
+    // Every account is consumed, so the accounts of the caller after the condition are found
+    let mut all_of = true;
+    for factory in &condition.all_of {
+        all_of &= holds(factory, false)?;
+    }
+
+    let mut any_of = condition.any_of.is_empty();
+    for factory in &condition.any_of {
+        any_of |= holds(factory, false)?;
+    }
+
+    let mut none_of = true;
+    for factory in &condition.none_of {
+        none_of &= !holds(factory, true)?;
+    }
+
+    Ok(all_of && any_of && none_of)
+}
+
+/// Asserts the owner satisfies the condition, see `verify_tag_condition`
+pub fn assert_tag_condition(
+    program_id: &Pubkey,
+    condition: &TagCondition,
+    owner: &Pubkey,
+    accounts_iter: &mut Iter<AccountInfo>,
+) -> Result<(), ProgramError> {
+    if !verify_tag_condition(program_id, condition, owner, accounts_iter)? {
+        return Err(TagError::TagConditionNotMet.into());
+    }
+    Ok(())
+}
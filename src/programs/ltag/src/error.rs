@@ -41,6 +41,78 @@ pub enum TagError {
     /// Invalid tag
     #[error("Invalid tag")]
     InvalidTag,
+
+    /// Tag record can not be transferred
+    #[error("Tag record can not be transferred")]
+    TagRecordNotTransferable,
+
+    /// Factory authority can not withdraw tag records
+    #[error("Factory authority can not withdraw tag records")]
+    AuthorityCannotWithdraw,
+
+    /// Tag record has expired
+    #[error("Tag record has expired")]
+    TagRecordExpired,
+
+    /// Owner is not in the claim allowlist
+    #[error("Owner is not in the claim allowlist")]
+    InvalidClaimProof,
+
+    /// Factory is not token gated
+    #[error("Factory is not token gated")]
+    InvalidTokenGate,
+
+    /// Not enough tokens to hold the tag record
+    #[error("Not enough tokens to hold the tag record")]
+    NotEnoughTokens,
+
+    /// Owner still holds enough tokens
+    #[error("Owner still holds enough tokens")]
+    EnoughTokens,
+
+    /// Account is not the tag record address of the owner
+    #[error("Account is not the tag record address of the owner")]
+    InvalidTagRecordAddress,
+
+    /// Owner does not satisfy the tag condition
+    #[error("Owner does not satisfy the tag condition")]
+    TagConditionNotMet,
+
+    /// Tag record is not active
+    #[error("Tag record is not active")]
+    TagRecordNotActive,
+
+    /// Invalid tag record attributes
+    #[error("Invalid tag record attributes")]
+    InvalidTagRecordAttributes,
+
+    /// Tag record factory has outstanding records
+    #[error("Tag record factory has outstanding records")]
+    TagRecordFactoryHasOutstandingRecords,
+
+    /// Tag has record factories
+    #[error("Tag has record factories")]
+    TagHasRecordFactories,
+
+    /// Records of an indexed factory can only be issued by the factory authority
+    #[error("Records of an indexed factory can only be issued by the factory authority")]
+    TagRecordFactoryIsIndexed,
+
+    /// Tag record was already claimed with the current claim root
+    #[error("Tag record was already claimed with the current claim root")]
+    TagRecordAlreadyClaimed,
+
+    /// Tag record was not claimed with tokens
+    #[error("Tag record was not claimed with tokens")]
+    TagRecordNotClaimedWithTokens,
+
+    /// Owner can not delete tag records
+    #[error("Owner can not delete tag records")]
+    OwnerCannotDelete,
+
+    /// Records of the factory can be indexed, deleted or transferred by the owner, so it can not be a none_of condition
+    #[error("Records of the factory can be indexed, deleted or transferred by the owner, so it can not be a none_of condition")]
+    InvalidTagConditionFactory,
 }
 
 impl PrintProgramError for TagError {
@@ -5,9 +5,14 @@ use solana_program::{
     pubkey::Pubkey,
     system_program,
 };
+use spl_associated_token_account::get_associated_token_address;
 
 use crate::{
-    get_tag_program_address, get_tag_record_factory_program_address, get_tag_record_program_address,
+    get_claim_marker_program_address, get_indexed_revoked_tag_record_program_address,
+    get_indexed_tag_record_program_address, get_revoked_tag_record_program_address,
+    get_tag_program_address, get_tag_record_factory_program_address,
+    get_tag_record_program_address,
+    state::{TagRecordAttribute, TagRecordStatus, TokenGate},
 };
 
 #[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
@@ -23,19 +28,129 @@ pub enum TagInstruction {
         bump_seed: u8,
     },
     CreateTagRecord {
+        #[allow(dead_code)] // but it's not
+        quantity: u64,
+
         #[allow(dead_code)] // but it's not
         bump_seed: u8,
     },
     DeleteTagRecordAsOwner,
-    DeleteTagRecordAsFactory,
+    DeleteTagRecordAsFactory {
+        #[allow(dead_code)] // but it's not
+        reason: Option<ContentSource>,
+
+        #[allow(dead_code)] // but it's not
+        bump_seed: u8,
+    },
 
+    /// Creates a factory of the tag, signed by the factory authority.
+    /// The tag authority has to co-sign if the tag restricts factories
     CreateTagRecordFactory {
         #[allow(dead_code)] // but it's not
         tag: Pubkey,
 
+        #[allow(dead_code)] // but it's not
+        owner_can_transfer: bool,
+
+        #[allow(dead_code)] // but it's not
+        authority_can_withdraw: bool,
+
+        #[allow(dead_code)] // but it's not
+        owner_cannot_delete: bool,
+
+        #[allow(dead_code)] // but it's not
+        record_validity: Option<u64>,
+
+        #[allow(dead_code)] // but it's not
+        indexed_records: bool,
+
+        #[allow(dead_code)] // but it's not
+        bump_seed: u8,
+    },
+
+    TransferTagRecord {
+        #[allow(dead_code)] // but it's not
+        bump_seed: u8,
+    },
+
+    RenewTagRecord,
+
+    UpdateTagInfo {
+        #[allow(dead_code)] // but it's not
+        info: Option<ContentSource>,
+    },
+
+    SetTagAuthority {
+        #[allow(dead_code)] // but it's not
+        new_authority: Pubkey,
+    },
+
+    /// The factory keeps its address (derived from the initial authority),
+    /// so records issued before the change stay valid
+    SetFactoryAuthority {
+        #[allow(dead_code)] // but it's not
+        new_authority: Pubkey,
+    },
+
+    /// Owner in the claim allowlist of the factory creates its own record.
+    /// An owner can claim once per claim root, even if the record is deleted
+    ClaimTagRecord {
+        #[allow(dead_code)] // but it's not
+        quantity: u64,
+
+        #[allow(dead_code)] // but it's not
+        proof: Vec<[u8; 32]>,
+
+        #[allow(dead_code)] // but it's not
+        bump_seed: u8,
+
+        #[allow(dead_code)] // but it's not
+        claim_marker_bump_seed: u8,
+    },
+
+    SetClaimRoot {
+        #[allow(dead_code)] // but it's not
+        claim_root: Option<[u8; 32]>,
+    },
+
+    SetTokenGate {
+        #[allow(dead_code)] // but it's not
+        token_gate: Option<TokenGate>,
+    },
+
+    /// Holder of enough tokens of the gated mint creates its own record
+    ClaimTagRecordWithTokens {
         #[allow(dead_code)] // but it's not
         bump_seed: u8,
     },
+
+    /// Anyone can revoke a record claimed with tokens once the owner no longer holds enough tokens
+    RevokeTagRecordWithTokens,
+
+    /// Factory authority attests the record with evidence, attributes and a status.
+    /// Only authorities that can withdraw records can set a status other than active
+    UpdateTagRecordAttributes {
+        #[allow(dead_code)] // but it's not
+        evidence: Option<ContentSource>,
+
+        #[allow(dead_code)] // but it's not
+        attributes: Vec<TagRecordAttribute>,
+
+        #[allow(dead_code)] // but it's not
+        status: TagRecordStatus,
+    },
+
+    /// Only a factory without outstanding records can be deleted
+    DeleteTagRecordFactory,
+
+    /// Only a tag without record factories can be deleted
+    DeleteTag,
+
+    /// Restricts the creation of record factories to the tag authority, so the tag can always be deleted
+    SetRestrictFactories {
+        #[allow(dead_code)] // but it's not
+        restrict_factories: bool,
+    },
 }
 
 /// Creates a tag transction
@@ -78,6 +193,7 @@ pub fn create_tag_record(
     authority: &Pubkey,
     payer: &Pubkey,
     // Args
+    quantity: u64,
 ) -> Instruction {
     let (tag_record_address, tag_record_bump_seed) =
         get_tag_record_program_address(program_id, factory, owner);
@@ -85,6 +201,42 @@ pub fn create_tag_record(
     Instruction {
         program_id: *program_id,
         data: (TagInstruction::CreateTagRecord {
+            quantity,
+            bump_seed: tag_record_bump_seed,
+        })
+        .try_to_vec()
+        .unwrap(),
+        accounts: vec![
+            AccountMeta::new(tag_record_address, false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new(*factory, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    }
+}
+
+/// Creates the record at `index`, which has to be the record count of the factory
+pub fn create_indexed_tag_record(
+    program_id: &Pubkey,
+
+    // Accounts
+    owner: &Pubkey,
+    factory: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    index: u64,
+    quantity: u64,
+) -> Instruction {
+    let (tag_record_address, tag_record_bump_seed) =
+        get_indexed_tag_record_program_address(program_id, factory, owner, index);
+
+    Instruction {
+        program_id: *program_id,
+        data: (TagInstruction::CreateTagRecord {
+            quantity,
             bump_seed: tag_record_bump_seed,
         })
         .try_to_vec()
@@ -123,53 +275,514 @@ pub fn delete_tag_record_as_owner(
     }
 }
 
+/// Revokes a tag record, the reason (if any) is kept in a tombstone account paid by the payer
 pub fn delete_tag_record_as_factory(
     program_id: &Pubkey,
     // Accounts
     tag_record: &Pubkey,
+    owner: &Pubkey,
     factory: &Pubkey,
     authority: &Pubkey,
     withdraw_destination: &Pubkey,
+    payer: &Pubkey,
     // Args
+    reason: Option<ContentSource>,
 ) -> Instruction {
+    let (revoked_tag_record, revoked_tag_record_bump_seed) =
+        get_revoked_tag_record_program_address(program_id, factory, owner);
+    delete_tag_record_as_factory_with_revoked_tag_record(
+        program_id,
+        tag_record,
+        factory,
+        authority,
+        withdraw_destination,
+        payer,
+        &revoked_tag_record,
+        revoked_tag_record_bump_seed,
+        reason,
+    )
+}
+
+/// Revokes the record at `index` of a factory with indexed records, every index has its own tombstone
+#[allow(clippy::too_many_arguments)]
+pub fn delete_indexed_tag_record_as_factory(
+    program_id: &Pubkey,
+    // Accounts
+    tag_record: &Pubkey,
+    owner: &Pubkey,
+    factory: &Pubkey,
+    authority: &Pubkey,
+    withdraw_destination: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    index: u64,
+    reason: Option<ContentSource>,
+) -> Instruction {
+    let (revoked_tag_record, revoked_tag_record_bump_seed) =
+        get_indexed_revoked_tag_record_program_address(program_id, factory, owner, index);
+    delete_tag_record_as_factory_with_revoked_tag_record(
+        program_id,
+        tag_record,
+        factory,
+        authority,
+        withdraw_destination,
+        payer,
+        &revoked_tag_record,
+        revoked_tag_record_bump_seed,
+        reason,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn delete_tag_record_as_factory_with_revoked_tag_record(
+    program_id: &Pubkey,
+    // Accounts
+    tag_record: &Pubkey,
+    factory: &Pubkey,
+    authority: &Pubkey,
+    withdraw_destination: &Pubkey,
+    payer: &Pubkey,
+    revoked_tag_record: &Pubkey,
+    // Args
+    revoked_tag_record_bump_seed: u8,
+    reason: Option<ContentSource>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*tag_record, false),
+        AccountMeta::new(*factory, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*withdraw_destination, false),
+    ];
+    if reason.is_some() {
+        accounts.push(AccountMeta::new(*revoked_tag_record, false));
+        accounts.push(AccountMeta::new(*payer, true));
+        accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+    }
+
     Instruction {
         program_id: *program_id,
-        data: (TagInstruction::DeleteTagRecordAsFactory)
-            .try_to_vec()
-            .unwrap(),
-        accounts: vec![
-            AccountMeta::new(*tag_record, false),
-            AccountMeta::new(*factory, false),
-            AccountMeta::new_readonly(*authority, true),
-            AccountMeta::new(*withdraw_destination, false),
-        ],
+        data: (TagInstruction::DeleteTagRecordAsFactory {
+            reason,
+            bump_seed: revoked_tag_record_bump_seed,
+        })
+        .try_to_vec()
+        .unwrap(),
+        accounts,
     }
 }
+
+#[allow(clippy::too_many_arguments)]
 pub fn create_tag_record_factory(
     program_id: &Pubkey,
     // Accounts
     tag: &Pubkey,
+    tag_authority: Option<&Pubkey>,
     authority: &Pubkey,
     payer: &Pubkey,
     // Args
+    owner_can_transfer: bool,
+    authority_can_withdraw: bool,
+    owner_cannot_delete: bool,
+    record_validity: Option<u64>,
+    indexed_records: bool,
 ) -> Instruction {
     let (tag_record_factory, tag_record_factory_bump_seed) =
         get_tag_record_factory_program_address(program_id, tag, authority);
+    let mut accounts = vec![
+        AccountMeta::new(tag_record_factory, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*tag, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    if let Some(tag_authority) = tag_authority {
+        accounts.push(AccountMeta::new_readonly(*tag_authority, true));
+    }
 
     Instruction {
         program_id: *program_id,
         data: (TagInstruction::CreateTagRecordFactory {
             tag: *tag,
+            owner_can_transfer,
+            authority_can_withdraw,
+            owner_cannot_delete,
+            record_validity,
+            indexed_records,
             bump_seed: tag_record_factory_bump_seed,
         })
         .try_to_vec()
         .unwrap(),
+        accounts,
+    }
+}
+
+pub fn transfer_tag_record(
+    program_id: &Pubkey,
+    // Accounts
+    owner: &Pubkey,
+    new_owner: &Pubkey,
+    factory: &Pubkey,
+    payer: &Pubkey,
+    // Args
+) -> Instruction {
+    let tag_record = get_tag_record_program_address(program_id, factory, owner).0;
+    let (new_tag_record, new_tag_record_bump_seed) =
+        get_tag_record_program_address(program_id, factory, new_owner);
+
+    Instruction {
+        program_id: *program_id,
+        data: (TagInstruction::TransferTagRecord {
+            bump_seed: new_tag_record_bump_seed,
+        })
+        .try_to_vec()
+        .unwrap(),
+        accounts: vec![
+            AccountMeta::new(tag_record, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(*factory, false),
+            AccountMeta::new(new_tag_record, false),
+            AccountMeta::new_readonly(*new_owner, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    }
+}
+
+pub fn transfer_indexed_tag_record(
+    program_id: &Pubkey,
+    // Accounts
+    owner: &Pubkey,
+    new_owner: &Pubkey,
+    factory: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    index: u64,
+) -> Instruction {
+    let tag_record = get_indexed_tag_record_program_address(program_id, factory, owner, index).0;
+    let (new_tag_record, new_tag_record_bump_seed) =
+        get_indexed_tag_record_program_address(program_id, factory, new_owner, index);
+
+    Instruction {
+        program_id: *program_id,
+        data: (TagInstruction::TransferTagRecord {
+            bump_seed: new_tag_record_bump_seed,
+        })
+        .try_to_vec()
+        .unwrap(),
+        accounts: vec![
+            AccountMeta::new(tag_record, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(*factory, false),
+            AccountMeta::new(new_tag_record, false),
+            AccountMeta::new_readonly(*new_owner, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    }
+}
+
+pub fn renew_tag_record(
+    program_id: &Pubkey,
+    // Accounts
+    tag_record: &Pubkey,
+    factory: &Pubkey,
+    authority: &Pubkey,
+    // Args
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        data: (TagInstruction::RenewTagRecord).try_to_vec().unwrap(),
+        accounts: vec![
+            AccountMeta::new(*tag_record, false),
+            AccountMeta::new_readonly(*factory, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+    }
+}
+
+pub fn update_tag_info(
+    program_id: &Pubkey,
+    // Accounts
+    tag: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    info: Option<ContentSource>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        data: (TagInstruction::UpdateTagInfo { info })
+            .try_to_vec()
+            .unwrap(),
+        accounts: vec![
+            AccountMeta::new(*tag, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    }
+}
+
+pub fn set_tag_authority(
+    program_id: &Pubkey,
+    // Accounts
+    tag: &Pubkey,
+    authority: &Pubkey,
+    // Args
+    new_authority: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        data: (TagInstruction::SetTagAuthority {
+            new_authority: *new_authority,
+        })
+        .try_to_vec()
+        .unwrap(),
+        accounts: vec![
+            AccountMeta::new(*tag, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+    }
+}
+
+pub fn set_factory_authority(
+    program_id: &Pubkey,
+    // Accounts
+    factory: &Pubkey,
+    authority: &Pubkey,
+    // Args
+    new_authority: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        data: (TagInstruction::SetFactoryAuthority {
+            new_authority: *new_authority,
+        })
+        .try_to_vec()
+        .unwrap(),
         accounts: vec![
-            AccountMeta::new(tag_record_factory, false),
+            AccountMeta::new(*factory, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+    }
+}
+
+pub fn claim_tag_record(
+    program_id: &Pubkey,
+    // Accounts
+    owner: &Pubkey,
+    factory: &Pubkey,
+    // Args
+    quantity: u64,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    let (tag_record_address, tag_record_bump_seed) =
+        get_tag_record_program_address(program_id, factory, owner);
+    let (claim_marker_address, claim_marker_bump_seed) =
+        get_claim_marker_program_address(program_id, factory, owner);
+
+    Instruction {
+        program_id: *program_id,
+        data: (TagInstruction::ClaimTagRecord {
+            quantity,
+            proof,
+            bump_seed: tag_record_bump_seed,
+            claim_marker_bump_seed,
+        })
+        .try_to_vec()
+        .unwrap(),
+        accounts: vec![
+            AccountMeta::new(tag_record_address, false),
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(*factory, false),
+            AccountMeta::new(claim_marker_address, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    }
+}
+
+pub fn set_claim_root(
+    program_id: &Pubkey,
+    // Accounts
+    factory: &Pubkey,
+    authority: &Pubkey,
+    // Args
+    claim_root: Option<[u8; 32]>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        data: (TagInstruction::SetClaimRoot { claim_root })
+            .try_to_vec()
+            .unwrap(),
+        accounts: vec![
+            AccountMeta::new(*factory, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+    }
+}
+
+pub fn set_token_gate(
+    program_id: &Pubkey,
+    // Accounts
+    factory: &Pubkey,
+    authority: &Pubkey,
+    // Args
+    token_gate: Option<TokenGate>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        data: (TagInstruction::SetTokenGate { token_gate })
+            .try_to_vec()
+            .unwrap(),
+        accounts: vec![
+            AccountMeta::new(*factory, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+    }
+}
+
+pub fn claim_tag_record_with_tokens(
+    program_id: &Pubkey,
+    // Accounts
+    owner: &Pubkey,
+    factory: &Pubkey,
+    mint: &Pubkey,
+    // Args
+) -> Instruction {
+    let (tag_record_address, tag_record_bump_seed) =
+        get_tag_record_program_address(program_id, factory, owner);
+
+    Instruction {
+        program_id: *program_id,
+        data: (TagInstruction::ClaimTagRecordWithTokens {
+            bump_seed: tag_record_bump_seed,
+        })
+        .try_to_vec()
+        .unwrap(),
+        accounts: vec![
+            AccountMeta::new(tag_record_address, false),
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(*factory, false),
+            AccountMeta::new_readonly(get_associated_token_address(owner, mint), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    }
+}
+
+pub fn revoke_tag_record_with_tokens(
+    program_id: &Pubkey,
+    // Accounts
+    owner: &Pubkey,
+    factory: &Pubkey,
+    mint: &Pubkey,
+    // Args
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        data: (TagInstruction::RevokeTagRecordWithTokens)
+            .try_to_vec()
+            .unwrap(),
+        accounts: vec![
+            AccountMeta::new(
+                get_tag_record_program_address(program_id, factory, owner).0,
+                false,
+            ),
+            AccountMeta::new(*owner, false),
+            AccountMeta::new(*factory, false),
+            AccountMeta::new_readonly(get_associated_token_address(owner, mint), false),
+        ],
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_tag_record_attributes(
+    program_id: &Pubkey,
+    // Accounts
+    tag_record: &Pubkey,
+    factory: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    evidence: Option<ContentSource>,
+    attributes: Vec<TagRecordAttribute>,
+    status: TagRecordStatus,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        data: (TagInstruction::UpdateTagRecordAttributes {
+            evidence,
+            attributes,
+            status,
+        })
+        .try_to_vec()
+        .unwrap(),
+        accounts: vec![
+            AccountMeta::new(*tag_record, false),
+            AccountMeta::new_readonly(*factory, false),
             AccountMeta::new_readonly(*authority, true),
-            AccountMeta::new_readonly(*tag, false),
             AccountMeta::new(*payer, true),
             AccountMeta::new_readonly(system_program::id(), false),
         ],
     }
 }
+
+/// Deletes an empty factory, the factory is passed explicitly since its authority can change
+pub fn delete_tag_record_factory(
+    program_id: &Pubkey,
+    // Accounts
+    tag: &Pubkey,
+    factory: &Pubkey,
+    authority: &Pubkey,
+    withdraw_destination: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        data: (TagInstruction::DeleteTagRecordFactory)
+            .try_to_vec()
+            .unwrap(),
+        accounts: vec![
+            AccountMeta::new(*factory, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*tag, false),
+            AccountMeta::new(*withdraw_destination, false),
+        ],
+    }
+}
+
+pub fn delete_tag(
+    program_id: &Pubkey,
+    // Accounts
+    tag: &Pubkey,
+    authority: &Pubkey,
+    withdraw_destination: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        data: (TagInstruction::DeleteTag).try_to_vec().unwrap(),
+        accounts: vec![
+            AccountMeta::new(*tag, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*withdraw_destination, false),
+        ],
+    }
+}
+
+pub fn set_restrict_factories(
+    program_id: &Pubkey,
+    // Accounts
+    tag: &Pubkey,
+    authority: &Pubkey,
+    // Args
+    restrict_factories: bool,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        data: (TagInstruction::SetRestrictFactories { restrict_factories })
+            .try_to_vec()
+            .unwrap(),
+        accounts: vec![
+            AccountMeta::new(*tag, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+    }
+}
@@ -1,7 +1,9 @@
+pub mod condition;
 #[cfg(not(feature = "no-entrypoint"))]
 pub mod entrypoint;
 pub mod error;
 pub mod instruction;
+pub mod merkle;
 pub mod names;
 pub mod processor;
 pub mod state;
@@ -39,6 +41,104 @@ pub fn get_tag_record_program_address_seeds<'a>(
     [b"record", factory.as_ref(), owner.as_ref(), bump_seed]
 }
 
+/// Records of factories with indexed records, an owner can hold many records of the factory
+pub fn get_indexed_tag_record_program_address(
+    program_id: &Pubkey,
+    factory: &Pubkey,
+    owner: &Pubkey,
+    index: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"record",
+            factory.as_ref(),
+            owner.as_ref(),
+            &index.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+pub fn get_indexed_tag_record_program_address_seeds<'a>(
+    factory: &'a Pubkey,
+    owner: &'a Pubkey,
+    index: &'a [u8; 8],
+    bump_seed: &'a [u8; 1],
+) -> [&'a [u8]; 5] {
+    [
+        b"record",
+        factory.as_ref(),
+        owner.as_ref(),
+        index,
+        bump_seed,
+    ]
+}
+
+pub fn get_revoked_tag_record_program_address(
+    program_id: &Pubkey,
+    factory: &Pubkey,
+    owner: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"revoked", factory.as_ref(), owner.as_ref()], program_id)
+}
+
+pub fn get_revoked_tag_record_program_address_seeds<'a>(
+    factory: &'a Pubkey,
+    owner: &'a Pubkey,
+    bump_seed: &'a [u8; 1],
+) -> [&'a [u8]; 4] {
+    [b"revoked", factory.as_ref(), owner.as_ref(), bump_seed]
+}
+
+/// Tombstones of indexed records, so every index keeps its own revocation reason
+pub fn get_indexed_revoked_tag_record_program_address(
+    program_id: &Pubkey,
+    factory: &Pubkey,
+    owner: &Pubkey,
+    index: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"revoked",
+            factory.as_ref(),
+            owner.as_ref(),
+            &index.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+pub fn get_indexed_revoked_tag_record_program_address_seeds<'a>(
+    factory: &'a Pubkey,
+    owner: &'a Pubkey,
+    index: &'a [u8; 8],
+    bump_seed: &'a [u8; 1],
+) -> [&'a [u8]; 5] {
+    [
+        b"revoked",
+        factory.as_ref(),
+        owner.as_ref(),
+        index,
+        bump_seed,
+    ]
+}
+
+pub fn get_claim_marker_program_address(
+    program_id: &Pubkey,
+    factory: &Pubkey,
+    owner: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"claimed", factory.as_ref(), owner.as_ref()], program_id)
+}
+
+pub fn get_claim_marker_program_address_seeds<'a>(
+    factory: &'a Pubkey,
+    owner: &'a Pubkey,
+    bump_seed: &'a [u8; 1],
+) -> [&'a [u8]; 4] {
+    [b"claimed", factory.as_ref(), owner.as_ref(), bump_seed]
+}
+
 pub fn get_tag_record_factory_program_address(
     program_id: &Pubkey,
     tag: &Pubkey,
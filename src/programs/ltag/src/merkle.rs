@@ -0,0 +1,24 @@
+//! Merkle allowlist for claiming tag records
+
+use solana_program::{keccak::hashv, pubkey::Pubkey};
+
+/// Leaf of the allowlist, an owner that can claim a record with the quantity
+pub fn get_claim_leaf(owner: &Pubkey, quantity: u64) -> [u8; 32] {
+    hashv(&[owner.as_ref(), &quantity.to_le_bytes()]).to_bytes()
+}
+
+/// Hashes a pair of nodes, the nodes are sorted so proofs do not need to carry the side of the siblings
+pub fn hash_claim_nodes(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[a, b]).to_bytes()
+    } else {
+        hashv(&[b, a]).to_bytes()
+    }
+}
+
+pub fn verify_claim_proof(leaf: [u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_claim_nodes(&node, sibling));
+    &computed == root
+}
@@ -1,11 +1,12 @@
 use borsh::BorshSerialize;
 use shared::{
-    account::{dispose_account, get_account_data},
+    account::{dispose_account, get_account_data, resize_and_serialize_account},
     content::ContentSource,
 };
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     borsh::try_from_slice_unchecked,
+    clock::Clock,
     entrypoint::ProgramResult,
     msg,
     program_error::ProgramError,
@@ -18,14 +19,19 @@ use shared::account::create_and_serialize_account_verify_with_bump;
 
 use crate::{
     error::TagError,
+    get_claim_marker_program_address_seeds, get_indexed_revoked_tag_record_program_address_seeds,
+    get_indexed_tag_record_program_address_seeds, get_revoked_tag_record_program_address_seeds,
     get_tag_program_address_seeds, get_tag_record_factory_program_address_seeds,
     get_tag_record_program_address_seeds,
     instruction::TagInstruction,
+    merkle::{get_claim_leaf, verify_claim_proof},
     names::entity_name_is_valid,
     state::{
-        get_tag_record_data_with_factory, get_tag_record_data_with_owner,
-        get_tag_record_factory_with_authority, AccountType, TagAccount, TagRecordAccount,
-        TagRecordFactoryAccount,
+        assert_valid_tag_record_attributes, get_tag_data_with_authority,
+        get_tag_record_data_with_factory, get_tag_record_data_with_factory_and_signed_owner,
+        get_tag_record_data_with_owner, get_tag_record_factory_with_authority, AccountType,
+        ClaimMarkerAccount, RevokedTagRecordAccount, TagAccount, TagRecordAccount,
+        TagRecordAttribute, TagRecordFactoryAccount, TagRecordSource, TagRecordStatus, TokenGate,
     },
 };
 
@@ -75,6 +81,8 @@ impl Processor {
             tag,
             authority: *authority_info.key,
             info,
+            factory_count: 0,
+            restrict_factories: false,
         };
 
         create_and_serialize_account_verify_with_bump(
@@ -93,6 +101,7 @@ impl Processor {
     pub fn process_create_tag_record(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
+        quantity: u64,
         tag_record_bump_seed: u8,
     ) -> ProgramResult {
         let accounts_iter = &mut accounts.iter();
@@ -111,7 +120,14 @@ impl Processor {
             tag_record_authority_info,
         )?;
 
-        factory_data.outstanding_records = factory_data.outstanding_records.checked_add(1).unwrap();
+        if quantity == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        factory_data.outstanding_records = factory_data
+            .outstanding_records
+            .checked_add(quantity)
+            .unwrap();
 
         if !tag_record_info.try_data_is_empty()? {
             // Expected not to exist
@@ -119,18 +135,40 @@ impl Processor {
         }
 
         let rent = Rent::get()?;
+        let index = factory_data.next_record_index();
+        let index_bytes = index.map(u64::to_le_bytes);
         let bump_seeds = [tag_record_bump_seed];
-        let seeds = get_tag_record_program_address_seeds(
-            tag_record_factory_info.key,
-            tag_record_owner_info.key,
-            &bump_seeds,
-        );
+        let seeds = match &index_bytes {
+            Some(index_bytes) => get_indexed_tag_record_program_address_seeds(
+                tag_record_factory_info.key,
+                tag_record_owner_info.key,
+                index_bytes,
+                &bump_seeds,
+            )
+            .to_vec(),
+            None => get_tag_record_program_address_seeds(
+                tag_record_factory_info.key,
+                tag_record_owner_info.key,
+                &bump_seeds,
+            )
+            .to_vec(),
+        };
 
+        let now = Clock::get()?.unix_timestamp;
         let tag_account = TagRecordAccount {
             account_type: AccountType::TagRecord,
             factory: *tag_record_factory_info.key,
             owner: *tag_record_owner_info.key,
             tag: factory_data.tag,
+            quantity,
+            expires_at: factory_data.get_record_expiry(None, now),
+            issuer: *tag_record_authority_info.key,
+            issued_at: now,
+            evidence: None,
+            attributes: Vec::new(),
+            status: TagRecordStatus::Active,
+            index,
+            source: TagRecordSource::Authority,
         };
 
         factory_data.serialize(&mut *tag_record_factory_info.data.borrow_mut())?;
@@ -149,6 +187,8 @@ impl Processor {
     pub fn process_delete_tag_record_as_factory(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
+        reason: Option<ContentSource>,
+        revoked_tag_record_bump_seed: u8,
     ) -> ProgramResult {
         let accounts_iter = &mut accounts.iter();
         let tag_record_info = next_account_info(accounts_iter)?;
@@ -164,9 +204,11 @@ impl Processor {
             return Err(TagError::InvalidAuthority.into());
         }
 
-        factory_data.outstanding_records = factory_data.outstanding_records.checked_sub(1).unwrap();
+        if !factory_data.authority_can_withdraw {
+            return Err(TagError::AuthorityCannotWithdraw.into());
+        }
 
-        let _tag_record = get_tag_record_data_with_factory(
+        let tag_record = get_tag_record_data_with_factory(
             program_id,
             tag_record_info,
             tag_record_factory_info,
@@ -174,9 +216,78 @@ impl Processor {
             tag_record_authority_info,
         )?;
 
+        factory_data.outstanding_records = factory_data
+            .outstanding_records
+            .checked_sub(tag_record.quantity)
+            .unwrap();
+
         factory_data.serialize(&mut *tag_record_factory_info.data.borrow_mut())?;
         dispose_account(tag_record_info, destination_account_info);
 
+        if let Some(reason) = reason {
+            let revoked_tag_record_info = next_account_info(accounts_iter)?;
+            let payer_account = next_account_info(accounts_iter)?;
+            let system_account = next_account_info(accounts_iter)?;
+
+            let revoked_tag_record = RevokedTagRecordAccount {
+                account_type: AccountType::RevokedTagRecord,
+                tag: tag_record.tag,
+                owner: tag_record.owner,
+                factory: tag_record.factory,
+                index: tag_record.index,
+                reason,
+            };
+
+            let rent = Rent::get()?;
+            if revoked_tag_record_info.try_data_is_empty()? {
+                let index_bytes = tag_record.index.map(u64::to_le_bytes);
+                let bump_seeds = [revoked_tag_record_bump_seed];
+                let seeds = match &index_bytes {
+                    Some(index_bytes) => get_indexed_revoked_tag_record_program_address_seeds(
+                        tag_record_factory_info.key,
+                        &tag_record.owner,
+                        index_bytes,
+                        &bump_seeds,
+                    )
+                    .to_vec(),
+                    None => get_revoked_tag_record_program_address_seeds(
+                        tag_record_factory_info.key,
+                        &tag_record.owner,
+                        &bump_seeds,
+                    )
+                    .to_vec(),
+                };
+                create_and_serialize_account_verify_with_bump(
+                    payer_account,
+                    revoked_tag_record_info,
+                    &revoked_tag_record,
+                    &seeds,
+                    program_id,
+                    system_account,
+                    &rent,
+                )?;
+            } else {
+                // The record was issued and revoked before, keep the latest reason
+                let previous = get_account_data::<RevokedTagRecordAccount>(
+                    program_id,
+                    revoked_tag_record_info,
+                )?;
+                if previous.factory != tag_record.factory
+                    || previous.owner != tag_record.owner
+                    || previous.index != tag_record.index
+                {
+                    return Err(ProgramError::InvalidSeeds);
+                }
+                resize_and_serialize_account(
+                    payer_account,
+                    revoked_tag_record_info,
+                    &revoked_tag_record,
+                    system_account,
+                    &rent,
+                )?;
+            }
+        }
+
         Ok(())
     }
 
@@ -194,9 +305,7 @@ impl Processor {
         let mut factory_data =
             get_account_data::<TagRecordFactoryAccount>(program_id, tag_record_factory_info)?;
 
-        factory_data.outstanding_records = factory_data.outstanding_records.checked_sub(1).unwrap();
-
-        let _tag_record = get_tag_record_data_with_owner(
+        let tag_record = get_tag_record_data_with_owner(
             program_id,
             tag_record_info,
             tag_record_factory_info,
@@ -204,6 +313,19 @@ impl Processor {
             tag_record_owner_info,
         )?;
 
+        if &tag_record.factory != tag_record_factory_info.key {
+            return Err(TagError::InvalidTagRecordFactory.into());
+        }
+
+        if factory_data.owner_cannot_delete {
+            return Err(TagError::OwnerCannotDelete.into());
+        }
+
+        factory_data.outstanding_records = factory_data
+            .outstanding_records
+            .checked_sub(tag_record.quantity)
+            .unwrap();
+
         factory_data.serialize(&mut *tag_record_factory_info.data.borrow_mut())?;
         dispose_account(tag_record_info, destination_account_info);
 
@@ -213,17 +335,21 @@ impl Processor {
     pub fn process_create_tag_record_factory(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
+        owner_can_transfer: bool,
+        authority_can_withdraw: bool,
+        owner_cannot_delete: bool,
+        record_validity: Option<u64>,
+        indexed_records: bool,
         tag_record_factory_bump_seed: u8,
     ) -> ProgramResult {
         let accounts_iter = &mut accounts.iter();
         let tag_record_factory_info = next_account_info(accounts_iter)?;
-        let tag_authority_info = next_account_info(accounts_iter)?;
+        let tag_record_authority_info = next_account_info(accounts_iter)?;
         let tag_info = next_account_info(accounts_iter)?;
-
         let payer_account = next_account_info(accounts_iter)?;
         let system_account = next_account_info(accounts_iter)?;
 
-        if !tag_authority_info.is_signer {
+        if !tag_record_authority_info.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
@@ -237,21 +363,36 @@ impl Processor {
             return Err(TagError::TagDoesNotExist.into());
         }
 
+        // Factories keep the tag from being deleted, so a restricted tag only lets its authority create them
+        let mut tag_data = get_account_data::<TagAccount>(program_id, tag_info)?;
+        if tag_data.restrict_factories {
+            let tag_authority_info = next_account_info(accounts_iter)?;
+            get_tag_data_with_authority(program_id, tag_info, tag_authority_info)?;
+        }
+        tag_data.factory_count = tag_data.factory_count.checked_add(1).unwrap();
+        tag_data.serialize(&mut *tag_info.data.borrow_mut())?;
+
         let rent = Rent::get()?;
         let bump_seeds = [tag_record_factory_bump_seed];
         let seeds = get_tag_record_factory_program_address_seeds(
             tag_info.key,
-            tag_authority_info.key,
+            tag_record_authority_info.key,
             &bump_seeds,
         );
 
         let tag_record_factory_account = TagRecordFactoryAccount {
             account_type: AccountType::TagRecordFactory,
-            authority: *tag_authority_info.key,
+            authority: *tag_record_authority_info.key,
             tag: *tag_info.key,
             outstanding_records: 0,
-            authority_can_withdraw: false,
-            owner_can_transfer: false,
+            authority_can_withdraw,
+            owner_can_transfer,
+            owner_cannot_delete,
+            record_validity,
+            claim_root: None,
+            token_gate: None,
+            indexed_records,
+            record_count: 0,
         };
 
         create_and_serialize_account_verify_with_bump(
@@ -266,6 +407,564 @@ impl Processor {
         Ok(())
     }
 
+    pub fn process_delete_tag_record_factory(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let tag_record_factory_info = next_account_info(accounts_iter)?;
+        let tag_record_authority_info = next_account_info(accounts_iter)?;
+        let tag_info = next_account_info(accounts_iter)?;
+        let destination_account_info = next_account_info(accounts_iter)?;
+
+        let factory_data = get_tag_record_factory_with_authority(
+            program_id,
+            tag_record_factory_info,
+            tag_record_authority_info,
+        )?;
+
+        if factory_data.outstanding_records > 0 {
+            return Err(TagError::TagRecordFactoryHasOutstandingRecords.into());
+        }
+
+        if &factory_data.tag != tag_info.key {
+            return Err(TagError::InvalidTag.into());
+        }
+
+        let mut tag_data = get_account_data::<TagAccount>(program_id, tag_info)?;
+        tag_data.factory_count = tag_data.factory_count.checked_sub(1).unwrap();
+        tag_data.serialize(&mut *tag_info.data.borrow_mut())?;
+
+        dispose_account(tag_record_factory_info, destination_account_info);
+        Ok(())
+    }
+
+    pub fn process_delete_tag(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let tag_info = next_account_info(accounts_iter)?;
+        let authority_info = next_account_info(accounts_iter)?;
+        let destination_account_info = next_account_info(accounts_iter)?;
+
+        let tag_data = get_tag_data_with_authority(program_id, tag_info, authority_info)?;
+        if tag_data.factory_count > 0 {
+            return Err(TagError::TagHasRecordFactories.into());
+        }
+
+        dispose_account(tag_info, destination_account_info);
+        Ok(())
+    }
+
+    pub fn process_transfer_tag_record(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        new_tag_record_bump_seed: u8,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let tag_record_info = next_account_info(accounts_iter)?;
+        let tag_record_owner_info = next_account_info(accounts_iter)?;
+        let tag_record_factory_info = next_account_info(accounts_iter)?;
+        let new_tag_record_info = next_account_info(accounts_iter)?;
+        let new_tag_record_owner_info = next_account_info(accounts_iter)?;
+
+        let payer_account = next_account_info(accounts_iter)?;
+        let system_account = next_account_info(accounts_iter)?;
+
+        let factory_data =
+            get_account_data::<TagRecordFactoryAccount>(program_id, tag_record_factory_info)?;
+
+        if !factory_data.owner_can_transfer {
+            return Err(TagError::TagRecordNotTransferable.into());
+        }
+
+        let mut tag_record = get_tag_record_data_with_factory_and_signed_owner(
+            program_id,
+            tag_record_info,
+            tag_record_factory_info.key,
+            tag_record_owner_info,
+        )?;
+
+        if !new_tag_record_info.try_data_is_empty()? {
+            // The recipient can only hold one record per factory and index
+            return Err(TagError::TagRecordAlreadyExist.into());
+        }
+
+        // Indexed records keep their index
+        let rent = Rent::get()?;
+        let index_bytes = tag_record.index.map(u64::to_le_bytes);
+        let bump_seeds = [new_tag_record_bump_seed];
+        let seeds = match &index_bytes {
+            Some(index_bytes) => get_indexed_tag_record_program_address_seeds(
+                tag_record_factory_info.key,
+                new_tag_record_owner_info.key,
+                index_bytes,
+                &bump_seeds,
+            )
+            .to_vec(),
+            None => get_tag_record_program_address_seeds(
+                tag_record_factory_info.key,
+                new_tag_record_owner_info.key,
+                &bump_seeds,
+            )
+            .to_vec(),
+        };
+
+        tag_record.owner = *new_tag_record_owner_info.key;
+        create_and_serialize_account_verify_with_bump(
+            payer_account,
+            new_tag_record_info,
+            &tag_record,
+            &seeds,
+            program_id,
+            system_account,
+            &rent,
+        )?;
+
+        // The payer funds the new record, so it also receives the rent of the old one
+        dispose_account(tag_record_info, payer_account);
+
+        Ok(())
+    }
+
+    pub fn process_renew_tag_record(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let tag_record_info = next_account_info(accounts_iter)?;
+        let tag_record_factory_info = next_account_info(accounts_iter)?;
+        let tag_record_authority_info = next_account_info(accounts_iter)?;
+
+        let factory_data = get_tag_record_factory_with_authority(
+            program_id,
+            tag_record_factory_info,
+            tag_record_authority_info,
+        )?;
+
+        let mut tag_record = get_tag_record_data_with_factory(
+            program_id,
+            tag_record_info,
+            tag_record_factory_info,
+            &factory_data,
+            tag_record_authority_info,
+        )?;
+
+        tag_record.expires_at =
+            factory_data.get_record_expiry(tag_record.expires_at, Clock::get()?.unix_timestamp);
+        tag_record.serialize(&mut *tag_record_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub fn process_update_tag_record_attributes(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        evidence: Option<ContentSource>,
+        attributes: Vec<TagRecordAttribute>,
+        status: TagRecordStatus,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let tag_record_info = next_account_info(accounts_iter)?;
+        let tag_record_factory_info = next_account_info(accounts_iter)?;
+        let tag_record_authority_info = next_account_info(accounts_iter)?;
+        let payer_account = next_account_info(accounts_iter)?;
+        let system_account = next_account_info(accounts_iter)?;
+
+        let factory_data = get_tag_record_factory_with_authority(
+            program_id,
+            tag_record_factory_info,
+            tag_record_authority_info,
+        )?;
+
+        let mut tag_record = get_tag_record_data_with_factory(
+            program_id,
+            tag_record_info,
+            tag_record_factory_info,
+            &factory_data,
+            tag_record_authority_info,
+        )?;
+
+        assert_valid_tag_record_attributes(&attributes)?;
+
+        // Any status but active invalidates the record, which is a withdrawal in effect
+        if status != TagRecordStatus::Active && !factory_data.authority_can_withdraw {
+            return Err(TagError::AuthorityCannotWithdraw.into());
+        }
+
+        // The signing authority attests the new data
+        tag_record.issuer = *tag_record_authority_info.key;
+        tag_record.evidence = evidence;
+        tag_record.attributes = attributes;
+        tag_record.status = status;
+
+        resize_and_serialize_account(
+            payer_account,
+            tag_record_info,
+            &tag_record,
+            system_account,
+            &Rent::get()?,
+        )
+    }
+
+    pub fn process_update_tag_info(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        info: Option<ContentSource>,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let tag_info = next_account_info(accounts_iter)?;
+        let authority_info = next_account_info(accounts_iter)?;
+        let payer_account = next_account_info(accounts_iter)?;
+        let system_account = next_account_info(accounts_iter)?;
+
+        let mut tag_data = get_tag_data_with_authority(program_id, tag_info, authority_info)?;
+        tag_data.info = info;
+
+        resize_and_serialize_account(
+            payer_account,
+            tag_info,
+            &tag_data,
+            system_account,
+            &Rent::get()?,
+        )
+    }
+
+    pub fn process_set_tag_authority(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        new_authority: Pubkey,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let tag_info = next_account_info(accounts_iter)?;
+        let authority_info = next_account_info(accounts_iter)?;
+
+        let mut tag_data = get_tag_data_with_authority(program_id, tag_info, authority_info)?;
+        tag_data.authority = new_authority;
+        tag_data.serialize(&mut *tag_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub fn process_set_restrict_factories(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        restrict_factories: bool,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let tag_info = next_account_info(accounts_iter)?;
+        let authority_info = next_account_info(accounts_iter)?;
+
+        let mut tag_data = get_tag_data_with_authority(program_id, tag_info, authority_info)?;
+        tag_data.restrict_factories = restrict_factories;
+        tag_data.serialize(&mut *tag_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub fn process_set_factory_authority(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        new_authority: Pubkey,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let tag_record_factory_info = next_account_info(accounts_iter)?;
+        let tag_record_authority_info = next_account_info(accounts_iter)?;
+
+        let mut factory_data = get_tag_record_factory_with_authority(
+            program_id,
+            tag_record_factory_info,
+            tag_record_authority_info,
+        )?;
+
+        // Records refer to the factory address, which does not change with the authority
+        factory_data.authority = new_authority;
+        factory_data.serialize(&mut *tag_record_factory_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub fn process_claim_tag_record(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        quantity: u64,
+        proof: Vec<[u8; 32]>,
+        tag_record_bump_seed: u8,
+        claim_marker_bump_seed: u8,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let tag_record_info = next_account_info(accounts_iter)?;
+        let tag_record_owner_info = next_account_info(accounts_iter)?;
+        let tag_record_factory_info = next_account_info(accounts_iter)?;
+        let claim_marker_info = next_account_info(accounts_iter)?;
+        let system_account = next_account_info(accounts_iter)?;
+
+        if !tag_record_owner_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut factory_data =
+            get_account_data::<TagRecordFactoryAccount>(program_id, tag_record_factory_info)?;
+
+        factory_data.assert_not_indexed_records()?;
+
+        let claim_root = factory_data.claim_root.ok_or(TagError::InvalidClaimProof)?;
+        if !verify_claim_proof(
+            get_claim_leaf(tag_record_owner_info.key, quantity),
+            &proof,
+            &claim_root,
+        ) {
+            return Err(TagError::InvalidClaimProof.into());
+        }
+
+        if quantity == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if !tag_record_info.try_data_is_empty()? {
+            // Expected not to exist
+            return Err(TagError::TagRecordAlreadyExist.into());
+        }
+
+        factory_data.outstanding_records = factory_data
+            .outstanding_records
+            .checked_add(quantity)
+            .unwrap();
+
+        let rent = Rent::get()?;
+        let bump_seeds = [tag_record_bump_seed];
+        let seeds = get_tag_record_program_address_seeds(
+            tag_record_factory_info.key,
+            tag_record_owner_info.key,
+            &bump_seeds,
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let tag_account = TagRecordAccount {
+            account_type: AccountType::TagRecord,
+            factory: *tag_record_factory_info.key,
+            owner: *tag_record_owner_info.key,
+            tag: factory_data.tag,
+            quantity,
+            expires_at: factory_data.get_record_expiry(None, now),
+            issuer: factory_data.authority,
+            issued_at: now,
+            evidence: None,
+            attributes: Vec::new(),
+            status: TagRecordStatus::Active,
+            index: None,
+            source: TagRecordSource::Allowlist,
+        };
+
+        factory_data.serialize(&mut *tag_record_factory_info.data.borrow_mut())?;
+
+        // The owner pays for its own record
+        create_and_serialize_account_verify_with_bump(
+            tag_record_owner_info,
+            tag_record_info,
+            &tag_account,
+            &seeds,
+            program_id,
+            system_account,
+            &rent,
+        )?;
+
+        // A revoked or deleted record can not be claimed again with the same proof
+        let claim_marker = ClaimMarkerAccount {
+            account_type: AccountType::ClaimMarker,
+            factory: *tag_record_factory_info.key,
+            owner: *tag_record_owner_info.key,
+            claim_root,
+        };
+        if claim_marker_info.try_data_is_empty()? {
+            let bump_seeds = [claim_marker_bump_seed];
+            let seeds = get_claim_marker_program_address_seeds(
+                tag_record_factory_info.key,
+                tag_record_owner_info.key,
+                &bump_seeds,
+            );
+            create_and_serialize_account_verify_with_bump(
+                tag_record_owner_info,
+                claim_marker_info,
+                &claim_marker,
+                &seeds,
+                program_id,
+                system_account,
+                &rent,
+            )?;
+        } else {
+            let previous = get_account_data::<ClaimMarkerAccount>(program_id, claim_marker_info)?;
+            if previous.factory != claim_marker.factory || previous.owner != claim_marker.owner {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            if previous.claim_root == claim_root {
+                return Err(TagError::TagRecordAlreadyClaimed.into());
+            }
+            claim_marker.serialize(&mut *claim_marker_info.data.borrow_mut())?;
+        }
+        Ok(())
+    }
+
+    pub fn process_set_claim_root(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        claim_root: Option<[u8; 32]>,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let tag_record_factory_info = next_account_info(accounts_iter)?;
+        let tag_record_authority_info = next_account_info(accounts_iter)?;
+
+        let mut factory_data = get_tag_record_factory_with_authority(
+            program_id,
+            tag_record_factory_info,
+            tag_record_authority_info,
+        )?;
+
+        factory_data.claim_root = claim_root;
+        factory_data.serialize(&mut *tag_record_factory_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub fn process_set_token_gate(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        token_gate: Option<TokenGate>,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let tag_record_factory_info = next_account_info(accounts_iter)?;
+        let tag_record_authority_info = next_account_info(accounts_iter)?;
+
+        let mut factory_data = get_tag_record_factory_with_authority(
+            program_id,
+            tag_record_factory_info,
+            tag_record_authority_info,
+        )?;
+
+        factory_data.token_gate = token_gate;
+        factory_data.serialize(&mut *tag_record_factory_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub fn process_claim_tag_record_with_tokens(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        tag_record_bump_seed: u8,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let tag_record_info = next_account_info(accounts_iter)?;
+        let tag_record_owner_info = next_account_info(accounts_iter)?;
+        let tag_record_factory_info = next_account_info(accounts_iter)?;
+        let token_account_info = next_account_info(accounts_iter)?;
+        let system_account = next_account_info(accounts_iter)?;
+
+        if !tag_record_owner_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut factory_data =
+            get_account_data::<TagRecordFactoryAccount>(program_id, tag_record_factory_info)?;
+
+        factory_data.assert_not_indexed_records()?;
+
+        let (balance, token_gate) =
+            factory_data.get_token_gate_balance(token_account_info, tag_record_owner_info.key)?;
+        if balance < token_gate.min_amount {
+            return Err(TagError::NotEnoughTokens.into());
+        }
+
+        if !tag_record_info.try_data_is_empty()? {
+            // Expected not to exist
+            return Err(TagError::TagRecordAlreadyExist.into());
+        }
+
+        factory_data.outstanding_records = factory_data.outstanding_records.checked_add(1).unwrap();
+
+        let rent = Rent::get()?;
+        let bump_seeds = [tag_record_bump_seed];
+        let seeds = get_tag_record_program_address_seeds(
+            tag_record_factory_info.key,
+            tag_record_owner_info.key,
+            &bump_seeds,
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let tag_account = TagRecordAccount {
+            account_type: AccountType::TagRecord,
+            factory: *tag_record_factory_info.key,
+            owner: *tag_record_owner_info.key,
+            tag: factory_data.tag,
+            quantity: 1,
+            expires_at: factory_data.get_record_expiry(None, now),
+            issuer: factory_data.authority,
+            issued_at: now,
+            evidence: None,
+            attributes: Vec::new(),
+            status: TagRecordStatus::Active,
+            index: None,
+            source: TagRecordSource::TokenGate,
+        };
+
+        factory_data.serialize(&mut *tag_record_factory_info.data.borrow_mut())?;
+
+        // The owner pays for its own record
+        create_and_serialize_account_verify_with_bump(
+            tag_record_owner_info,
+            tag_record_info,
+            &tag_account,
+            &seeds,
+            program_id,
+            system_account,
+            &rent,
+        )?;
+        Ok(())
+    }
+
+    pub fn process_revoke_tag_record_with_tokens(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let tag_record_info = next_account_info(accounts_iter)?;
+        let tag_record_owner_info = next_account_info(accounts_iter)?;
+        let tag_record_factory_info = next_account_info(accounts_iter)?;
+        let token_account_info = next_account_info(accounts_iter)?;
+
+        let mut factory_data =
+            get_account_data::<TagRecordFactoryAccount>(program_id, tag_record_factory_info)?;
+        let tag_record = get_account_data::<TagRecordAccount>(program_id, tag_record_info)?;
+
+        if &tag_record.factory != tag_record_factory_info.key {
+            return Err(TagError::InvalidTagRecordFactory.into());
+        }
+
+        if &tag_record.owner != tag_record_owner_info.key {
+            return Err(TagError::InvalidOwner.into());
+        }
+
+        // Records issued by the authority or claimed from the allowlist do not depend on the balance
+        if tag_record.source != TagRecordSource::TokenGate {
+            return Err(TagError::TagRecordNotClaimedWithTokens.into());
+        }
+
+        // The associated token account is the one checked when claiming
+        let (balance, token_gate) =
+            factory_data.get_token_gate_balance(token_account_info, tag_record_owner_info.key)?;
+        if balance >= token_gate.min_amount {
+            return Err(TagError::EnoughTokens.into());
+        }
+
+        factory_data.outstanding_records = factory_data
+            .outstanding_records
+            .checked_sub(tag_record.quantity)
+            .unwrap();
+        factory_data.serialize(&mut *tag_record_factory_info.data.borrow_mut())?;
+
+        // The rent goes back to the owner
+        dispose_account(tag_record_info, tag_record_owner_info);
+
+        Ok(())
+    }
+
     pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
         let instruction = try_from_slice_unchecked::<TagInstruction>(data)?;
         match instruction {
@@ -277,21 +976,115 @@ impl Processor {
                 msg!("Instruction: Create tag");
                 Self::process_create_tag(program_id, accounts, tag, info, bump_seed)
             }
-            TagInstruction::CreateTagRecord { bump_seed } => {
+            TagInstruction::CreateTagRecord {
+                quantity,
+                bump_seed,
+            } => {
                 msg!("Instruction: Create tag record");
-                Self::process_create_tag_record(program_id, accounts, bump_seed)
+                Self::process_create_tag_record(program_id, accounts, quantity, bump_seed)
             }
             TagInstruction::DeleteTagRecordAsOwner => {
                 msg!("Instruction: Delete tag record as owner");
                 Self::process_delete_tag_record_as_owner(program_id, accounts)
             }
-            TagInstruction::DeleteTagRecordAsFactory => {
+            TagInstruction::DeleteTagRecordAsFactory { reason, bump_seed } => {
                 msg!("Instruction: Delete tag record as record");
-                Self::process_delete_tag_record_as_factory(program_id, accounts)
+                Self::process_delete_tag_record_as_factory(program_id, accounts, reason, bump_seed)
             }
-            TagInstruction::CreateTagRecordFactory { tag: _, bump_seed } => {
+            TagInstruction::CreateTagRecordFactory {
+                tag: _,
+                owner_can_transfer,
+                authority_can_withdraw,
+                owner_cannot_delete,
+                record_validity,
+                indexed_records,
+                bump_seed,
+            } => {
                 msg!("Instruction: Create tag record factory");
-                Self::process_create_tag_record_factory(program_id, accounts, bump_seed)
+                Self::process_create_tag_record_factory(
+                    program_id,
+                    accounts,
+                    owner_can_transfer,
+                    authority_can_withdraw,
+                    owner_cannot_delete,
+                    record_validity,
+                    indexed_records,
+                    bump_seed,
+                )
+            }
+            TagInstruction::TransferTagRecord { bump_seed } => {
+                msg!("Instruction: Transfer tag record");
+                Self::process_transfer_tag_record(program_id, accounts, bump_seed)
+            }
+            TagInstruction::RenewTagRecord => {
+                msg!("Instruction: Renew tag record");
+                Self::process_renew_tag_record(program_id, accounts)
+            }
+            TagInstruction::UpdateTagInfo { info } => {
+                msg!("Instruction: Update tag info");
+                Self::process_update_tag_info(program_id, accounts, info)
+            }
+            TagInstruction::SetTagAuthority { new_authority } => {
+                msg!("Instruction: Set tag authority");
+                Self::process_set_tag_authority(program_id, accounts, new_authority)
+            }
+            TagInstruction::SetFactoryAuthority { new_authority } => {
+                msg!("Instruction: Set factory authority");
+                Self::process_set_factory_authority(program_id, accounts, new_authority)
+            }
+            TagInstruction::ClaimTagRecord {
+                quantity,
+                proof,
+                bump_seed,
+                claim_marker_bump_seed,
+            } => {
+                msg!("Instruction: Claim tag record");
+                Self::process_claim_tag_record(
+                    program_id,
+                    accounts,
+                    quantity,
+                    proof,
+                    bump_seed,
+                    claim_marker_bump_seed,
+                )
+            }
+            TagInstruction::SetClaimRoot { claim_root } => {
+                msg!("Instruction: Set claim root");
+                Self::process_set_claim_root(program_id, accounts, claim_root)
+            }
+            TagInstruction::SetTokenGate { token_gate } => {
+                msg!("Instruction: Set token gate");
+                Self::process_set_token_gate(program_id, accounts, token_gate)
+            }
+            TagInstruction::ClaimTagRecordWithTokens { bump_seed } => {
+                msg!("Instruction: Claim tag record with tokens");
+                Self::process_claim_tag_record_with_tokens(program_id, accounts, bump_seed)
+            }
+            TagInstruction::RevokeTagRecordWithTokens => {
+                msg!("Instruction: Revoke tag record with tokens");
+                Self::process_revoke_tag_record_with_tokens(program_id, accounts)
+            }
+            TagInstruction::UpdateTagRecordAttributes {
+                evidence,
+                attributes,
+                status,
+            } => {
+                msg!("Instruction: Update tag record attributes");
+                Self::process_update_tag_record_attributes(
+                    program_id, accounts, evidence, attributes, status,
+                )
+            }
+            TagInstruction::DeleteTagRecordFactory => {
+                msg!("Instruction: Delete tag record factory");
+                Self::process_delete_tag_record_factory(program_id, accounts)
+            }
+            TagInstruction::DeleteTag => {
+                msg!("Instruction: Delete tag");
+                Self::process_delete_tag(program_id, accounts)
+            }
+            TagInstruction::SetRestrictFactories { restrict_factories } => {
+                msg!("Instruction: Set restrict factories");
+                Self::process_set_restrict_factories(program_id, accounts, restrict_factories)
             }
         }
     }
@@ -2,18 +2,31 @@ use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use shared::{
     account::{get_account_data, MaxSize},
     content::ContentSource,
+    token::{assert_is_associated_token_account, get_token_balance},
 };
 use solana_program::{
-    account_info::AccountInfo, program_error::ProgramError, program_pack::IsInitialized,
+    account_info::AccountInfo,
+    clock::{Clock, UnixTimestamp},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::IsInitialized,
     pubkey::Pubkey,
+    sysvar::Sysvar,
 };
 
 use crate::error::TagError;
+
+pub const MAX_TAG_RECORD_ATTRIBUTES: usize = 16;
+pub const MAX_TAG_RECORD_ATTRIBUTE_KEY_LENGTH: usize = 32;
+pub const MAX_TAG_RECORD_ATTRIBUTE_VALUE_LENGTH: usize = 128;
+
 #[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
 pub enum AccountType {
     Tag,
     TagRecord,
     TagRecordFactory,
+    RevokedTagRecord,
+    ClaimMarker,
 }
 #[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
 pub struct TagAccount {
@@ -21,6 +34,12 @@ pub struct TagAccount {
     pub tag: String,
     pub info: Option<ContentSource>,
     pub authority: Pubkey,
+
+    /// Record factories of the tag, the tag can only be deleted without factories
+    pub factory_count: u64,
+
+    /// Only the authority can create record factories, so nobody else can keep the tag from being deleted
+    pub restrict_factories: bool,
 }
 
 impl MaxSize for TagAccount {
@@ -35,17 +54,129 @@ impl IsInitialized for TagAccount {
     }
 }
 
+pub fn get_tag_data_with_authority<'a>(
+    program_id: &Pubkey,
+    tag_info: &AccountInfo<'a>,
+    authority_info: &AccountInfo<'a>,
+) -> Result<TagAccount, ProgramError> {
+    if !authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let data = get_account_data::<TagAccount>(program_id, tag_info)?;
+
+    if &data.authority != authority_info.key {
+        return Err(TagError::InvalidAuthority.into());
+    }
+
+    Ok(data)
+}
+
 #[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
 pub struct TagRecordAccount {
     pub account_type: AccountType,
     pub tag: Pubkey,
     pub owner: Pubkey,
     pub factory: Pubkey,
+
+    /// Quantity or level of the record, e.g. a contributor tier
+    pub quantity: u64,
+
+    /// The record is no longer valid from this time, unless renewed by the factory authority
+    pub expires_at: Option<UnixTimestamp>,
+
+    /// Factory authority attesting the record
+    pub issuer: Pubkey,
+    pub issued_at: UnixTimestamp,
+
+    /// Proof reference of the attestation, e.g. a KYC report
+    pub evidence: Option<ContentSource>,
+    pub attributes: Vec<TagRecordAttribute>,
+    pub status: TagRecordStatus,
+
+    /// Index of the record if the factory has indexed records
+    pub index: Option<u64>,
+
+    /// How the record was obtained
+    pub source: TagRecordSource,
+}
+
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
+pub enum TagRecordSource {
+    /// Issued by the factory authority
+    Authority,
+
+    /// Claimed by the owner from the allowlist of the factory
+    Allowlist,
+
+    /// Claimed by the owner with tokens of the gated mint, anyone can revoke it once the balance drops
+    TokenGate,
+}
+
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
+pub struct TagRecordAttribute {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
+pub enum TagRecordStatus {
+    Active,
+    Pending,
+    Suspended,
 }
 
 impl MaxSize for TagRecordAccount {
     fn get_max_size(&self) -> Option<usize> {
-        Some(1 + 32 + 32 + 32)
+        None
+    }
+}
+
+pub fn assert_valid_tag_record_attributes(attributes: &[TagRecordAttribute]) -> ProgramResult {
+    if attributes.len() > MAX_TAG_RECORD_ATTRIBUTES {
+        return Err(TagError::InvalidTagRecordAttributes.into());
+    }
+    for (i, attribute) in attributes.iter().enumerate() {
+        if attribute.key.is_empty()
+            || attribute.key.len() > MAX_TAG_RECORD_ATTRIBUTE_KEY_LENGTH
+            || attribute.value.len() > MAX_TAG_RECORD_ATTRIBUTE_VALUE_LENGTH
+        {
+            return Err(TagError::InvalidTagRecordAttributes.into());
+        }
+
+        // Keys are unique
+        if attributes[..i]
+            .iter()
+            .any(|other| other.key == attribute.key)
+        {
+            return Err(TagError::InvalidTagRecordAttributes.into());
+        }
+    }
+    Ok(())
+}
+
+impl TagRecordAccount {
+    /// Records are only valid while active and not expired
+    pub fn assert_valid(&self, current_unix_timestamp: UnixTimestamp) -> ProgramResult {
+        if self.status != TagRecordStatus::Active {
+            return Err(TagError::TagRecordNotActive.into());
+        }
+        self.assert_not_expired(current_unix_timestamp)
+    }
+
+    pub fn get_attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value.as_ref())
+    }
+
+    pub fn assert_not_expired(&self, current_unix_timestamp: UnixTimestamp) -> ProgramResult {
+        if let Some(expires_at) = self.expires_at {
+            if current_unix_timestamp >= expires_at {
+                return Err(TagError::TagRecordExpired.into());
+            }
+        }
+        Ok(())
     }
 }
 
@@ -65,15 +196,28 @@ pub fn get_tag_record_data_with_factory_and_signed_owner<'a>(
     if !owner.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
+    get_tag_record_data_with_factory_and_owner(program_id, tag_record_info, factory, owner.key)
+}
+
+/// Deserializes the tag record and checks the factory and the owner.
+/// The owner has to be verified by the caller
+pub fn get_tag_record_data_with_factory_and_owner(
+    program_id: &Pubkey,
+    tag_record_info: &AccountInfo,
+    factory: &Pubkey,
+    owner: &Pubkey,
+) -> Result<TagRecordAccount, ProgramError> {
     let data = get_account_data::<TagRecordAccount>(program_id, tag_record_info)?;
 
     if &data.factory != factory {
         return Err(TagError::InvalidTagRecordFactory.into());
     }
 
-    if &data.owner != owner.key {
+    if &data.owner != owner {
         return Err(TagError::InvalidOwner.into());
     }
+
+    data.assert_valid(Clock::get()?.unix_timestamp)?;
     Ok(data)
 }
 
@@ -116,19 +260,149 @@ pub fn get_tag_record_data_with_owner<'a>(
     }
 }
 
+/// Tombstone of a tag record revoked by the factory authority, keeping the reason of the revocation
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
+pub struct RevokedTagRecordAccount {
+    pub account_type: AccountType,
+    pub tag: Pubkey,
+    pub owner: Pubkey,
+    pub factory: Pubkey,
+
+    /// Index of the revoked record if the factory has indexed records
+    pub index: Option<u64>,
+    pub reason: ContentSource,
+}
+
+impl MaxSize for RevokedTagRecordAccount {
+    fn get_max_size(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl IsInitialized for RevokedTagRecordAccount {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::RevokedTagRecord
+    }
+}
+
+/// Marks that an owner claimed a record from the allowlist, so the same proof can not mint it again
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
+pub struct ClaimMarkerAccount {
+    pub account_type: AccountType,
+    pub factory: Pubkey,
+    pub owner: Pubkey,
+
+    /// Root the record was claimed with, the owner can only claim again once the root changes
+    pub claim_root: [u8; 32],
+}
+
+impl MaxSize for ClaimMarkerAccount {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(1 + 32 + 32 + 32)
+    }
+}
+
+impl IsInitialized for ClaimMarkerAccount {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::ClaimMarker
+    }
+}
+
 #[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
 pub struct TagRecordFactoryAccount {
     pub account_type: AccountType,
     pub tag: Pubkey,
     pub authority: Pubkey,
+
+    /// Total quantity of the outstanding records
     pub outstanding_records: u64,
     pub owner_can_transfer: bool,
     pub authority_can_withdraw: bool,
+
+    /// Owners can not delete their own records. Factories of none_of conditions require it,
+    /// as the owner would otherwise escape the condition by deleting the record
+    pub owner_cannot_delete: bool,
+
+    /// Validity period (seconds) of issued and renewed records, records never expire if None
+    pub record_validity: Option<u64>,
+
+    /// Merkle root of the owners that can claim a record themselves
+    pub claim_root: Option<[u8; 32]>,
+
+    /// Holders of enough tokens can claim a record, which anyone can revoke once the balance drops
+    pub token_gate: Option<TokenGate>,
+
+    /// Records are addressed by (factory, owner, index), so an owner can hold many records
+    pub indexed_records: bool,
+
+    /// Indexed records issued, the index of the next record
+    pub record_count: u64,
+}
+
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
+pub struct TokenGate {
+    pub mint: Pubkey,
+    pub min_amount: u64,
 }
 
 impl MaxSize for TagRecordFactoryAccount {
     fn get_max_size(&self) -> Option<usize> {
-        Some(1 + 32 + 32 + 8 + 1 + 1)
+        Some(1 + 32 + 32 + 8 + 1 + 1 + 1 + 1 + 8 + 1 + 32 + 1 + 32 + 8 + 1 + 8)
+    }
+}
+
+impl TagRecordFactoryAccount {
+    /// Index of the next record, if the factory has indexed records
+    pub fn next_record_index(&mut self) -> Option<u64> {
+        if self.indexed_records {
+            let index = self.record_count;
+            self.record_count = index.checked_add(1).unwrap();
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    pub fn assert_not_indexed_records(&self) -> ProgramResult {
+        if self.indexed_records {
+            return Err(TagError::TagRecordFactoryIsIndexed.into());
+        }
+        Ok(())
+    }
+
+    /// The absence of a record can only be trusted if it is unique and the owner can not move it away
+    pub fn assert_can_be_excluded(&self) -> ProgramResult {
+        if self.indexed_records || !self.owner_cannot_delete || self.owner_can_transfer {
+            return Err(TagError::InvalidTagConditionFactory.into());
+        }
+        Ok(())
+    }
+
+    /// Balance of the owner's associated token account of the gated mint
+    pub fn get_token_gate_balance(
+        &self,
+        token_account_info: &AccountInfo,
+        owner: &Pubkey,
+    ) -> Result<(u64, &TokenGate), ProgramError> {
+        let token_gate = self.token_gate.as_ref().ok_or(TagError::InvalidTokenGate)?;
+        assert_is_associated_token_account(token_account_info, owner, &token_gate.mint)?;
+        Ok((get_token_balance(token_account_info)?, token_gate))
+    }
+
+    /// Expiry of a record issued or renewed now
+    /// A renewal of a record that has not yet expired extends it from its current expiry
+    pub fn get_record_expiry(
+        &self,
+        current_expiry: Option<UnixTimestamp>,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> Option<UnixTimestamp> {
+        self.record_validity.map(|validity| {
+            current_expiry
+                .unwrap_or(current_unix_timestamp)
+                .max(current_unix_timestamp)
+                .checked_add(validity as UnixTimestamp)
+                .unwrap()
+        })
     }
 }
 
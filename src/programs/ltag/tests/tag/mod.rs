@@ -1,12 +1,27 @@
-use ltag::state::{TagAccount, TagRecordAccount, TagRecordFactoryAccount};
+use ltag::error::TagError;
+use ltag::merkle::{get_claim_leaf, hash_claim_nodes};
+use ltag::state::{
+    RevokedTagRecordAccount, TagAccount, TagRecordAccount, TagRecordAttribute,
+    TagRecordFactoryAccount, TagRecordSource, TagRecordStatus, TokenGate,
+    MAX_TAG_RECORD_ATTRIBUTES,
+};
 use ltag::{
-    get_tag_program_address, get_tag_record_factory_program_address, get_tag_record_program_address,
+    get_indexed_revoked_tag_record_program_address, get_indexed_tag_record_program_address,
+    get_revoked_tag_record_program_address, get_tag_program_address,
+    get_tag_record_factory_program_address, get_tag_record_program_address,
 };
+use shared::content::ContentSource;
 use solana_program::borsh::try_from_slice_unchecked;
+use solana_program::clock::Clock;
 use solana_program::hash::Hash;
+use solana_program::{program_pack::Pack, system_instruction};
 use solana_program_test::*;
 use solana_sdk::signature::Keypair;
+use solana_sdk::{
+    instruction::InstructionError, transaction::TransactionError, transport::TransportError,
+};
 use solana_sdk::{pubkey::Pubkey, signer::Signer, transaction::Transaction};
+use spl_associated_token_account::{create_associated_token_account, get_associated_token_address};
 
 use crate::utils::program_test;
 
@@ -53,6 +68,7 @@ pub async fn create_tag_record(
     owner: &Pubkey,
     factory: &Pubkey,
     authority: &Keypair,
+    quantity: u64,
 ) -> Pubkey {
     // Create tag record
     banks_client
@@ -63,6 +79,7 @@ pub async fn create_tag_record(
                 factory,
                 &authority.pubkey(),
                 &payer.pubkey(),
+                quantity,
             )],
             Some(&payer.pubkey()),
             &[payer, authority],
@@ -82,6 +99,7 @@ pub async fn create_tag_record(
     assert_eq!(&tag_record_account.factory, factory);
     assert_eq!(&tag_record_account.owner, owner);
     assert_eq!(&tag_record_account.tag, tag);
+    assert_eq!(tag_record_account.quantity, quantity);
     tag_record_address
 }
 
@@ -137,7 +155,11 @@ pub async fn create_tag_record_factory(
     payer: &Keypair,
     recent_blockhash: &Hash,
     tag: &Pubkey,
+    tag_authority: &Keypair,
     authority: &Keypair,
+    owner_can_transfer: bool,
+    authority_can_withdraw: bool,
+    record_validity: Option<u64>,
 ) -> Pubkey {
     // Create tag record
     banks_client
@@ -145,11 +167,17 @@ pub async fn create_tag_record_factory(
             &[ltag::instruction::create_tag_record_factory(
                 &ltag::id(),
                 tag,
+                Some(&tag_authority.pubkey()),
                 &authority.pubkey(),
                 &payer.pubkey(),
+                owner_can_transfer,
+                authority_can_withdraw,
+                false,
+                record_validity,
+                false,
             )],
             Some(&payer.pubkey()),
-            &[payer, authority],
+            &[payer, tag_authority, authority],
             *recent_blockhash,
         ))
         .await
@@ -168,6 +196,15 @@ pub async fn create_tag_record_factory(
     assert_eq!(&tag_record_factory_account.authority, &authority.pubkey());
     assert_eq!(&tag_record_factory_account.outstanding_records, &0);
     assert_eq!(&tag_record_factory_account.tag, tag);
+    assert_eq!(
+        tag_record_factory_account.owner_can_transfer,
+        owner_can_transfer
+    );
+    assert_eq!(
+        tag_record_factory_account.authority_can_withdraw,
+        authority_can_withdraw
+    );
+    assert_eq!(tag_record_factory_account.record_validity, record_validity);
     tag_record_factory_address
 }
 
@@ -204,7 +241,11 @@ async fn success() {
         &payer,
         &recent_blockhash,
         &tag,
+        &tag_authority,
         &factory_authority,
+        false,
+        false,
+        None,
     )
     .await;
 
@@ -216,12 +257,13 @@ async fn success() {
         &owner.pubkey(),
         &factory,
         &factory_authority,
+        3,
     )
     .await;
 
     assert_eq!(
         get_outstanding_records(&mut banks_client, &factory).await,
-        1
+        3
     );
 
     delete_tag_record(
@@ -241,6 +283,1738 @@ async fn success() {
         0
     );
 }
+
+#[tokio::test]
+async fn success_transfer() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let tag_authority = Keypair::new();
+    let tag = create_tag(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        "name",
+        &tag_authority,
+    )
+    .await;
+
+    let owner = Keypair::new();
+    let new_owner = Keypair::new();
+    let factory_authority = Keypair::new();
+    let factory = create_tag_record_factory(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &tag_authority,
+        &factory_authority,
+        true,
+        false,
+        None,
+    )
+    .await;
+
+    let tag_record = create_tag_record(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &owner.pubkey(),
+        &factory,
+        &factory_authority,
+        1,
+    )
+    .await;
+
+    // Only the owner can transfer
+    assert!(banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::transfer_tag_record(
+                &ltag::id(),
+                &new_owner.pubkey(),
+                &owner.pubkey(),
+                &factory,
+                &payer.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &new_owner],
+            recent_blockhash,
+        ))
+        .await
+        .is_err());
+
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::transfer_tag_record(
+                &ltag::id(),
+                &owner.pubkey(),
+                &new_owner.pubkey(),
+                &factory,
+                &payer.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    assert!(banks_client
+        .get_account(tag_record)
+        .await
+        .unwrap()
+        .is_none());
+
+    let new_tag_record =
+        get_tag_record_program_address(&ltag::id(), &factory, &new_owner.pubkey()).0;
+    let new_tag_record_account = try_from_slice_unchecked::<TagRecordAccount>(
+        &banks_client
+            .get_account(new_tag_record)
+            .await
+            .unwrap()
+            .expect("tag record not found")
+            .data,
+    )
+    .unwrap();
+    assert_eq!(new_tag_record_account.owner, new_owner.pubkey());
+    assert_eq!(new_tag_record_account.factory, factory);
+    assert_eq!(
+        get_outstanding_records(&mut banks_client, &factory).await,
+        1
+    );
+
+    // The recipient already holds a record
+    create_tag_record(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &owner.pubkey(),
+        &factory,
+        &factory_authority,
+        1,
+    )
+    .await;
+    assert!(banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::transfer_tag_record(
+                &ltag::id(),
+                &new_owner.pubkey(),
+                &owner.pubkey(),
+                &factory,
+                &payer.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &new_owner],
+            recent_blockhash,
+        ))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn fail_transfer_not_transferable() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let tag_authority = Keypair::new();
+    let tag = create_tag(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        "name",
+        &tag_authority,
+    )
+    .await;
+
+    let owner = Keypair::new();
+    let factory_authority = Keypair::new();
+    let factory = create_tag_record_factory(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &tag_authority,
+        &factory_authority,
+        false,
+        false,
+        None,
+    )
+    .await;
+
+    create_tag_record(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &owner.pubkey(),
+        &factory,
+        &factory_authority,
+        1,
+    )
+    .await;
+
+    let error = banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::transfer_tag_record(
+                &ltag::id(),
+                &owner.pubkey(),
+                &Keypair::new().pubkey(),
+                &factory,
+                &payer.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code)
+        )) if code == TagError::TagRecordNotTransferable as u32
+    ));
+}
+async fn get_expires_at(banks_client: &mut BanksClient, tag_record: &Pubkey) -> Option<i64> {
+    let tag_record_account_info = banks_client
+        .get_account(*tag_record)
+        .await
+        .expect("get_tag_record")
+        .expect("tag record not found");
+    try_from_slice_unchecked::<TagRecordAccount>(&tag_record_account_info.data)
+        .unwrap()
+        .expires_at
+}
+
+#[tokio::test]
+async fn success_renew() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let tag_authority = Keypair::new();
+    let tag = create_tag(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        "name",
+        &tag_authority,
+    )
+    .await;
+
+    let validity = 365 * 24 * 60 * 60;
+    let owner = Keypair::new();
+    let factory_authority = Keypair::new();
+    let factory = create_tag_record_factory(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &tag_authority,
+        &factory_authority,
+        false,
+        false,
+        Some(validity),
+    )
+    .await;
+
+    let now = banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+    let tag_record = create_tag_record(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &owner.pubkey(),
+        &factory,
+        &factory_authority,
+        1,
+    )
+    .await;
+    let expires_at = get_expires_at(&mut banks_client, &tag_record)
+        .await
+        .unwrap();
+    assert!(expires_at >= now + validity as i64);
+
+    // Only the factory authority can renew
+    assert!(banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::renew_tag_record(
+                &ltag::id(),
+                &tag_record,
+                &factory,
+                &owner.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .is_err());
+
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::renew_tag_record(
+                &ltag::id(),
+                &tag_record,
+                &factory,
+                &factory_authority.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    // The record had not expired, so it is extended from its expiry
+    assert_eq!(
+        get_expires_at(&mut banks_client, &tag_record).await,
+        Some(expires_at + validity as i64)
+    );
+}
+
+#[tokio::test]
+async fn fail_transfer_expired() {
+    let mut context = program_test().start_with_context().await;
+    let tag_authority = Keypair::new();
+    let tag = create_tag(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        "name",
+        &tag_authority,
+    )
+    .await;
+
+    let validity = 60;
+    let owner = Keypair::new();
+    let factory_authority = Keypair::new();
+    let factory = create_tag_record_factory(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &tag,
+        &tag_authority,
+        &factory_authority,
+        true,
+        false,
+        Some(validity),
+    )
+    .await;
+
+    let tag_record = create_tag_record(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &tag,
+        &owner.pubkey(),
+        &factory,
+        &factory_authority,
+        1,
+    )
+    .await;
+    let expires_at = get_expires_at(&mut context.banks_client, &tag_record)
+        .await
+        .unwrap();
+
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    while clock.unix_timestamp <= expires_at {
+        context.warp_to_slot(clock.slot + 400).unwrap();
+        clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    }
+
+    let recent_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let error = context
+        .banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::transfer_tag_record(
+                &ltag::id(),
+                &owner.pubkey(),
+                &Keypair::new().pubkey(),
+                &factory,
+                &context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code)
+        )) if code == TagError::TagRecordExpired as u32
+    ));
+}
+
+#[tokio::test]
+async fn success_update_authorities() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let tag_authority = Keypair::new();
+    let tag = create_tag(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        "name",
+        &tag_authority,
+    )
+    .await;
+
+    let info = Some(ContentSource::String(
+        "A much longer description of the tag".into(),
+    ));
+    let new_tag_authority = Keypair::new();
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[
+                ltag::instruction::update_tag_info(
+                    &ltag::id(),
+                    &tag,
+                    &tag_authority.pubkey(),
+                    &payer.pubkey(),
+                    info.clone(),
+                ),
+                ltag::instruction::set_tag_authority(
+                    &ltag::id(),
+                    &tag,
+                    &tag_authority.pubkey(),
+                    &new_tag_authority.pubkey(),
+                ),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &tag_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    let tag_account = try_from_slice_unchecked::<TagAccount>(
+        &banks_client
+            .get_account(tag)
+            .await
+            .unwrap()
+            .expect("tag not found")
+            .data,
+    )
+    .unwrap();
+    assert_eq!(tag_account.info, info);
+    assert_eq!(tag_account.authority, new_tag_authority.pubkey());
+
+    let owner = Keypair::new();
+    let factory_authority = Keypair::new();
+    let factory = create_tag_record_factory(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &tag_authority,
+        &factory_authority,
+        false,
+        true,
+        None,
+    )
+    .await;
+    let tag_record = create_tag_record(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &owner.pubkey(),
+        &factory,
+        &factory_authority,
+        1,
+    )
+    .await;
+
+    // Hand the factory over, e.g. to a governance
+    let new_factory_authority = Keypair::new();
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::set_factory_authority(
+                &ltag::id(),
+                &factory,
+                &factory_authority.pubkey(),
+                &new_factory_authority.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    // The previous authority can no longer issue records
+    assert!(banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::create_tag_record(
+                &ltag::id(),
+                &Keypair::new().pubkey(),
+                &factory,
+                &factory_authority.pubkey(),
+                &payer.pubkey(),
+                1,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .is_err());
+
+    // Records issued before the change are still managed by the factory
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::delete_tag_record_as_factory(
+                &ltag::id(),
+                &tag_record,
+                &owner.pubkey(),
+                &factory,
+                &new_factory_authority.pubkey(),
+                &payer.pubkey(),
+                &payer.pubkey(),
+                None,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &new_factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(
+        get_outstanding_records(&mut banks_client, &factory).await,
+        0
+    );
+
+    // The new authority can delete the empty factory
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::delete_tag_record_factory(
+                &ltag::id(),
+                &tag,
+                &factory,
+                &new_factory_authority.pubkey(),
+                &payer.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &new_factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+    assert!(banks_client.get_account(factory).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn success_claim() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let tag_authority = Keypair::new();
+    let tag = create_tag(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        "name",
+        &tag_authority,
+    )
+    .await;
+
+    let factory_authority = Keypair::new();
+    let factory = create_tag_record_factory(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &tag_authority,
+        &factory_authority,
+        false,
+        false,
+        None,
+    )
+    .await;
+
+    // Allowlist of two owners
+    let owner = Keypair::new();
+    let other = Keypair::new();
+    let owner_leaf = get_claim_leaf(&owner.pubkey(), 2);
+    let other_leaf = get_claim_leaf(&other.pubkey(), 1);
+    let claim_root = hash_claim_nodes(&owner_leaf, &other_leaf);
+
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::set_claim_root(
+                &ltag::id(),
+                &factory,
+                &factory_authority.pubkey(),
+                Some(claim_root),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    let fund_owner = system_instruction::transfer(&payer.pubkey(), &owner.pubkey(), 100000000);
+
+    // The quantity is part of the leaf
+    assert!(banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[
+                fund_owner.clone(),
+                ltag::instruction::claim_tag_record(
+                    &ltag::id(),
+                    &owner.pubkey(),
+                    &factory,
+                    3,
+                    vec![other_leaf],
+                ),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .is_err());
+
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[
+                fund_owner,
+                ltag::instruction::claim_tag_record(
+                    &ltag::id(),
+                    &owner.pubkey(),
+                    &factory,
+                    2,
+                    vec![other_leaf],
+                ),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    let tag_record = get_tag_record_program_address(&ltag::id(), &factory, &owner.pubkey()).0;
+    let tag_record_account = try_from_slice_unchecked::<TagRecordAccount>(
+        &banks_client
+            .get_account(tag_record)
+            .await
+            .unwrap()
+            .expect("tag record not found")
+            .data,
+    )
+    .unwrap();
+    assert_eq!(tag_record_account.owner, owner.pubkey());
+    assert_eq!(tag_record_account.quantity, 2);
+    assert_eq!(
+        get_outstanding_records(&mut banks_client, &factory).await,
+        2
+    );
+
+    // A deleted record can not be claimed again with the same proof
+    delete_tag_record(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag_record,
+        &factory,
+        &factory_authority,
+        &owner,
+        &Pubkey::new_unique(),
+    )
+    .await;
+
+    let error = banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::claim_tag_record(
+                &ltag::id(),
+                &owner.pubkey(),
+                &factory,
+                2,
+                vec![other_leaf],
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code)
+        )) if code == TagError::TagRecordAlreadyClaimed as u32
+    ));
+
+    // A new allowlist allows the owner to claim again
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::set_claim_root(
+                &ltag::id(),
+                &factory,
+                &factory_authority.pubkey(),
+                Some(owner_leaf),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::claim_tag_record(
+                &ltag::id(),
+                &owner.pubkey(),
+                &factory,
+                2,
+                vec![],
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_outstanding_records(&mut banks_client, &factory).await,
+        2
+    );
+
+    // Close the allowlist
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::set_claim_root(
+                &ltag::id(),
+                &factory,
+                &factory_authority.pubkey(),
+                None,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    let error = banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[
+                system_instruction::transfer(&payer.pubkey(), &other.pubkey(), 100000000),
+                ltag::instruction::claim_tag_record(
+                    &ltag::id(),
+                    &other.pubkey(),
+                    &factory,
+                    1,
+                    vec![owner_leaf],
+                ),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &other],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        TransportError::TransactionError(TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(code)
+        )) if code == TagError::InvalidClaimProof as u32
+    ));
+}
+
+#[tokio::test]
+async fn success_token_gate() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let tag_authority = Keypair::new();
+    let tag = create_tag(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        "name",
+        &tag_authority,
+    )
+    .await;
+
+    let factory_authority = Keypair::new();
+    let factory = create_tag_record_factory(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &tag_authority,
+        &factory_authority,
+        false,
+        false,
+        None,
+    )
+    .await;
+
+    // Mint 10 tokens to the owner
+    let mint = Keypair::new();
+    let owner = Keypair::new();
+    let owner_token_account = get_associated_token_address(&owner.pubkey(), &mint.pubkey());
+    let rent = banks_client.get_rent().await.unwrap();
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &mint.pubkey(),
+                    rent.minimum_balance(spl_token::state::Mint::LEN),
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::id(),
+                ),
+                spl_token::instruction::initialize_mint(
+                    &spl_token::id(),
+                    &mint.pubkey(),
+                    &payer.pubkey(),
+                    None,
+                    0,
+                )
+                .unwrap(),
+                create_associated_token_account(&payer.pubkey(), &owner.pubkey(), &mint.pubkey()),
+                spl_token::instruction::mint_to(
+                    &spl_token::id(),
+                    &mint.pubkey(),
+                    &owner_token_account,
+                    &payer.pubkey(),
+                    &[],
+                    10,
+                )
+                .unwrap(),
+                system_instruction::transfer(&payer.pubkey(), &owner.pubkey(), 100000000),
+                ltag::instruction::set_token_gate(
+                    &ltag::id(),
+                    &factory,
+                    &factory_authority.pubkey(),
+                    Some(TokenGate {
+                        mint: mint.pubkey(),
+                        min_amount: 5,
+                    }),
+                ),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &mint, &factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::claim_tag_record_with_tokens(
+                &ltag::id(),
+                &owner.pubkey(),
+                &factory,
+                &mint.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    let tag_record = get_tag_record_program_address(&ltag::id(), &factory, &owner.pubkey()).0;
+    assert!(banks_client
+        .get_account(tag_record)
+        .await
+        .unwrap()
+        .is_some());
+    assert_eq!(
+        get_outstanding_records(&mut banks_client, &factory).await,
+        1
+    );
+
+    // The owner still holds enough tokens
+    let error = banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::revoke_tag_record_with_tokens(
+                &ltag::id(),
+                &owner.pubkey(),
+                &factory,
+                &mint.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code)
+        )) if code == TagError::EnoughTokens as u32
+    ));
+
+    // Anyone can revoke once the balance drops below the minimum
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[
+                spl_token::instruction::burn(
+                    &spl_token::id(),
+                    &owner_token_account,
+                    &mint.pubkey(),
+                    &owner.pubkey(),
+                    &[],
+                    6,
+                )
+                .unwrap(),
+                ltag::instruction::revoke_tag_record_with_tokens(
+                    &ltag::id(),
+                    &owner.pubkey(),
+                    &factory,
+                    &mint.pubkey(),
+                ),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    assert!(banks_client
+        .get_account(tag_record)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        get_outstanding_records(&mut banks_client, &factory).await,
+        0
+    );
+
+    // Records issued by the authority can not be revoked with tokens
+    create_tag_record(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &owner.pubkey(),
+        &factory,
+        &factory_authority,
+        1,
+    )
+    .await;
+    assert_eq!(
+        get_tag_record(&mut banks_client, &tag_record).await.source,
+        TagRecordSource::Authority
+    );
+
+    let error = banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[
+                system_instruction::transfer(&payer.pubkey(), &owner.pubkey(), 1),
+                ltag::instruction::revoke_tag_record_with_tokens(
+                    &ltag::id(),
+                    &owner.pubkey(),
+                    &factory,
+                    &mint.pubkey(),
+                ),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        TransportError::TransactionError(TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(code)
+        )) if code == TagError::TagRecordNotClaimedWithTokens as u32
+    ));
+}
+
+#[tokio::test]
+async fn success_revoke_with_reason() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let tag_authority = Keypair::new();
+    let tag = create_tag(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        "name",
+        &tag_authority,
+    )
+    .await;
+
+    let owner = Keypair::new();
+    let factory_authority = Keypair::new();
+    let factory = create_tag_record_factory(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &tag_authority,
+        &factory_authority,
+        false,
+        true,
+        None,
+    )
+    .await;
+
+    let tag_record = create_tag_record(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &owner.pubkey(),
+        &factory,
+        &factory_authority,
+        1,
+    )
+    .await;
+
+    let reason = ContentSource::String("Left the team".into());
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::delete_tag_record_as_factory(
+                &ltag::id(),
+                &tag_record,
+                &owner.pubkey(),
+                &factory,
+                &factory_authority.pubkey(),
+                &payer.pubkey(),
+                &payer.pubkey(),
+                Some(reason.clone()),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    assert!(banks_client
+        .get_account(tag_record)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        get_outstanding_records(&mut banks_client, &factory).await,
+        0
+    );
+
+    let revoked_tag_record =
+        get_revoked_tag_record_program_address(&ltag::id(), &factory, &owner.pubkey()).0;
+    let revoked_tag_record_account = try_from_slice_unchecked::<RevokedTagRecordAccount>(
+        &banks_client
+            .get_account(revoked_tag_record)
+            .await
+            .unwrap()
+            .expect("revoked tag record not found")
+            .data,
+    )
+    .unwrap();
+    assert_eq!(revoked_tag_record_account.owner, owner.pubkey());
+    assert_eq!(revoked_tag_record_account.tag, tag);
+    assert_eq!(revoked_tag_record_account.reason, reason);
+}
+
+async fn get_tag_record(banks_client: &mut BanksClient, tag_record: &Pubkey) -> TagRecordAccount {
+    try_from_slice_unchecked::<TagRecordAccount>(
+        &banks_client
+            .get_account(*tag_record)
+            .await
+            .unwrap()
+            .expect("tag record not found")
+            .data,
+    )
+    .unwrap()
+}
+
+fn attribute(key: &str, value: &str) -> TagRecordAttribute {
+    TagRecordAttribute {
+        key: key.into(),
+        value: value.into(),
+    }
+}
+
+#[tokio::test]
+async fn success_update_attributes() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let tag_authority = Keypair::new();
+    let tag = create_tag(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        "name",
+        &tag_authority,
+    )
+    .await;
+
+    let owner = Keypair::new();
+    let factory_authority = Keypair::new();
+    let factory = create_tag_record_factory(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &tag_authority,
+        &factory_authority,
+        true,
+        true,
+        None,
+    )
+    .await;
+
+    let tag_record = create_tag_record(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &owner.pubkey(),
+        &factory,
+        &factory_authority,
+        1,
+    )
+    .await;
+
+    let tag_record_account = get_tag_record(&mut banks_client, &tag_record).await;
+    assert_eq!(tag_record_account.issuer, factory_authority.pubkey());
+    assert_eq!(tag_record_account.status, TagRecordStatus::Active);
+    assert!(tag_record_account.evidence.is_none());
+    assert!(tag_record_account.attributes.is_empty());
+
+    // e.g. a KYC attestation
+    let evidence = ContentSource::External {
+        url: "https://example.com/kyc/report".into(),
+    };
+    let attributes = vec![attribute("level", "2"), attribute("country", "SE")];
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::update_tag_record_attributes(
+                &ltag::id(),
+                &tag_record,
+                &factory,
+                &factory_authority.pubkey(),
+                &payer.pubkey(),
+                Some(evidence.clone()),
+                attributes.clone(),
+                TagRecordStatus::Active,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    let tag_record_account = get_tag_record(&mut banks_client, &tag_record).await;
+    assert_eq!(tag_record_account.evidence, Some(evidence));
+    assert_eq!(tag_record_account.attributes, attributes);
+    assert_eq!(tag_record_account.get_attribute("level"), Some("2"));
+
+    // Only the factory authority can attest
+    assert!(banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::update_tag_record_attributes(
+                &ltag::id(),
+                &tag_record,
+                &factory,
+                &owner.pubkey(),
+                &payer.pubkey(),
+                None,
+                vec![attribute("level", "3")],
+                TagRecordStatus::Active,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .is_err());
+
+    // Too many and duplicate attributes
+    let too_many_attributes = (0..MAX_TAG_RECORD_ATTRIBUTES + 1)
+        .map(|i| attribute(&i.to_string(), ""))
+        .collect::<Vec<_>>();
+    for invalid_attributes in [
+        too_many_attributes,
+        vec![attribute("level", "2"), attribute("level", "3")],
+    ] {
+        let error = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[ltag::instruction::update_tag_record_attributes(
+                    &ltag::id(),
+                    &tag_record,
+                    &factory,
+                    &factory_authority.pubkey(),
+                    &payer.pubkey(),
+                    None,
+                    invalid_attributes,
+                    TagRecordStatus::Active,
+                )],
+                Some(&payer.pubkey()),
+                &[&payer, &factory_authority],
+                recent_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            TransportError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(code)
+            )) if code == TagError::InvalidTagRecordAttributes as u32
+        ));
+    }
+
+    // A suspended record is not valid
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::update_tag_record_attributes(
+                &ltag::id(),
+                &tag_record,
+                &factory,
+                &factory_authority.pubkey(),
+                &payer.pubkey(),
+                None,
+                vec![],
+                TagRecordStatus::Suspended,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    let error = banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::transfer_tag_record(
+                &ltag::id(),
+                &owner.pubkey(),
+                &Keypair::new().pubkey(),
+                &factory,
+                &payer.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code)
+        )) if code == TagError::TagRecordNotActive as u32
+    ));
+}
+
+#[tokio::test]
+async fn success_delete_tag_and_factory() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let tag_authority = Keypair::new();
+    let tag = create_tag(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        "name",
+        &tag_authority,
+    )
+    .await;
+
+    let owner = Keypair::new();
+    let factory_authority = Keypair::new();
+    let factory = create_tag_record_factory(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &tag_authority,
+        &factory_authority,
+        false,
+        false,
+        None,
+    )
+    .await;
+
+    let tag_record = create_tag_record(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &owner.pubkey(),
+        &factory,
+        &factory_authority,
+        1,
+    )
+    .await;
+
+    let delete_tag = |destination: Pubkey| {
+        Transaction::new_signed_with_payer(
+            &[ltag::instruction::delete_tag(
+                &ltag::id(),
+                &tag,
+                &tag_authority.pubkey(),
+                &destination,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &tag_authority],
+            recent_blockhash,
+        )
+    };
+    let delete_factory = |destination: Pubkey| {
+        Transaction::new_signed_with_payer(
+            &[ltag::instruction::delete_tag_record_factory(
+                &ltag::id(),
+                &tag,
+                &factory,
+                &factory_authority.pubkey(),
+                &destination,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &factory_authority],
+            recent_blockhash,
+        )
+    };
+
+    // The tag still has a factory, and the factory still has a record
+    let error = banks_client
+        .process_transaction(delete_tag(Pubkey::new_unique()))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code)
+        )) if code == TagError::TagHasRecordFactories as u32
+    ));
+
+    let error = banks_client
+        .process_transaction(delete_factory(Pubkey::new_unique()))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code)
+        )) if code == TagError::TagRecordFactoryHasOutstandingRecords as u32
+    ));
+
+    delete_tag_record(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag_record,
+        &factory,
+        &factory_authority,
+        &owner,
+        &payer.pubkey(),
+    )
+    .await;
+
+    let destination = Pubkey::new_unique();
+    banks_client
+        .process_transaction(delete_factory(destination))
+        .await
+        .unwrap();
+    assert!(banks_client.get_account(factory).await.unwrap().is_none());
+
+    let tag_account = try_from_slice_unchecked::<TagAccount>(
+        &banks_client.get_account(tag).await.unwrap().unwrap().data,
+    )
+    .unwrap();
+    assert_eq!(tag_account.factory_count, 0);
+
+    // Once restricted, only the tag authority can create factories, so nobody else can keep the tag from being deleted
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::set_restrict_factories(
+                &ltag::id(),
+                &tag,
+                &tag_authority.pubkey(),
+                true,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &tag_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+    let tag_account = try_from_slice_unchecked::<TagAccount>(
+        &banks_client.get_account(tag).await.unwrap().unwrap().data,
+    )
+    .unwrap();
+    assert!(tag_account.restrict_factories);
+
+    let griefer = Keypair::new();
+    let error = banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::create_tag_record_factory(
+                &ltag::id(),
+                &tag,
+                Some(&griefer.pubkey()),
+                &griefer.pubkey(),
+                &payer.pubkey(),
+                false,
+                false,
+                false,
+                None,
+                false,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &griefer],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code)
+        )) if code == TagError::InvalidAuthority as u32
+    ));
+
+    banks_client
+        .process_transaction(delete_tag(destination))
+        .await
+        .unwrap();
+    assert!(banks_client.get_account(tag).await.unwrap().is_none());
+    assert!(banks_client.get_balance(destination).await.unwrap() > 0);
+}
+
+#[tokio::test]
+async fn success_indexed_records() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let tag_authority = Keypair::new();
+    let tag = create_tag(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        "hackathon-winner",
+        &tag_authority,
+    )
+    .await;
+
+    let factory_authority = Keypair::new();
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::create_tag_record_factory(
+                &ltag::id(),
+                &tag,
+                None,
+                &factory_authority.pubkey(),
+                &payer.pubkey(),
+                true,
+                true,
+                false,
+                None,
+                true,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+    let factory =
+        get_tag_record_factory_program_address(&ltag::id(), &tag, &factory_authority.pubkey()).0;
+
+    // The same owner wins twice
+    let owner = Keypair::new();
+    for index in 0..2 {
+        banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[ltag::instruction::create_indexed_tag_record(
+                    &ltag::id(),
+                    &owner.pubkey(),
+                    &factory,
+                    &factory_authority.pubkey(),
+                    &payer.pubkey(),
+                    index,
+                    1,
+                )],
+                Some(&payer.pubkey()),
+                &[&payer, &factory_authority],
+                recent_blockhash,
+            ))
+            .await
+            .unwrap();
+
+        let tag_record =
+            get_indexed_tag_record_program_address(&ltag::id(), &factory, &owner.pubkey(), index).0;
+        let tag_record_account = get_tag_record(&mut banks_client, &tag_record).await;
+        assert_eq!(tag_record_account.index, Some(index));
+        assert_eq!(tag_record_account.owner, owner.pubkey());
+    }
+    assert_eq!(
+        get_outstanding_records(&mut banks_client, &factory).await,
+        2
+    );
+
+    // The index has to be the record count of the factory
+    assert!(banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::create_indexed_tag_record(
+                &ltag::id(),
+                &owner.pubkey(),
+                &factory,
+                &factory_authority.pubkey(),
+                &payer.pubkey(),
+                0,
+                2,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .is_err());
+
+    // Records are verified as the records of the owner for any index
+    let new_owner = Keypair::new();
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::transfer_indexed_tag_record(
+                &ltag::id(),
+                &owner.pubkey(),
+                &new_owner.pubkey(),
+                &factory,
+                &payer.pubkey(),
+                1,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    let new_tag_record =
+        get_indexed_tag_record_program_address(&ltag::id(), &factory, &new_owner.pubkey(), 1).0;
+    let tag_record_account = get_tag_record(&mut banks_client, &new_tag_record).await;
+    assert_eq!(tag_record_account.owner, new_owner.pubkey());
+    assert_eq!(tag_record_account.index, Some(1));
+
+    delete_tag_record(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &new_tag_record,
+        &factory,
+        &factory_authority,
+        &new_owner,
+        &payer.pubkey(),
+    )
+    .await;
+    assert_eq!(
+        get_outstanding_records(&mut banks_client, &factory).await,
+        1
+    );
+
+    // Every revoked index keeps its own reason
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::create_indexed_tag_record(
+                &ltag::id(),
+                &owner.pubkey(),
+                &factory,
+                &factory_authority.pubkey(),
+                &payer.pubkey(),
+                2,
+                1,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    for (index, reason) in [(0, "First reason"), (2, "Second reason")] {
+        banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[ltag::instruction::delete_indexed_tag_record_as_factory(
+                    &ltag::id(),
+                    &get_indexed_tag_record_program_address(
+                        &ltag::id(),
+                        &factory,
+                        &owner.pubkey(),
+                        index,
+                    )
+                    .0,
+                    &owner.pubkey(),
+                    &factory,
+                    &factory_authority.pubkey(),
+                    &payer.pubkey(),
+                    &payer.pubkey(),
+                    index,
+                    Some(ContentSource::String(reason.into())),
+                )],
+                Some(&payer.pubkey()),
+                &[&payer, &factory_authority],
+                recent_blockhash,
+            ))
+            .await
+            .unwrap();
+    }
+
+    for (index, reason) in [(0, "First reason"), (2, "Second reason")] {
+        let revoked_tag_record = get_indexed_revoked_tag_record_program_address(
+            &ltag::id(),
+            &factory,
+            &owner.pubkey(),
+            index,
+        )
+        .0;
+        let revoked_tag_record_account = try_from_slice_unchecked::<RevokedTagRecordAccount>(
+            &banks_client
+                .get_account(revoked_tag_record)
+                .await
+                .unwrap()
+                .expect("revoked tag record not found")
+                .data,
+        )
+        .unwrap();
+        assert_eq!(revoked_tag_record_account.index, Some(index));
+        assert_eq!(
+            revoked_tag_record_account.reason,
+            ContentSource::String(reason.into())
+        );
+    }
+
+    // Indexed records can not be claimed, since an owner could claim many times
+    let error = banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::claim_tag_record(
+                &ltag::id(),
+                &owner.pubkey(),
+                &factory,
+                1,
+                vec![],
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code)
+        )) if code == TagError::TagRecordFactoryIsIndexed as u32
+    ));
+}
+
+#[tokio::test]
+async fn fail_revoke_authority_cannot_withdraw() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let tag_authority = Keypair::new();
+    let tag = create_tag(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        "name",
+        &tag_authority,
+    )
+    .await;
+
+    let owner = Keypair::new();
+    let factory_authority = Keypair::new();
+    let factory = create_tag_record_factory(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &tag_authority,
+        &factory_authority,
+        false,
+        false,
+        None,
+    )
+    .await;
+
+    let tag_record = create_tag_record(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &tag,
+        &owner.pubkey(),
+        &factory,
+        &factory_authority,
+        1,
+    )
+    .await;
+
+    let error = banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::delete_tag_record_as_factory(
+                &ltag::id(),
+                &tag_record,
+                &owner.pubkey(),
+                &factory,
+                &factory_authority.pubkey(),
+                &payer.pubkey(),
+                &payer.pubkey(),
+                None,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code)
+        )) if code == TagError::AuthorityCannotWithdraw as u32
+    ));
+
+    // Nor invalidate the record by changing its status
+    let error = banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[ltag::instruction::update_tag_record_attributes(
+                &ltag::id(),
+                &tag_record,
+                &factory,
+                &factory_authority.pubkey(),
+                &payer.pubkey(),
+                None,
+                vec![],
+                TagRecordStatus::Suspended,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &factory_authority],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code)
+        )) if code == TagError::AuthorityCannotWithdraw as u32
+    ));
+}
 /*
 #[tokio::test]
 async fn fail_update_wrong_payer() {
@@ -23,6 +23,21 @@ pub enum UserInstruction {
         #[allow(dead_code)] // but it's not
         profile: Option<ContentSource>,
     },
+    TransferUser {
+        #[allow(dead_code)] // but it's not
+        new_owner: Pubkey,
+    },
+
+    // Moves the user to a new name, the old account forwards to the new one for a grace period
+    RenameUser {
+        #[allow(dead_code)] // but it's not
+        new_name: String,
+        #[allow(dead_code)] // but it's not
+        new_user_account_bump_seed: u8,
+    },
+
+    // Closes the user account and refunds the owner. Tombstones of renamed users can be closed by anyone after the grace period
+    DeleteUser,
 }
 
 /// Creates a create user transction
@@ -72,3 +87,72 @@ pub fn create_update_user_transaction(
         ],
     }
 }
+
+/// Creates a transfer user transaction
+pub fn create_transfer_user_transaction(
+    program_id: &Pubkey,
+    username: &str,
+    owner: &Pubkey,
+    new_owner: &Pubkey,
+) -> Instruction {
+    let (user_account, _) = find_user_account_program_address(program_id, username);
+    Instruction {
+        program_id: *program_id,
+        data: (UserInstruction::TransferUser {
+            new_owner: *new_owner,
+        })
+        .try_to_vec()
+        .unwrap(),
+        accounts: vec![
+            AccountMeta::new(user_account, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+    }
+}
+
+/// Creates a rename user transaction
+pub fn create_rename_user_transaction(
+    program_id: &Pubkey,
+    username: &str,
+    new_username: &str,
+    owner: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let (user_account, _) = find_user_account_program_address(program_id, username);
+    let (new_user_account, new_user_account_bump_seed) =
+        find_user_account_program_address(program_id, new_username);
+    Instruction {
+        program_id: *program_id,
+        data: (UserInstruction::RenameUser {
+            new_name: new_username.into(),
+            new_user_account_bump_seed,
+        })
+        .try_to_vec()
+        .unwrap(),
+        accounts: vec![
+            AccountMeta::new(user_account, false),
+            AccountMeta::new(new_user_account, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    }
+}
+
+/// Creates a delete user transaction, the owner does not have to sign for tombstones past the grace period
+pub fn create_delete_user_transaction(
+    program_id: &Pubkey,
+    username: &str,
+    owner: &Pubkey,
+    owner_signer: bool,
+) -> Instruction {
+    let (user_account, _) = find_user_account_program_address(program_id, username);
+    Instruction {
+        program_id: *program_id,
+        data: (UserInstruction::DeleteUser).try_to_vec().unwrap(),
+        accounts: vec![
+            AccountMeta::new(user_account, false),
+            AccountMeta::new(*owner, owner_signer),
+        ],
+    }
+}
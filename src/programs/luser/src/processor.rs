@@ -19,9 +19,12 @@ use crate::{
 
 use super::{
     instruction::UserInstruction,
-    state::{deserialize_user_account, UserAccount},
+    state::{
+        deserialize_user_account, get_user_account_for_signed_owner, UserAccount, UserRename,
+        RENAME_GRACE_PERIOD,
+    },
 };
-use shared::account::create_and_serialize_account_verify_with_bump;
+use shared::account::{create_and_serialize_account_verify_with_bump, dispose_account};
 
 pub struct Processor {}
 impl Processor {
@@ -64,6 +67,7 @@ impl Processor {
             profile,
             creation_timestamp: Clock::get()?.unix_timestamp as u64,
             owner: *owner_info.key, // payer becomes owner
+            renamed: None,
         };
 
         create_and_serialize_account_verify_with_bump(
@@ -79,6 +83,7 @@ impl Processor {
     }
 
     pub fn process_update_user(
+        program_id: &Pubkey,
         accounts: &[AccountInfo],
         profile: Option<ContentSource>,
     ) -> ProgramResult {
@@ -86,16 +91,117 @@ impl Processor {
 
         let user_account_info = next_account_info(accounts_iter)?;
         let owner_info = next_account_info(accounts_iter)?;
-        if !owner_info.is_signer {
-            return Err(ProgramError::MissingRequiredSignature);
+
+        let mut user =
+            get_user_account_for_signed_owner(program_id, user_account_info, owner_info)?;
+        user.profile = profile;
+        user.serialize(&mut *user_account_info.data.borrow_mut())?;
+        Ok(())
+    }
+
+    pub fn process_transfer_user(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        new_owner: Pubkey,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+
+        let user_account_info = next_account_info(accounts_iter)?;
+        let owner_info = next_account_info(accounts_iter)?;
+
+        let mut user =
+            get_user_account_for_signed_owner(program_id, user_account_info, owner_info)?;
+        user.owner = new_owner;
+        user.serialize(&mut *user_account_info.data.borrow_mut())?;
+        Ok(())
+    }
+
+    pub fn process_rename_user(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        new_name: String,
+        new_user_account_bump_seed: u8,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+
+        if !entity_name_is_valid(new_name.as_ref()) {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let user_account_info = next_account_info(accounts_iter)?;
+        let new_user_account_info = next_account_info(accounts_iter)?;
+        if !new_user_account_info.try_data_is_empty()? {
+            // Already exist
+            return Err(ProgramError::InvalidArgument);
+        }
+        let owner_info = next_account_info(accounts_iter)?;
+        let payer_info = next_account_info(accounts_iter)?;
+        let system_account = next_account_info(accounts_iter)?;
+
+        let mut user =
+            get_user_account_for_signed_owner(program_id, user_account_info, owner_info)?;
+
+        let rent = Rent::get()?;
+        let mut seeds = create_user_account_program_address_seeds(&new_name);
+        seeds.push(vec![new_user_account_bump_seed]);
+
+        let seed_slice = &seeds.iter().map(|x| &x[..]).collect::<Vec<&[u8]>>()[..];
+
+        let new_user = UserAccount {
+            name: new_name,
+            ..user.clone()
+        };
+
+        create_and_serialize_account_verify_with_bump(
+            payer_info,
+            new_user_account_info,
+            &new_user,
+            seed_slice,
+            program_id,
+            system_account,
+            &rent,
+        )?;
+
+        // Keep the old name as a tombstone, so links to it can be forwarded for a while
+        user.renamed = Some(UserRename {
+            new_user: *new_user_account_info.key,
+            expires_at: (Clock::get()?.unix_timestamp as u64)
+                .checked_add(RENAME_GRACE_PERIOD)
+                .unwrap(),
+        });
+        user.serialize(&mut *user_account_info.data.borrow_mut())?;
+        Ok(())
+    }
+
+    pub fn process_delete_user(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+
+        let user_account_info = next_account_info(accounts_iter)?;
+        let owner_info = next_account_info(accounts_iter)?;
+        if user_account_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
         }
 
-        let mut user = deserialize_user_account(*user_account_info.data.borrow())?;
+        let user = deserialize_user_account(*user_account_info.data.borrow())?;
         if &user.owner != owner_info.key {
             return Err(ProgramError::InvalidAccountData);
         }
-        user.profile = profile;
-        user.serialize(&mut *user_account_info.data.borrow_mut())?;
+
+        match &user.renamed {
+            // Tombstones are kept for the grace period, even if the owner signs
+            Some(renamed) => {
+                if (Clock::get()?.unix_timestamp as u64) < renamed.expires_at {
+                    return Err(ProgramError::InvalidArgument);
+                }
+            }
+            None => {
+                if !owner_info.is_signer {
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+            }
+        }
+
+        dispose_account(user_account_info, owner_info);
         Ok(())
     }
 
@@ -119,7 +225,27 @@ impl Processor {
             }
             UserInstruction::UpdateUser { profile } => {
                 msg!("Instruction: Update user");
-                Self::process_update_user(accounts, profile)
+                Self::process_update_user(program_id, accounts, profile)
+            }
+            UserInstruction::TransferUser { new_owner } => {
+                msg!("Instruction: Transfer user");
+                Self::process_transfer_user(program_id, accounts, new_owner)
+            }
+            UserInstruction::RenameUser {
+                new_name,
+                new_user_account_bump_seed,
+            } => {
+                msg!("Instruction: Rename user");
+                Self::process_rename_user(
+                    program_id,
+                    accounts,
+                    new_name,
+                    new_user_account_bump_seed,
+                )
+            }
+            UserInstruction::DeleteUser => {
+                msg!("Instruction: Delete user");
+                Self::process_delete_user(program_id, accounts)
             }
         }
     }
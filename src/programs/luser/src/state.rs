@@ -2,7 +2,10 @@ use std::io::Result;
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use shared::{account::MaxSize, content::ContentSource};
-use solana_program::{borsh::try_from_slice_unchecked, pubkey::Pubkey};
+use solana_program::{
+    account_info::AccountInfo, borsh::try_from_slice_unchecked, program_error::ProgramError,
+    pubkey::Pubkey,
+};
 
 pub const MAX_URI_LENGTH: usize = 200;
 pub const MAX_NAME_LENGTH: usize = 100;
@@ -12,8 +15,12 @@ pub const MAX_USER_LEN: usize = 32 // owner pubkey
     + MAX_NAME_LENGTH
     + 1  // option
     + MAX_URI_LENGTH
+    + 1 + 32 + 8 // renamed
     + 200; // some padding
 
+/// Seconds the old username forwards to the new one after a rename, before the old name can be released
+pub const RENAME_GRACE_PERIOD: u64 = 30 * 24 * 60 * 60;
+
 #[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
 pub enum AccountType {
     User,
@@ -26,6 +33,18 @@ pub struct UserAccount {
     pub creation_timestamp: u64,
     pub name: String,
     pub profile: Option<ContentSource>, // The link to the profile data
+
+    /// Set when the user has been renamed, the account is then only a tombstone for the old name
+    pub renamed: Option<UserRename>,
+}
+
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
+pub struct UserRename {
+    /// The user account of the new name
+    pub new_user: Pubkey,
+
+    /// End of the grace period, after it anyone can close the tombstone and release the old name
+    pub expires_at: u64,
 }
 
 impl MaxSize for UserAccount {
@@ -38,3 +57,23 @@ pub fn deserialize_user_account(data: &[u8]) -> Result<UserAccount> {
     let user_account: UserAccount = try_from_slice_unchecked(data)?;
     Ok(user_account)
 }
+
+/// Deserializes the user account and checks the owner signed, tombstones of renamed users can not be used
+pub fn get_user_account_for_signed_owner(
+    program_id: &Pubkey,
+    user_account_info: &AccountInfo,
+    owner_info: &AccountInfo,
+) -> std::result::Result<UserAccount, ProgramError> {
+    if !owner_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if user_account_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let user = deserialize_user_account(*user_account_info.data.borrow())?;
+    if &user.owner != owner_info.key || user.renamed.is_some() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(user)
+}
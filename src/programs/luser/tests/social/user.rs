@@ -1,6 +1,8 @@
 use luser::find_user_account_program_address;
 use luser::instruction::{
-    create_update_user_transaction, create_user_transaction, UserInstruction,
+    create_delete_user_transaction, create_rename_user_transaction,
+    create_transfer_user_transaction, create_update_user_transaction, create_user_transaction,
+    UserInstruction,
 };
 use luser::state::deserialize_user_account;
 use solana_program::hash::Hash;
@@ -255,3 +257,219 @@ async fn fail_already_exist() {
         ))
     ));
 }
+
+#[tokio::test]
+async fn success_transfer() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let username = "name";
+    let owner = Keypair::new();
+    let new_owner = Keypair::new();
+    let user_account_address = create_and_verify_user(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        username,
+        "profile",
+        &owner,
+    )
+    .await;
+
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[create_transfer_user_transaction(
+                &luser::id(),
+                username,
+                &owner.pubkey(),
+                &new_owner.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    let user_account_info = banks_client
+        .get_account(user_account_address)
+        .await
+        .expect("get_user")
+        .expect("user not found");
+    let user = deserialize_user_account(&user_account_info.data).unwrap();
+    assert_eq!(user.owner, new_owner.pubkey());
+
+    // Old owner can no longer update
+    let err = banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[create_update_user_transaction(
+                &luser::id(),
+                username,
+                Some("updated_profile".into()),
+                &owner.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap_err();
+    match err {
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidAccountData,
+        )) => {}
+        _ => panic!("Wrong error type"),
+    }
+}
+
+#[tokio::test]
+async fn success_rename() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let username = "name";
+    let new_username = "new_name";
+    let owner = Keypair::new();
+    let user_account_address = create_and_verify_user(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        username,
+        "profile",
+        &owner,
+    )
+    .await;
+
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[create_rename_user_transaction(
+                &luser::id(),
+                username,
+                new_username,
+                &owner.pubkey(),
+                &payer.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    // New user keeps owner and profile
+    let new_user_account_address = find_user_account_program_address(&luser::id(), new_username).0;
+    let new_user_account_info = banks_client
+        .get_account(new_user_account_address)
+        .await
+        .expect("get_user")
+        .expect("user not found");
+    let new_user = deserialize_user_account(&new_user_account_info.data).unwrap();
+    assert_eq!(new_user.name, new_username);
+    assert_eq!(new_user.owner, owner.pubkey());
+    assert_eq!(new_user.profile, Some("profile".into()));
+    assert_eq!(new_user.renamed, None);
+
+    // Old user forwards to the new one
+    let user_account_info = banks_client
+        .get_account(user_account_address)
+        .await
+        .expect("get_user")
+        .expect("user not found");
+    let user = deserialize_user_account(&user_account_info.data).unwrap();
+    assert_eq!(
+        user.renamed.map(|renamed| renamed.new_user),
+        Some(new_user_account_address)
+    );
+
+    // The tombstone can not be updated
+    let err = banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[create_update_user_transaction(
+                &luser::id(),
+                username,
+                Some("updated_profile".into()),
+                &owner.pubkey(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap_err();
+    match err {
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidAccountData,
+        )) => {}
+        _ => panic!("Wrong error type"),
+    }
+
+    // Nor closed before the grace period has passed
+    let err = banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[create_delete_user_transaction(
+                &luser::id(),
+                username,
+                &owner.pubkey(),
+                true,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap_err();
+    match err {
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidArgument,
+        )) => {}
+        _ => panic!("Wrong error type"),
+    }
+}
+
+#[tokio::test]
+async fn success_delete() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let username = "name";
+    let owner = Keypair::new();
+    let user_account_address = create_and_verify_user(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        username,
+        "profile",
+        &owner,
+    )
+    .await;
+    let user_rent = banks_client
+        .get_account(user_account_address)
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+
+    banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[create_delete_user_transaction(
+                &luser::id(),
+                username,
+                &owner.pubkey(),
+                true,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    assert!(banks_client
+        .get_account(user_account_address)
+        .await
+        .unwrap()
+        .is_none());
+
+    // Rent is refunded to the owner
+    assert_eq!(
+        banks_client.get_balance(owner.pubkey()).await.unwrap(),
+        user_rent
+    );
+}
@@ -333,6 +333,66 @@ pub fn assert_is_valid_account_of_types<T: BorshDeserialize + PartialEq, F: Fn(&
 }
  */
 
+/// Resizes the account to the serialized size of the data and serializes the data into it
+/// The payer tops up the account to stay rent exempt if the account grows
+pub fn resize_and_serialize_account<'a, T: BorshSerialize>(
+    payer_info: &AccountInfo<'a>,
+    account_info: &AccountInfo<'a>,
+    account_data: &T,
+    system_info: &AccountInfo<'a>,
+    rent: &Rent,
+) -> Result<(), ProgramError> {
+    resize_and_serialize_account_with_padding(
+        payer_info,
+        account_info,
+        account_data,
+        0,
+        system_info,
+        rent,
+    )
+}
+
+/// Same as resize_and_serialize_account, but keeps padding bytes after the serialized data
+/// for accounts that are later serialized in place and can grow
+pub fn resize_and_serialize_account_with_padding<'a, T: BorshSerialize>(
+    payer_info: &AccountInfo<'a>,
+    account_info: &AccountInfo<'a>,
+    account_data: &T,
+    padding: usize,
+    system_info: &AccountInfo<'a>,
+    rent: &Rent,
+) -> Result<(), ProgramError> {
+    check_system_program(system_info.key)?;
+
+    let mut serialized_data = account_data.try_to_vec()?;
+    serialized_data.resize(serialized_data.len().checked_add(padding).unwrap(), 0);
+    let account_size = serialized_data.len();
+
+    let minimum_balance = rent.minimum_balance(account_size);
+    if account_info.lamports() < minimum_balance {
+        invoke(
+            &system_instruction::transfer(
+                payer_info.key,
+                account_info.key,
+                minimum_balance - account_info.lamports(),
+            ),
+            &[
+                payer_info.clone(),
+                account_info.clone(),
+                system_info.clone(),
+            ],
+        )?;
+    }
+
+    account_info.realloc(account_size, false)?;
+    account_info
+        .data
+        .borrow_mut()
+        .copy_from_slice(&serialized_data);
+
+    Ok(())
+}
+
 /// Disposes account by transferring its lamports to the beneficiary account and zeros its data
 // After transaction completes the runtime would remove the account with no lamports
 pub fn dispose_account(account_info: &AccountInfo, beneficiary_info: &AccountInfo) {
@@ -24,6 +24,10 @@ pub enum UtilsError {
     /// Invalid Account type
     #[error("Invalid Account type")]
     InvalidAccountType,
+
+    /// Invalid associated token account
+    #[error("Invalid associated token account")]
+    InvalidAssociatedTokenAccount,
 }
 
 impl PrintProgramError for UtilsError {
@@ -1,4 +1,5 @@
 pub mod content;
 pub mod error;
 pub mod account;
-pub mod seeds;
\ No newline at end of file
+pub mod seeds;
+pub mod token;
\ No newline at end of file
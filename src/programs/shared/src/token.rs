@@ -0,0 +1,57 @@
+//! SPL token account helpers
+
+use arrayref::array_ref;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    program_pack::Pack, pubkey::Pubkey,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::{account::check_data_len, error::UtilsError};
+
+/// Returns Tokens balance.
+/// Extrats balance field without unpacking entire struct.
+pub fn get_token_balance(token_account: &AccountInfo) -> Result<u64, ProgramError> {
+    let data = token_account.try_borrow_data()?;
+    check_data_len(&data, spl_token::state::Account::get_packed_len())?;
+    let amount = array_ref![data, 64, 8];
+
+    Ok(u64::from_le_bytes(*amount))
+}
+
+/// Returns Token account owner.
+/// Extrats owner field without unpacking entire struct.
+pub fn get_token_account_owner(token_account: &AccountInfo) -> Result<Pubkey, ProgramError> {
+    let data = token_account.try_borrow_data()?;
+    check_data_len(&data, spl_token::state::Account::get_packed_len())?;
+    let owner = array_ref![data, 32, 32];
+
+    Ok(Pubkey::new_from_array(*owner))
+}
+
+/// Returns Token account mint.
+/// Extrats mint field without unpacking entire struct.
+pub fn get_token_account_mint(token_account: &AccountInfo) -> Result<Pubkey, ProgramError> {
+    let data = token_account.try_borrow_data()?;
+    check_data_len(&data, spl_token::state::Account::get_packed_len())?;
+    let mint = array_ref![data, 0, 32];
+
+    Ok(Pubkey::new_from_array(*mint))
+}
+
+/// Asserts the token account is the associated token account of the owner for the mint
+pub fn assert_is_associated_token_account(
+    token_account: &AccountInfo,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> ProgramResult {
+    if token_account.owner != &spl_token::id() {
+        return Err(UtilsError::InvalidAccountOwner.into());
+    }
+
+    if token_account.key != &get_associated_token_address(owner, mint) {
+        return Err(UtilsError::InvalidAssociatedTokenAccount.into());
+    }
+
+    Ok(())
+}